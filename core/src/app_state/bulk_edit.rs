@@ -0,0 +1,139 @@
+//! Editing several objects of the workspace at once.
+
+use std::fmt::Display;
+
+use crate::{
+    data_type_registry::{Location, Locations, MaybeDOType, MaybeProfile, Policy, Version},
+    pidinfo::PidInfo,
+};
+
+/// The value of an attribute over all selected objects.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Shared<T> {
+    Same(T),
+    Mixed,
+}
+
+impl<T: PartialEq + Clone> Shared<T> {
+    /// `Same` if all values are equal. No values at all count as mixed.
+    pub fn of(mut values: impl Iterator<Item = T>) -> Self {
+        let first = match values.next() {
+            Some(first) => first,
+            None => return Shared::Mixed,
+        };
+        if values.all(|value| value == first) {
+            Shared::Same(first)
+        } else {
+            Shared::Mixed
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Shared<U> {
+        match self {
+            Shared::Same(value) => Shared::Same(f(value)),
+            Shared::Mixed => Shared::Mixed,
+        }
+    }
+}
+
+/// What happens to the locations of the selected objects.
+#[derive(Clone, Debug, Default, EnumIter, PartialEq, Eq)]
+pub enum LocationsChange {
+    #[default]
+    Keep,
+    /// Adds the locations which the object does not have yet.
+    Append(Vec<String>),
+    Replace(Vec<String>),
+}
+
+/// Associates changes with their Display name (for the user interface).
+impl Display for LocationsChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocationsChange::Keep => write!(f, "Keep locations"),
+            LocationsChange::Append(_) => write!(f, "Append locations"),
+            LocationsChange::Replace(_) => write!(f, "Replace locations"),
+        }
+    }
+}
+
+/// Changes for several objects. Attributes which are `None` stay as they are.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BulkEdit {
+    pub profile: Option<MaybeProfile>,
+    pub digital_object_type: Option<MaybeDOType>,
+    pub policy: Option<Policy>,
+    pub locations: LocationsChange,
+    pub version: Option<Version>,
+}
+
+impl BulkEdit {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Applies the changes to the local state of the object
+    /// and returns if the object changed. Changed objects are marked as modified.
+    pub fn apply(&self, info: &mut PidInfo) -> bool {
+        let before = info.as_record();
+        if let Some(profile) = &self.profile {
+            info.profile = profile.clone();
+        }
+        if let Some(digital_object_type) = &self.digital_object_type {
+            info.digital_object_type = digital_object_type.clone();
+        }
+        if let Some(policy) = &self.policy {
+            info.policy = policy.clone();
+        }
+        match &self.locations {
+            LocationsChange::Keep => {}
+            LocationsChange::Append(urls) => {
+                for url in urls {
+                    if !info.locations.urls().contains(&url.as_str()) {
+                        info.locations.0.push(Location::new(url.clone()));
+                    }
+                }
+            }
+            LocationsChange::Replace(urls) => info.locations = Locations::from_urls(urls.clone()),
+        }
+        if let Some(version) = &self.version {
+            info.version = version.clone();
+        }
+        let changed = !before.same_content_like(&info.as_record());
+        info.update_state();
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pidinfo::State;
+
+    #[test]
+    fn shared_values() {
+        assert_eq!(Shared::of(vec![1, 1].into_iter()), Shared::Same(1));
+        assert_eq!(Shared::of(vec![1, 2].into_iter()), Shared::Mixed);
+        assert_eq!(Shared::<u8>::of(Vec::new().into_iter()), Shared::Mixed);
+    }
+
+    #[test]
+    fn applies_changes_and_marks_modified() {
+        let mut info = PidInfo::default();
+        info.locations = Locations::from_urls(vec!["https://example.org/a".into()]);
+        let mut info = PidInfo::from_registered(info.as_record());
+
+        let edit = BulkEdit {
+            locations: LocationsChange::Append(vec!["https://example.org/a".into(), "https://example.org/b".into()]),
+            version: Some(Version("2.0.0".into())),
+            ..BulkEdit::default()
+        };
+        assert!(edit.apply(&mut info));
+        assert_eq!(info.state(), State::Modified);
+        assert_eq!(info.locations.urls(), vec!["https://example.org/a", "https://example.org/b"]);
+
+        let mut clean = PidInfo::from_registered(info.as_record());
+        assert!(!edit.apply(&mut clean));
+        assert_eq!(clean.state(), State::Clean);
+    }
+}
@@ -3,19 +3,23 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::collection_service::collection::Collection;
 use crate::strum::IntoEnumIterator;
 
-#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub struct DataID(pub u16);
 
-#[derive(Debug, PartialEq, Eq, Clone, EnumIter)]
+#[derive(Debug, PartialEq, Eq, Clone, EnumIter, Serialize, Deserialize)]
+#[serde(tag = "type", content = "content")]
 pub enum Data {
     AnnotatedImage(AnnotatedImage),
-    Collection(Collection),
+    Collection(Box<Collection>),
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AnnotatedImage {
     pub url: String,
     pub annotation_urls: Vec<String>,
@@ -50,7 +54,7 @@ impl TryFrom<String> for DataID {
     type Error = ();
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        value.parse().map(|ok| Self(ok)).map_err(|_error| ())
+        value.parse().map(Self).map_err(|_error| ())
     }
 }
 
@@ -0,0 +1,68 @@
+//! Starting new objects from existing ones.
+
+use super::data::Data;
+use crate::{
+    data_type_registry::{
+        DateCreated, DateModified, Etag, HasNewVersion, IsNewVersionOf, ObjectStatus, SupersededBy, Tombstone,
+    },
+    pidinfo::PidInfo,
+};
+
+/// A new unregistered draft with the local state of the given object.
+/// The PID, the dates and the etag are reset. The copy is neither a version of the
+/// original nor retired, so version links and lifecycle attributes are not copied.
+/// Data is not linked, see `duplicate_data`.
+pub fn duplicate(info: &PidInfo) -> PidInfo {
+    let mut record = info.as_record();
+    record.pid = String::new();
+    let mut copy = PidInfo::from_unregistered(record);
+    copy.date_created = DateCreated::default();
+    copy.date_modified = DateModified::default();
+    copy.etag = Etag::default();
+    copy.is_new_version_of = IsNewVersionOf(None);
+    copy.has_new_version = HasNewVersion(None);
+    copy.status = ObjectStatus::Active;
+    copy.tombstone = Tombstone(None);
+    copy.superseded_by = SupersededBy(None);
+    copy
+}
+
+/// A deep copy of the data. Copies of collections have no id yet
+/// and need to be registered at the collection service.
+pub fn duplicate_data(data: &Data) -> Data {
+    match data {
+        Data::AnnotatedImage(image) => Data::AnnotatedImage(image.clone()),
+        Data::Collection(collection) => Data::Collection(Box::new(collection.as_new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data_type_registry::{Locations, Pid, Version},
+        pidinfo::State,
+    };
+
+    #[test]
+    fn duplicates_are_unregistered_drafts() {
+        let mut original = PidInfo::default();
+        *original.pid_mut() = "21.T11148/original".into();
+        original.locations = Locations::from_urls(vec!["https://example.org/a".into()]);
+        original.version = Version("2.1.0".into());
+        original.is_new_version_of = IsNewVersionOf(Some(Pid("21.T11148/older".into())));
+        original.status = ObjectStatus::Deprecated;
+        original.etag = Etag("\"abc\"".into());
+        let original = PidInfo::from_registered(original.as_record());
+
+        let copy = duplicate(&original);
+        assert_eq!(copy.pid(), "");
+        assert_eq!(copy.state(), State::Unregistered);
+        assert_eq!(copy.locations, original.locations);
+        assert_eq!(copy.version, original.version);
+        assert_eq!(copy.is_new_version_of, IsNewVersionOf(None));
+        assert_eq!(copy.status, ObjectStatus::Active);
+        assert_eq!(copy.etag, Etag::default());
+        assert_eq!(copy.data, None);
+    }
+}
@@ -0,0 +1,146 @@
+//! The end of an object's life: deprecating or withdrawing it,
+//! leaving a tombstone and pointing to a successor.
+
+use std::fmt::Display;
+
+use crate::{
+    data_type_registry::{Locations, ObjectStatus, Pid, SupersededBy, Tombstone},
+    pidinfo::PidInfo,
+};
+
+/// What happens to the locations of an object when its status changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum LocationHandling {
+    #[default]
+    Keep,
+    /// Removes all locations, i.e. because the data was deleted.
+    Clear,
+    /// Replaces all locations with the given one, i.e. a landing page or the successor.
+    Redirect(String),
+}
+
+/// Associates handlings with their Display name (for the user interface).
+impl Display for LocationHandling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocationHandling::Keep => write!(f, "Keep locations"),
+            LocationHandling::Clear => write!(f, "Clear locations"),
+            LocationHandling::Redirect(_) => write!(f, "Redirect locations"),
+        }
+    }
+}
+
+/// A change of the lifecycle status of an object.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Retirement {
+    pub status: ObjectStatus,
+    pub tombstone: Option<String>,
+    pub superseded_by: Option<Pid>,
+    pub locations: LocationHandling,
+}
+
+impl Retirement {
+    /// The current lifecycle state of the given object.
+    pub fn of(info: &PidInfo) -> Self {
+        Self {
+            status: info.status,
+            tombstone: info.tombstone.0.clone(),
+            superseded_by: info.superseded_by.0.clone(),
+            locations: LocationHandling::Keep,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(pid) = &self.superseded_by {
+            if pid.is_empty() {
+                return Err("The successor PID is empty.".into());
+            }
+        }
+        if let LocationHandling::Redirect(url) = &self.locations {
+            if url.trim().is_empty() {
+                return Err("The redirect location is empty.".into());
+            }
+        }
+        if self.status == ObjectStatus::Withdrawn && self.tombstone.is_none() {
+            return Err("Withdrawn objects need a tombstone text.".into());
+        }
+        Ok(())
+    }
+
+    /// Applies the change to the local state of the object.
+    /// Reactivating an object removes tombstone and successor.
+    pub fn apply(self, info: &mut PidInfo) -> Result<(), String> {
+        self.validate()?;
+        info.status = self.status;
+        if self.status.is_tombstoned() {
+            info.tombstone = Tombstone(self.tombstone);
+            info.superseded_by = SupersededBy(self.superseded_by);
+        } else {
+            info.tombstone = Tombstone::default();
+            info.superseded_by = SupersededBy::default();
+        }
+        match self.locations {
+            LocationHandling::Keep => {}
+            LocationHandling::Clear => info.locations = Locations::default(),
+            LocationHandling::Redirect(url) => info.locations = Locations::from_urls(vec![url]),
+        }
+        info.update_state();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn withdrawing_writes_tombstone_and_redirects() {
+        let mut info = PidInfo::default();
+        info.locations = Locations::from_urls(vec!["https://example.com/data".into()]);
+        let successor = Pid("21.T11148/successor".into());
+        let retirement = Retirement {
+            status: ObjectStatus::Withdrawn,
+            tombstone: Some("Replaced by a corrected version.".into()),
+            superseded_by: Some(successor.clone()),
+            locations: LocationHandling::Redirect(successor.to_url()),
+        };
+        retirement.apply(&mut info).unwrap();
+
+        let restored = PidInfo::from_registered(info.as_record());
+        assert_eq!(restored.status, ObjectStatus::Withdrawn);
+        assert_eq!(restored.superseded_by, SupersededBy(Some(successor.clone())));
+        assert_eq!(restored.locations, Locations::from_urls(vec![successor.to_url()]));
+        assert!(restored.published_status().is_tombstoned());
+
+        let mut info = restored;
+        Retirement::default().apply(&mut info).unwrap();
+        assert_eq!(info.tombstone, Tombstone(None));
+        assert_eq!(info.as_record().entries.get("21.T11148/objectStatus"), None);
+    }
+
+    #[test]
+    fn status_stays_local_if_draft_types_are_not_published() {
+        let mut info = PidInfo::default();
+        let retirement = Retirement {
+            status: ObjectStatus::Deprecated,
+            tombstone: Some("Use the new version.".into()),
+            ..Default::default()
+        };
+        retirement.apply(&mut info).unwrap();
+
+        let published = PidInfo::from_published(info.as_record().without_draft_types(), &info);
+        assert_eq!(published.published_status(), ObjectStatus::Active);
+        assert_eq!(published.status, ObjectStatus::Deprecated);
+        assert_eq!(published.tombstone, Tombstone(Some("Use the new version.".into())));
+        assert!(published.status_unpublished());
+    }
+
+    #[test]
+    fn withdrawing_requires_tombstone() {
+        let retirement = Retirement {
+            status: ObjectStatus::Withdrawn,
+            ..Default::default()
+        };
+        assert!(retirement.apply(&mut PidInfo::default()).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! The state of objects and their data, independent of the agents holding it.
+
+pub mod data;
+pub mod workspace;
+pub mod lifecycle;
+pub mod versioning;
+pub mod bulk_edit;
+pub mod templates;
+pub mod duplicate;
+pub mod outbox;
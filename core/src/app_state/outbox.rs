@@ -0,0 +1,175 @@
+//! Changes which could not be published, because a service was unreachable.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::data::DataID;
+use crate::{collection_service::collection::Collection, data_type_registry::Pid, service_communication::PidRecord};
+
+/// A change to publish at a service.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum Operation {
+    /// Registers the record. Its PID is the local one of the draft.
+    Register(PidRecord),
+    Update(PidRecord),
+    /// Pushes the collection of the data with the ETag of the last pull.
+    Push(DataID, Box<Collection>, String),
+}
+
+impl Operation {
+    /// The object the operation is about, if it is a record.
+    pub fn pid(&self) -> Option<Pid> {
+        match self {
+            Operation::Register(record) | Operation::Update(record) => Some(Pid(record.pid.clone())),
+            Operation::Push(..) => None,
+        }
+    }
+
+    /// The data the operation is about, if it is a collection.
+    pub fn data(&self) -> Option<DataID> {
+        match self {
+            Operation::Push(id, ..) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Whether publishing this operation makes the other one unnecessary,
+    /// as both publish the latest state of the same thing.
+    fn replaces(&self, other: &Operation) -> bool {
+        match (self, other) {
+            (Operation::Register(_), Operation::Register(_)) | (Operation::Update(_), Operation::Update(_)) => {
+                self.pid() == other.pid()
+            }
+            (Operation::Push(a, ..), Operation::Push(b, ..)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// An operation waiting to be published.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Pending {
+    pub operation: Operation,
+    /// Why the last attempt failed.
+    pub reason: String,
+    /// How often it was tried.
+    pub attempts: u32,
+}
+
+/// The operations waiting to be published, in the order they were queued.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Outbox {
+    pending: Vec<Pending>,
+    /// The operations taken by the last `take_all`, so their attempts are counted on
+    /// if they fail again.
+    #[serde(skip)]
+    replaying: Vec<Pending>,
+}
+
+impl Outbox {
+    /// Adds the operation. An older one for the same thing is replaced, keeping its place.
+    pub fn queue(&mut self, operation: Operation, reason: String) {
+        match self.pending.iter_mut().find(|p| operation.replaces(&p.operation)) {
+            Some(pending) => {
+                pending.operation = operation;
+                pending.reason = reason;
+                pending.attempts += 1;
+            }
+            None => {
+                let replayed = self.replaying.iter().position(|p| operation.replaces(&p.operation));
+                let attempts = replayed.map_or(0, |index| self.replaying.remove(index).attempts);
+                self.pending.push(Pending {
+                    operation,
+                    reason,
+                    attempts: attempts + 1,
+                })
+            }
+        }
+    }
+
+    /// Removes all operations, i.e. to publish them again.
+    /// Operations which fail again have to be queued again, and keep counting their attempts.
+    pub fn take_all(&mut self) -> Vec<Pending> {
+        self.replaying = self.pending.clone();
+        std::mem::take(&mut self.pending)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Pending> {
+        self.pending.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// The objects with unpublished changes.
+    pub fn pending_pids(&self) -> HashSet<Pid> {
+        self.pending.iter().filter_map(|p| p.operation.pid()).collect()
+    }
+
+    /// The data with unpublished changes.
+    pub fn pending_data(&self) -> HashSet<DataID> {
+        self.pending.iter().filter_map(|p| p.operation.data()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(pid: &str) -> PidRecord {
+        PidRecord {
+            pid: pid.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn newer_operations_replace_older_ones() {
+        let mut outbox = Outbox::default();
+        outbox.queue(Operation::Update(record("a")), "offline".into());
+        outbox.queue(Operation::Register(record("b")), "offline".into());
+        outbox.queue(Operation::Push(DataID(1), Box::default(), "etag".into()), "offline".into());
+        outbox.queue(Operation::Update(record("a")), "timed out".into());
+        outbox.queue(Operation::Push(DataID(1), Box::default(), "etag".into()), "offline".into());
+
+        assert_eq!(outbox.len(), 3);
+        let first = outbox.iter().next().unwrap();
+        assert_eq!(first.operation.pid(), Some(Pid("a".into())));
+        assert_eq!((first.reason.as_str(), first.attempts), ("timed out", 2));
+        assert_eq!(outbox.pending_pids(), vec![Pid("a".into()), Pid("b".into())].into_iter().collect());
+        assert_eq!(outbox.pending_data(), vec![DataID(1)].into_iter().collect());
+    }
+
+    #[test]
+    fn replays_keep_counting_attempts() {
+        let mut outbox = Outbox::default();
+        outbox.queue(Operation::Update(record("a")), "offline".into());
+        for attempts in 2..=3 {
+            for pending in outbox.take_all() {
+                outbox.queue(pending.operation, "still offline".into());
+            }
+            assert_eq!(outbox.iter().next().unwrap().attempts, attempts);
+        }
+        outbox.take_all();
+        outbox.take_all();
+        outbox.queue(Operation::Update(record("a")), "offline".into());
+        assert_eq!(outbox.iter().next().unwrap().attempts, 1);
+    }
+
+    #[test]
+    fn survives_storage() {
+        let mut outbox = Outbox::default();
+        outbox.queue(Operation::Register(record("draft")), "offline".into());
+        let stored = serde_json::to_string(&outbox).unwrap();
+        let mut restored: Outbox = serde_json::from_str(&stored).unwrap();
+        let taken = restored.take_all();
+        assert!(restored.is_empty());
+        assert_eq!(taken[0].operation.pid(), Some(Pid("draft".into())));
+    }
+}
@@ -0,0 +1,187 @@
+//! Templates for new drafts.
+//!
+//! A template is the local state of an object, optionally with its data.
+//! String values may contain placeholders, which are replaced when a draft is created.
+
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+use super::{
+    data::Data,
+    duplicate::{duplicate, duplicate_data},
+};
+use crate::{
+    data_type_registry::{DateCreated, DateModified, HasProfileKey},
+    pidinfo::PidInfo,
+    service_communication::PidRecord,
+};
+
+/// The version of the template files written by this client.
+pub const FORMAT_VERSION: u32 = 1;
+
+pub const NOW: &str = "{now}";
+pub const UUID: &str = "{uuid}";
+
+/// Placeholders and what they are replaced with (for the user interface).
+pub const PLACEHOLDERS: [(&str, &str); 2] = [
+    (NOW, "the date and time the draft is created"),
+    (UUID, "a random UUID, the same for all values of one draft"),
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Template {
+    pub name: String,
+    /// The local state of the object, without PID.
+    pub record: PidRecord,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Data>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateFile {
+    pub format_version: u32,
+    pub templates: Vec<Template>,
+}
+
+impl Template {
+    /// Creates a template from a copy of the object (see `duplicate`).
+    /// Dates are replaced by `{now}`, so drafts get the date of their creation.
+    pub fn from_object(name: String, info: &PidInfo, data: Option<Data>) -> Self {
+        let mut record = duplicate(info).as_record();
+        for key in [DateCreated::get_key(), DateModified::get_key()].iter() {
+            for entry in record.entries.get_mut(&key.0).into_iter().flatten() {
+                entry.value = json::Value::String(NOW.into());
+            }
+        }
+        let data = data.as_ref().map(duplicate_data);
+        Self { name, record, data }
+    }
+
+    /// The placeholders used in this template.
+    pub fn placeholders(&self) -> Vec<&'static str> {
+        let content = json::to_string(self).unwrap_or_default();
+        PLACEHOLDERS
+            .iter()
+            .map(|(placeholder, _)| *placeholder)
+            .filter(|placeholder| content.contains(placeholder))
+            .collect()
+    }
+
+    /// Creates a new unregistered draft (without PID) and its data from this template.
+    pub fn instantiate(&self, now: &str, uuid: &str) -> (PidInfo, Option<Data>) {
+        let mut record = self.record.clone();
+        for entry in record.entries.values_mut().flatten() {
+            expand(&mut entry.value, now, uuid);
+        }
+        let data = self.data.as_ref().and_then(|data| {
+            let mut value = json::to_value(data).ok()?;
+            expand(&mut value, now, uuid);
+            json::from_value(value)
+                .map_err(|e| log::error!("Data of template could not be restored: {}", e))
+                .ok()
+        });
+        (PidInfo::from_unregistered(record), data)
+    }
+}
+
+/// Replaces the placeholders in all strings of the value.
+fn expand(value: &mut json::Value, now: &str, uuid: &str) {
+    match value {
+        json::Value::String(s) => *s = s.replace(NOW, now).replace(UUID, uuid),
+        json::Value::Array(values) => values.iter_mut().for_each(|v| expand(v, now, uuid)),
+        json::Value::Object(map) => map.values_mut().for_each(|v| expand(v, now, uuid)),
+        _ => {}
+    }
+}
+
+/// A random (version 4) UUID.
+pub fn random_uuid() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+impl TemplateFile {
+    pub fn new(templates: Vec<Template>) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            templates,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        json::to_string_pretty(self).expect("Template file can always be serialized.")
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        let file: Self = json::from_str(s).map_err(|e| format!("Not a valid template file: {}", e))?;
+        if file.format_version > FORMAT_VERSION {
+            return Err(format!(
+                "Template file version {} is not supported (supported: {}).",
+                file.format_version, FORMAT_VERSION
+            ));
+        }
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data_type_registry::{DateTimeHandle, IsNewVersionOf, Locations, ObjectStatus, Pid, Tombstone},
+        pidinfo::State,
+    };
+    use std::convert::TryFrom;
+
+    #[test]
+    fn placeholders_are_expanded() {
+        let mut info = PidInfo::default();
+        *info.pid_mut() = "21.T11148/original".into();
+        info.locations = Locations::from_urls(vec!["https://example.org/{uuid}".into()]);
+        let template = Template::from_object("Page".into(), &info, None);
+        assert_eq!(template.record.pid, "");
+        assert_eq!(template.placeholders(), vec![NOW, UUID]);
+
+        let now = "2021-03-04T05:06:07+00:00";
+        let (draft, data) = template.instantiate(now, "1234");
+        assert_eq!(draft.state(), State::Unregistered);
+        assert_eq!(draft.locations.urls(), vec!["https://example.org/1234"]);
+        assert_eq!(draft.date_created.0, DateTimeHandle::try_from(&now.to_string()).unwrap());
+        assert_eq!(data, None);
+    }
+
+    #[test]
+    fn templates_of_retired_versions_are_active() {
+        let mut info = PidInfo::default();
+        *info.pid_mut() = "21.T11148/original".into();
+        info.is_new_version_of = IsNewVersionOf(Some(Pid("21.T11148/older".into())));
+        info.status = ObjectStatus::Withdrawn;
+        info.tombstone = Tombstone(Some("Retracted.".into()));
+        let template = Template::from_object("Page".into(), &info, None);
+        let (draft, _) = template.instantiate("2021-03-04T05:06:07+00:00", "1234");
+        assert_eq!(draft.is_new_version_of, IsNewVersionOf(None));
+        assert_eq!(draft.status, ObjectStatus::Active);
+        assert_eq!(draft.tombstone, Tombstone(None));
+    }
+
+    #[test]
+    fn random_uuids_have_version_4() {
+        let uuid = random_uuid();
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4");
+        assert_ne!(uuid, random_uuid());
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let json = r#"{ "formatVersion": 99, "templates": [] }"#;
+        assert!(TemplateFile::from_json(json).is_err());
+        let file = TemplateFile::new(vec![]);
+        assert_eq!(TemplateFile::from_json(&file.to_json()), Ok(file));
+    }
+}
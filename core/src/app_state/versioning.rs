@@ -0,0 +1,163 @@
+//! Versions of an object are separate objects, linked by
+//! `isNewVersionOf` (to the predecessor) and `hasNewVersion` (to the successor).
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    data_type_registry::*,
+    pidinfo::{PidInfo, State},
+    service_communication::PidRecord,
+};
+
+/// Creates an unregistered draft for the next version of a registered object.
+/// The draft links back to the object and the caller assigns its PID.
+/// The object is linked to the draft once the draft is registered, see `link_predecessor`.
+pub fn new_version(known: &HashMap<Pid, PidInfo>, info: &PidInfo, bump: Bump) -> Result<PidInfo, String> {
+    if info.state() == State::Unregistered {
+        return Err("Only registered objects can have a new version.".into());
+    }
+    if let Some(next) = successor(known, info) {
+        return Err(format!("This object already has a new version: {}", next.0));
+    }
+    let version = info.version.bump(bump)?;
+    let mut draft = info.clone();
+    draft.record = PidRecord::default();
+    *draft.state_mut() = State::Unregistered;
+    draft.version = version;
+    draft.is_new_version_of = IsNewVersionOf(Some(Pid(info.pid().clone())));
+    draft.has_new_version = HasNewVersion::default();
+    draft.date_created = DateCreated::default();
+    draft.date_modified = DateModified::default();
+    draft.status = ObjectStatus::default();
+    draft.tombstone = Tombstone::default();
+    draft.superseded_by = SupersededBy::default();
+    Ok(draft)
+}
+
+/// Replaces links to `old` with links to `new`, i.e. after a draft was registered.
+/// Returns true if any link was changed.
+pub fn replace_links(info: &mut PidInfo, old: &Pid, new: &Pid) -> bool {
+    let mut changed = false;
+    let links = vec![
+        &mut info.is_new_version_of.0,
+        &mut info.has_new_version.0,
+        &mut info.superseded_by.0,
+    ];
+    for link in links.into_iter().flatten() {
+        if *link == *old {
+            *link = new.clone();
+            changed = true;
+        }
+    }
+    if changed {
+        info.update_state();
+    }
+    changed
+}
+
+/// The next version of the object: the published one, or a draft in the workspace.
+pub fn successor(known: &HashMap<Pid, PidInfo>, info: &PidInfo) -> Option<Pid> {
+    info.has_new_version.0.clone().or_else(|| {
+        let pid = Pid(info.pid().clone());
+        known
+            .values()
+            .find(|other| other.is_new_version_of.0.as_ref() == Some(&pid))
+            .map(|draft| Pid(draft.pid().clone()))
+    })
+}
+
+/// Links the predecessor of a just registered version to it.
+/// Returns true if the predecessor was changed. It only has to be published again if
+/// the version relations are published (see `config::publish_draft_types`).
+pub fn link_predecessor(known: &mut HashMap<Pid, PidInfo>, registered: &Pid) -> bool {
+    let predecessor = match known.get(registered).and_then(|info| info.is_new_version_of.0.clone()) {
+        Some(pid) => pid,
+        None => return false,
+    };
+    match known.get_mut(&predecessor) {
+        Some(info) if info.has_new_version.0.is_none() => {
+            info.has_new_version = HasNewVersion(Some(registered.clone()));
+            info.update_state();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// All known versions of the object, from the oldest to the newest.
+/// Versions which are not in the workspace are included with their PID only.
+pub fn chain(known: &HashMap<Pid, PidInfo>, pid: &Pid) -> Vec<(Pid, Option<Version>)> {
+    let version_of = |pid: &Pid| known.get(pid).map(|info| info.version.clone());
+    let mut visited: HashSet<Pid> = HashSet::new();
+    visited.insert(pid.clone());
+
+    let mut older = Vec::new();
+    let mut current = known.get(pid).and_then(|info| info.is_new_version_of.0.clone());
+    while let Some(p) = current.filter(|p| visited.insert(p.clone())) {
+        current = known.get(&p).and_then(|info| info.is_new_version_of.0.clone());
+        older.push(p);
+    }
+    older.reverse();
+
+    let mut newer = Vec::new();
+    let mut current = known.get(pid).and_then(|info| successor(known, info));
+    while let Some(p) = current.filter(|p| visited.insert(p.clone())) {
+        current = known.get(&p).and_then(|info| successor(known, info));
+        newer.push(p);
+    }
+
+    older
+        .into_iter()
+        .chain(std::iter::once(pid.clone()))
+        .chain(newer)
+        .map(|p| {
+            let version = version_of(&p);
+            (p, version)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_version_is_linked_and_bumped() {
+        let v1 = Pid("21.T11148/v1".into());
+        let mut original = PidInfo::from_registered(PidInfo::default().as_record());
+        *original.pid_mut() = v1.0.clone();
+        let mut known = HashMap::new();
+        known.insert(v1.clone(), original.clone());
+
+        let mut draft = new_version(&known, &original, Bump::Minor).unwrap();
+        assert_eq!(draft.version, Version("1.1.0".into()));
+        assert_eq!(draft.state(), State::Unregistered);
+        assert_eq!(draft.is_new_version_of, IsNewVersionOf(Some(v1.clone())));
+
+        // the original stays unchanged until the draft is registered.
+        *draft.pid_mut() = "unregistered-1".into();
+        known.insert(Pid("unregistered-1".into()), draft.clone());
+        assert!(new_version(&known, &original, Bump::Patch).is_err());
+        let pids: Vec<Pid> = chain(&known, &v1).into_iter().map(|(pid, _)| pid).collect();
+        assert_eq!(pids, vec![v1.clone(), Pid("unregistered-1".into())]);
+
+        let registered = Pid("21.T11148/v2".into());
+        known.remove(&Pid("unregistered-1".into()));
+        *draft.pid_mut() = registered.0.clone();
+        known.insert(registered.clone(), draft);
+        assert!(link_predecessor(&mut known, &registered));
+        assert_eq!(known[&v1].has_new_version, HasNewVersion(Some(registered.clone())));
+        let expected = if crate::config::publish_draft_types() { State::Modified } else { State::Clean };
+        assert_eq!(known[&v1].state(), expected);
+        assert!(!link_predecessor(&mut known, &registered));
+    }
+
+    #[test]
+    fn links_to_registered_drafts_are_replaced() {
+        let mut info = PidInfo::default();
+        info.superseded_by = SupersededBy(Some(Pid("unregistered-1".into())));
+        let registered = Pid("21.T11148/v2".into());
+        assert!(replace_links(&mut info, &Pid("unregistered-1".into()), &registered));
+        assert_eq!(info.superseded_by, SupersededBy(Some(registered)));
+    }
+}
@@ -0,0 +1,368 @@
+//! A versioned, serializable representation of a workspace (or a part of it).
+//!
+//! It is used to share workspaces between colleagues or to move them between machines.
+//! Import and export are pure functions on the current state, so they can be tested natively.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+use super::data::{Data, DataID};
+use crate::{
+    data_type_registry::Pid,
+    pidinfo::{PidInfo, State},
+    service_communication::PidRecord,
+};
+
+/// The version of the format written by this client.
+/// Files with a higher version will be rejected on import.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceFile {
+    pub format_version: u32,
+    pub exported: String,
+    pub objects: Vec<WorkspaceObject>,
+    #[serde(default)]
+    pub data: Vec<WorkspaceData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceObject {
+    pub state: State,
+    /// The published record (empty, if unregistered).
+    pub record: PidRecord,
+    /// The local state, including all local edits.
+    pub local: PidRecord,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<DataID>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WorkspaceData {
+    pub id: DataID,
+    pub data: Data,
+}
+
+/// How to handle imported objects whose PID is already in the workspace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter)]
+pub enum Conflict {
+    /// Keep the local object, ignore the imported one.
+    #[default]
+    KeepLocal,
+    /// Replace the local object with the imported one.
+    Replace,
+    /// Keep the local object and add the imported one as a new unregistered draft.
+    KeepBoth,
+}
+
+/// Associates conflict strategies with their Display name (for the user interface).
+impl Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conflict::KeepLocal => write!(f, "Keep local objects"),
+            Conflict::Replace => write!(f, "Replace local objects"),
+            Conflict::KeepBoth => write!(f, "Keep both (import as draft)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    Syntax(String),
+    UnsupportedVersion(u32),
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Syntax(e) => write!(f, "Not a valid workspace file: {}", e),
+            ImportError::UnsupportedVersion(v) => write!(
+                f,
+                "Workspace file version {} is not supported (supported: {}).",
+                v, FORMAT_VERSION
+            ),
+        }
+    }
+}
+
+/// The result of an import, ready to be handed to the PidManager and DataManager.
+#[derive(Debug, Default)]
+pub struct Import {
+    pub pid_infos: Vec<PidInfo>,
+    pub data: Vec<(DataID, Data)>,
+    /// PIDs that were not imported because a local object was kept.
+    pub skipped: Vec<Pid>,
+}
+
+impl WorkspaceFile {
+    /// Exports the given objects and the data linked to them.
+    pub fn export<'a, I>(infos: I, known_data: &HashMap<DataID, Data>) -> Self
+    where
+        I: IntoIterator<Item = &'a PidInfo>,
+    {
+        let mut objects = Vec::new();
+        let mut data = Vec::new();
+        let mut exported_data: HashSet<DataID> = HashSet::new();
+        for info in infos {
+            if let Some(id) = info.data {
+                match known_data.get(&id) {
+                    Some(d) if exported_data.insert(id) => data.push(WorkspaceData {
+                        id,
+                        data: d.clone(),
+                    }),
+                    Some(_) => {}
+                    None => log::warn!("Data {:?} of {} is unknown and not exported.", id, info.pid()),
+                }
+            }
+            objects.push(WorkspaceObject {
+                state: info.state(),
+                record: info.record.clone(),
+                local: info.as_record(),
+                data: info.data,
+            });
+        }
+        Self {
+            format_version: FORMAT_VERSION,
+            exported: chrono::Utc::now().to_rfc3339(),
+            objects,
+            data,
+        }
+    }
+
+    /// Exports the selected objects of the workspace, or all of them if nothing is selected.
+    pub fn export_selection(
+        selection: &[Pid],
+        known_pids: &HashMap<Pid, PidInfo>,
+        known_data: &HashMap<DataID, Data>,
+    ) -> Self {
+        if selection.is_empty() {
+            return Self::export(known_pids.values(), known_data);
+        }
+        let infos = selection.iter().filter_map(|pid| {
+            let info = known_pids.get(pid);
+            if info.is_none() {
+                log::warn!("{} is not in the workspace and not exported.", pid);
+            }
+            info
+        });
+        Self::export(infos, known_data)
+    }
+
+    /// A file name for the export of the selection.
+    pub fn file_name(selection: &[Pid]) -> String {
+        match selection {
+            [] => "workspace.fairris.json".into(),
+            [single] => format!("{}.fairris.json", single.replace('/', "_")),
+            _ => format!("selection-{}.fairris.json", selection.len()),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        json::to_string_pretty(self).expect("Workspace file can always be serialized.")
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, ImportError> {
+        let value: json::Value =
+            json::from_str(s).map_err(|e| ImportError::Syntax(e.to_string()))?;
+        let version = value
+            .get("formatVersion")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| ImportError::Syntax("formatVersion is missing.".into()))?;
+        if version > FORMAT_VERSION as u64 {
+            return Err(ImportError::UnsupportedVersion(version as u32));
+        }
+        json::from_value(value).map_err(|e| ImportError::Syntax(e.to_string()))
+    }
+
+    /// Merges this file into the given workspace state.
+    ///
+    /// Data ids which are already used for other data are replaced by free ids.
+    /// PID conflicts are resolved using the given strategy. Data of skipped objects
+    /// is only imported if an imported object refers to it, too.
+    pub fn import(
+        self,
+        known_pids: &HashMap<Pid, PidInfo>,
+        known_data: &HashMap<DataID, Data>,
+        conflict: Conflict,
+    ) -> Import {
+        let mut result = Import::default();
+
+        let is_skipped = |object: &&WorkspaceObject| {
+            conflict == Conflict::KeepLocal && known_pids.contains_key(&Pid(object.local.pid.clone()))
+        };
+        let (skipped, imported): (Vec<&WorkspaceObject>, Vec<&WorkspaceObject>) =
+            self.objects.iter().partition(is_skipped);
+        let needed: HashSet<DataID> = imported.iter().filter_map(|object| object.data).collect();
+        let unneeded: HashSet<DataID> = skipped
+            .iter()
+            .filter_map(|object| object.data)
+            .filter(|id| !needed.contains(id))
+            .collect();
+
+        let mut used_ids: HashSet<DataID> = known_data.keys().cloned().collect();
+        let mut id_mapping: HashMap<DataID, DataID> = HashMap::new();
+        for WorkspaceData { id, data } in self.data {
+            if unneeded.contains(&id) {
+                continue;
+            }
+            if known_data.get(&id) == Some(&data) {
+                // identical data already exists, reuse it.
+                id_mapping.insert(id, id);
+                continue;
+            }
+            let mut new_id = id;
+            while used_ids.contains(&new_id) {
+                *new_id = new_id.wrapping_add(1);
+            }
+            used_ids.insert(new_id);
+            id_mapping.insert(id, new_id);
+            result.data.push((new_id, data));
+        }
+
+        let mut used_pids: HashSet<Pid> = known_pids.keys().cloned().collect();
+        for object in self.objects {
+            let pid = Pid(object.local.pid.clone());
+            let data = object.data.and_then(|id| id_mapping.get(&id).cloned());
+            let mut info = if !known_pids.contains_key(&pid) || conflict == Conflict::Replace {
+                PidInfo::from_parts(object.record, &object.local, object.state)
+            } else if conflict == Conflict::KeepBoth {
+                let draft_pid = Self::free_unregistered_pid(&used_pids);
+                let mut local = object.local;
+                local.pid = (*draft_pid).clone();
+                let record = PidRecord {
+                    pid: local.pid.clone(),
+                    ..Default::default()
+                };
+                PidInfo::from_parts(record, &local, State::Unregistered)
+            } else {
+                result.skipped.push(pid);
+                continue;
+            };
+            info.data = data;
+            used_pids.insert(Pid(info.pid().clone()));
+            result.pid_infos.push(info);
+        }
+        result
+    }
+
+    fn free_unregistered_pid(used: &HashSet<Pid>) -> Pid {
+        (0..)
+            .map(|n| Pid(format!("unregistered-import-{}", n)))
+            .find(|pid| !used.contains(pid))
+            .expect("There is always a free PID.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::data::AnnotatedImage;
+
+    fn workspace() -> (HashMap<Pid, PidInfo>, HashMap<DataID, Data>) {
+        let mut info = PidInfo::default();
+        *info.pid_mut() = "unregistered-1".into();
+        info.data = Some(DataID(7));
+        let data = Data::AnnotatedImage(AnnotatedImage {
+            url: "https://example.com/image.tiff".into(),
+            annotation_urls: vec![],
+        });
+        let mut pids = HashMap::new();
+        pids.insert(Pid(info.pid().clone()), info);
+        let mut known_data = HashMap::new();
+        known_data.insert(DataID(7), data);
+        (pids, known_data)
+    }
+
+    #[test]
+    fn roundtrip_into_empty_workspace() {
+        let (pids, data) = workspace();
+        let json = WorkspaceFile::export(pids.values(), &data).to_json();
+        let file = WorkspaceFile::from_json(&json).unwrap();
+        let import = file.import(&HashMap::new(), &HashMap::new(), Conflict::KeepLocal);
+        assert_eq!(import.pid_infos.len(), 1);
+        assert_eq!(import.pid_infos[0].as_record(), pids.values().next().unwrap().as_record());
+        assert_eq!(import.data, vec![(DataID(7), data[&DataID(7)].clone())]);
+    }
+
+    #[test]
+    fn conflicts() {
+        let (pids, data) = workspace();
+        let file = WorkspaceFile::export(pids.values(), &data);
+
+        let kept = file.clone().import(&pids, &data, Conflict::KeepLocal);
+        assert!(kept.pid_infos.is_empty());
+        assert_eq!(kept.skipped, vec![Pid("unregistered-1".into())]);
+        // identical data is reused instead of being duplicated.
+        assert!(kept.data.is_empty());
+
+        let both = file.import(&pids, &data, Conflict::KeepBoth);
+        assert_eq!(both.pid_infos.len(), 1);
+        assert_eq!(both.pid_infos[0].pid(), "unregistered-import-0");
+        assert_eq!(both.pid_infos[0].state(), State::Unregistered);
+        assert_eq!(both.pid_infos[0].data, Some(DataID(7)));
+    }
+
+    #[test]
+    fn selections_are_exported_with_their_data() {
+        let (mut pids, mut data) = workspace();
+        let mut other = PidInfo::default();
+        *other.pid_mut() = "unregistered-2".into();
+        other.data = Some(DataID(8));
+        pids.insert(Pid(other.pid().clone()), other);
+        data.insert(DataID(8), Data::AnnotatedImage(AnnotatedImage::default()));
+
+        let selection = vec![Pid("unregistered-2".into()), Pid("unregistered-missing".into())];
+        let file = WorkspaceFile::export_selection(&selection, &pids, &data);
+        assert_eq!(file.objects.len(), 1);
+        assert_eq!(file.objects[0].local.pid, "unregistered-2");
+        assert_eq!(file.data, vec![WorkspaceData { id: DataID(8), data: data[&DataID(8)].clone() }]);
+        assert_eq!(WorkspaceFile::file_name(&selection), "selection-2.fairris.json");
+
+        let all = WorkspaceFile::export_selection(&[], &pids, &data);
+        assert_eq!(all.objects.len(), 2);
+        assert_eq!(all.data.len(), 2);
+    }
+
+    #[test]
+    fn data_of_skipped_objects_is_not_imported() {
+        let (pids, data) = workspace();
+        let file = WorkspaceFile::export(pids.values(), &data);
+        // the local data changed since the export, so it is not identical anymore.
+        let mut changed = data.clone();
+        changed.insert(DataID(7), Data::AnnotatedImage(AnnotatedImage::default()));
+        let kept = file.clone().import(&pids, &changed, Conflict::KeepLocal);
+        assert!(kept.pid_infos.is_empty());
+        assert!(kept.data.is_empty());
+
+        let both = file.import(&pids, &changed, Conflict::KeepBoth);
+        assert_eq!(both.data.len(), 1);
+        assert_eq!(both.pid_infos[0].data, Some(both.data[0].0));
+    }
+
+    #[test]
+    fn conflicting_data_ids_are_remapped() {
+        let (pids, data) = workspace();
+        let file = WorkspaceFile::export(pids.values(), &data);
+        let mut other_data = HashMap::new();
+        other_data.insert(DataID(7), Data::AnnotatedImage(AnnotatedImage::default()));
+        let import = file.import(&HashMap::new(), &other_data, Conflict::KeepLocal);
+        assert_eq!(import.data[0].0, DataID(8));
+        assert_eq!(import.pid_infos[0].data, Some(DataID(8)));
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let json = r#"{ "formatVersion": 99, "exported": "", "objects": [] }"#;
+        assert_eq!(
+            WorkspaceFile::from_json(json),
+            Err(ImportError::UnsupportedVersion(99))
+        );
+    }
+}
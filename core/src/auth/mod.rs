@@ -0,0 +1,112 @@
+//! Authentication at the PIT and collection services.
+//!
+//! All requests pass the `Authorization` middleware, which adds the current `Credentials`.
+//! They are configured statically (a bearer token or an API key, see `config`),
+//! or come from a login with OpenID Connect (see `oidc`).
+
+pub mod oidc;
+mod pkce;
+
+pub use pkce::Pkce;
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    config,
+    service_communication::{
+        transport::{Header, Middleware},
+        ApiRequest,
+    },
+};
+
+/// What requests carry to authenticate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Credentials {
+    #[default]
+    Anonymous,
+    Bearer(String),
+    ApiKey { header: String, key: String },
+}
+
+impl Credentials {
+    /// The credentials set at build time. A bearer token wins over an API key.
+    pub fn configured() -> Self {
+        match (config::auth_token(), config::api_key()) {
+            (Some(token), _) => Credentials::Bearer(token.into()),
+            (None, Some(key)) => Credentials::ApiKey {
+                header: config::api_key_header().into(),
+                key: key.into(),
+            },
+            (None, None) => Credentials::Anonymous,
+        }
+    }
+
+    fn header(&self) -> Result<Option<Header>, String> {
+        match self {
+            Credentials::Anonymous => Ok(None),
+            Credentials::Bearer(token) => Header::bearer(token).map(Some),
+            Credentials::ApiKey { header, key } => Header::new(header, key).map(Some),
+        }
+    }
+}
+
+/// Adds the credentials to every request. Clones share them, so all pipelines
+/// use new credentials after a login, logout or refresh.
+#[derive(Debug, Clone, Default)]
+pub struct Authorization {
+    credentials: Rc<RefCell<Credentials>>,
+}
+
+impl Authorization {
+    pub fn new(credentials: Credentials) -> Self {
+        Self {
+            credentials: Rc::new(RefCell::new(credentials)),
+        }
+    }
+
+    pub fn set(&self, credentials: Credentials) {
+        *self.credentials.borrow_mut() = credentials;
+    }
+
+    pub fn credentials(&self) -> Credentials {
+        self.credentials.borrow().clone()
+    }
+}
+
+impl Middleware for Authorization {
+    fn prepare(&self, request: &mut ApiRequest) {
+        match self.credentials.borrow().header() {
+            Ok(Some(header)) => header.prepare(request),
+            Ok(None) => {}
+            Err(e) => log::error!("Request sent without credentials: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service_communication::transport::{send_blocking, Pipeline, Replay};
+
+    #[test]
+    fn pipelines_use_the_latest_credentials() {
+        let replay = Replay::default();
+        let authorization = Authorization::default();
+        let pipeline = Pipeline::new(replay.clone()).with(authorization.clone());
+        let request = || http::Request::get("http://localhost/a").body(None).unwrap();
+
+        send_blocking(&pipeline, request()).ok();
+        assert!(replay.last_headers().get("authorization").is_none());
+
+        authorization.set(Credentials::Bearer("token".into()));
+        send_blocking(&pipeline, request()).ok();
+        assert_eq!(replay.last_headers().get("authorization").unwrap(), "Bearer token");
+
+        authorization.set(Credentials::ApiKey {
+            header: "X-API-Key".into(),
+            key: "key".into(),
+        });
+        send_blocking(&pipeline, request()).ok();
+        assert_eq!(replay.last_headers().get("x-api-key").unwrap(), "key");
+    }
+}
@@ -0,0 +1,343 @@
+//! Login with OpenID Connect, using the authorization code flow with PKCE.
+//!
+//! 1. `discovery_request` finds the endpoints of the provider.
+//! 2. The browser goes to `authorization_uri`. The provider sends the user back to the redirect URI
+//!    with a code, see `parse_redirect`.
+//! 3. `token_request` exchanges the code for tokens, which make up the `Session`.
+//! 4. Before the access token expires, `refresh_request` gets new ones.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+use super::pkce::Pkce;
+use crate::{
+    data_type_registry::{Contributor, Identifier, CONTRIBUTOR_ROLES},
+    pidinfo::PidInfo,
+    service_communication::{ApiRequest, ApiResponse},
+};
+
+/// This client, as registered at the provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Client {
+    pub issuer: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: String,
+}
+
+/// The endpoints of a provider, from its discovery document.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Provider {
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+}
+
+/// A login which was started and waits for the provider to redirect back.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PendingLogin {
+    pub state: String,
+    pub verifier: String,
+    pub token_endpoint: String,
+}
+
+/// The logged in user, from the claims of the ID token.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    pub subject: String,
+    pub name: Option<String>,
+}
+
+/// The tokens of a login.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Session {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub token_endpoint: String,
+    pub identity: Identity,
+}
+
+pub fn discovery_request(issuer: &str) -> ApiRequest {
+    http::Request::get(format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/')))
+        .header("Accept", "application/json")
+        .body(None)
+        .expect("Failed to build this request.")
+}
+
+pub fn parse_provider(response: &ApiResponse) -> Result<Provider, String> {
+    if !response.status().is_success() {
+        return Err(format!("The login provider answered {}.", response.status()));
+    }
+    json::from_str(response.body()).map_err(|e| format!("Invalid discovery document: {}", e))
+}
+
+/// Starts a login. The pending login has to be kept until the provider redirects back.
+pub fn start_login(client: &Client, provider: &Provider) -> (String, PendingLogin) {
+    let pkce = Pkce::generate();
+    let state = super::pkce::random_token();
+    let query = form_urlencoded::Serializer::new(String::new())
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &client.client_id)
+        .append_pair("redirect_uri", &client.redirect_uri)
+        .append_pair("scope", &client.scope)
+        .append_pair("state", &state)
+        .append_pair("code_challenge", &pkce.challenge)
+        .append_pair("code_challenge_method", "S256")
+        .finish();
+    let separator = if provider.authorization_endpoint.contains('?') { '&' } else { '?' };
+    let uri = format!("{}{}{}", provider.authorization_endpoint, separator, query);
+    let pending = PendingLogin {
+        state,
+        verifier: pkce.verifier,
+        token_endpoint: provider.token_endpoint.clone(),
+    };
+    (uri, pending)
+}
+
+/// Reads the code from the query of the redirect (without `?`).
+/// `None` if the query does not belong to a login.
+pub fn parse_redirect(query: &str, pending: &PendingLogin) -> Option<Result<String, String>> {
+    let mut code = None;
+    let mut state = None;
+    let mut error = None;
+    for (name, value) in form_urlencoded::parse(query.trim_start_matches('?').as_bytes()) {
+        match name.as_ref() {
+            "code" => code = Some(value.into_owned()),
+            "state" => state = Some(value.into_owned()),
+            "error" => error = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+    if code.is_none() && error.is_none() {
+        return None;
+    }
+    Some(match (state, code, error) {
+        (Some(state), _, _) if state != pending.state => Err("The login does not match the one started.".into()),
+        (_, _, Some(error)) => Err(format!("The login failed: {}", error)),
+        (Some(_), Some(code), None) => Ok(code),
+        // without state, the code may stem from a login somebody else started.
+        (None, Some(_), None) => Err("The login response has no state.".into()),
+        _ => Err("The login failed.".into()),
+    })
+}
+
+pub fn token_request(client: &Client, pending: &PendingLogin, code: &str) -> ApiRequest {
+    let body = form_urlencoded::Serializer::new(String::new())
+        .append_pair("grant_type", "authorization_code")
+        .append_pair("code", code)
+        .append_pair("redirect_uri", &client.redirect_uri)
+        .append_pair("client_id", &client.client_id)
+        .append_pair("code_verifier", &pending.verifier)
+        .finish();
+    form_request(&pending.token_endpoint, body)
+}
+
+/// `None` if the session can not be refreshed.
+pub fn refresh_request(client: &Client, session: &Session) -> Option<ApiRequest> {
+    let body = form_urlencoded::Serializer::new(String::new())
+        .append_pair("grant_type", "refresh_token")
+        .append_pair("refresh_token", session.refresh_token.as_ref()?)
+        .append_pair("client_id", &client.client_id)
+        .finish();
+    Some(form_request(&session.token_endpoint, body))
+}
+
+fn form_request(uri: &str, body: String) -> ApiRequest {
+    http::Request::post(uri)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .header("Accept", "application/json")
+        .body(Some(body))
+        .expect("Failed to build this request.")
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+    id_token: Option<String>,
+}
+
+/// Reads the tokens of a login, or of a refresh of the `previous` session.
+/// Refreshes may come without ID token and refresh token, then the previous ones stay.
+pub fn parse_session(
+    response: &ApiResponse,
+    token_endpoint: &str,
+    previous: Option<&Session>,
+    now: DateTime<Utc>,
+) -> Result<Session, String> {
+    if !response.status().is_success() {
+        return Err(format!("The login provider answered {}: {}", response.status(), response.body()));
+    }
+    let tokens: TokenResponse =
+        json::from_str(response.body()).map_err(|e| format!("Invalid token response: {}", e))?;
+    let identity = match (tokens.id_token, previous) {
+        (Some(id_token), _) => Identity::from_id_token(&id_token)?,
+        (None, Some(previous)) => previous.identity.clone(),
+        (None, None) => return Err("The login provider sent no ID token.".into()),
+    };
+    let expires_at = match tokens.expires_in {
+        Some(seconds) => {
+            // negative lifetimes expire right away, `Duration::seconds` panics above the maximum.
+            let lifetime = Duration::seconds(seconds.clamp(0, i64::MAX / 1_000));
+            let expires = now.checked_add_signed(lifetime);
+            Some(expires.ok_or_else(|| format!("The tokens of the login provider expire too late ({} seconds).", seconds))?)
+        }
+        None => None,
+    };
+    Ok(Session {
+        access_token: tokens.access_token,
+        refresh_token: tokens
+            .refresh_token
+            .or_else(|| previous.and_then(|p| p.refresh_token.clone())),
+        expires_at,
+        token_endpoint: token_endpoint.to_owned(),
+        identity,
+    })
+}
+
+impl Session {
+    /// Tokens are refreshed a minute before they expire.
+    pub fn refresh_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at.map(|expires| expires - Duration::minutes(1))
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.expires_at, Some(expires) if expires <= now)
+    }
+}
+
+impl Identity {
+    /// Reads the claims of the token. The signature is not checked: the token comes directly
+    /// from the provider, and the services check the access token themselves.
+    pub fn from_id_token(id_token: &str) -> Result<Self, String> {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+        let payload = id_token.split('.').nth(1).ok_or("The ID token is not a JWT.")?;
+        let claims = URL_SAFE_NO_PAD
+            .decode(payload.trim_end_matches('='))
+            .map_err(|e| format!("Invalid ID token: {}", e))?;
+        let claims: json::Value = json::from_slice(&claims).map_err(|e| format!("Invalid ID token: {}", e))?;
+        let claim = |name: &str| claims.get(name).and_then(json::Value::as_str).map(str::to_owned);
+        Ok(Self {
+            subject: claim("sub").ok_or("The ID token has no subject.")?,
+            name: claim("name").or_else(|| claim("preferred_username")),
+        })
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.subject)
+    }
+
+    /// The user as contributor (author), if the subject is an identifier records can contain, like an ORCID.
+    pub fn contributor(&self) -> Option<Contributor> {
+        match Identifier::parse(&self.subject) {
+            Ok(Identifier::Other(_)) | Err(_) => None,
+            Ok(_) => Some(Contributor {
+                identifier: self.subject.clone(),
+                role: CONTRIBUTOR_ROLES[0].into(),
+            }),
+        }
+    }
+
+    /// Adds the user as contributor to a new object, unless they are already listed.
+    pub fn prefill(&self, info: &mut PidInfo) {
+        if let Some(contributor) = self.contributor() {
+            let listed = info.contributors.0.iter().any(|c| c.identifier == contributor.identifier);
+            if !listed {
+                info.contributors.0.push(contributor);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> Client {
+        Client {
+            issuer: "http://localhost:8090".into(),
+            client_id: "fairris".into(),
+            redirect_uri: "http://localhost:8080/".into(),
+            scope: "openid".into(),
+        }
+    }
+
+    fn provider() -> Provider {
+        Provider {
+            authorization_endpoint: "http://localhost:8090/oidc/authorize".into(),
+            token_endpoint: "http://localhost:8090/oidc/token".into(),
+        }
+    }
+
+    #[test]
+    fn redirects_must_match_the_started_login() {
+        let (uri, pending) = start_login(&client(), &provider());
+        assert!(uri.contains("code_challenge_method=S256"));
+        assert!(uri.contains(&format!("state={}", pending.state)));
+
+        let redirect = format!("code=abc&state={}", pending.state);
+        assert_eq!(parse_redirect(&redirect, &pending), Some(Ok("abc".into())));
+        assert!(parse_redirect("code=abc&state=other", &pending).unwrap().is_err());
+        assert!(parse_redirect("code=abc", &pending).unwrap().is_err());
+        assert!(parse_redirect("error=access_denied", &pending).unwrap().is_err());
+        assert_eq!(parse_redirect("", &pending), None);
+    }
+
+    #[test]
+    fn refreshes_keep_the_identity() {
+        let now = Utc::now();
+        // {"sub":"0000-0002-1825-0097","name":"Josiah Carberry"}
+        let id_token = "e30.eyJzdWIiOiIwMDAwLTAwMDItMTgyNS0wMDk3IiwibmFtZSI6Ikpvc2lhaCBDYXJiZXJyeSJ9.";
+        let login = http::Response::new(format!(
+            r#"{{"access_token":"a","refresh_token":"r","expires_in":300,"id_token":"{}"}}"#,
+            id_token
+        ));
+        let session = parse_session(&login, "http://localhost/token", None, now).unwrap();
+        assert_eq!(session.identity.display_name(), "Josiah Carberry");
+        assert_eq!(session.refresh_at(), Some(now + Duration::seconds(240)));
+
+        let refresh = http::Response::new(r#"{"access_token":"b","expires_in":300}"#.to_string());
+        let refreshed = parse_session(&refresh, "http://localhost/token", Some(&session), now).unwrap();
+        assert_eq!(refreshed.access_token, "b");
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("r"));
+        assert_eq!(refreshed.identity, session.identity);
+    }
+
+    #[test]
+    fn out_of_range_lifetimes_do_not_panic() {
+        let now = Utc::now();
+        // {"sub":"0000-0002-1825-0097","name":"Josiah Carberry"}
+        let id_token = "e30.eyJzdWIiOiIwMDAwLTAwMDItMTgyNS0wMDk3IiwibmFtZSI6Ikpvc2lhaCBDYXJiZXJyeSJ9.";
+        let response = |expires_in: i64| {
+            http::Response::new(format!(
+                r#"{{"access_token":"a","expires_in":{},"id_token":"{}"}}"#,
+                expires_in, id_token
+            ))
+        };
+        assert!(parse_session(&response(i64::MAX), "http://localhost/token", None, now).is_err());
+        let session = parse_session(&response(-300), "http://localhost/token", None, now).unwrap();
+        assert_eq!(session.expires_at, Some(now));
+        assert!(session.is_expired(now));
+    }
+
+    #[test]
+    fn orcid_users_are_prefilled_as_authors() {
+        let user = Identity {
+            subject: "0000-0002-1825-0097".into(),
+            name: None,
+        };
+        let mut info = PidInfo::default();
+        user.prefill(&mut info);
+        user.prefill(&mut info);
+        assert_eq!(info.contributors.0.len(), 1);
+        assert_eq!(info.contributors.0[0].role, "author");
+
+        let local = Identity {
+            subject: "f81d4fae-7dec".into(),
+            name: None,
+        };
+        assert_eq!(local.contributor(), None);
+    }
+}
@@ -0,0 +1,250 @@
+//! A command line client to register, update, resolve and compare FDO records.
+//!
+//! Records are sent and printed as they are. The record model of the web application
+//! only checks them, and problems are reported as warnings.
+
+use std::{path::Path, process, time::Duration};
+
+use fairris_core::{
+    auth::{Authorization, Credentials},
+    config,
+    pidinfo::PidInfo,
+    service_communication::{
+        handle_api, pit_api,
+        transport::{self, NativeTransport, Pipeline, Retry},
+        ApiRequest, ApiResponse, PidRecord,
+    },
+};
+
+const USAGE: &str = "\
+Usage: fairris-cli [--pit <uri>] [--token <token>] [--handle] [--yaml] <command>
+
+Commands:
+  register <file>      Registers the record in the file (JSON or YAML).
+  update <file>        Updates the published record with the PID given in the file.
+  resolve <pid>        Prints the published record.
+  diff <file> [<pid>]  Compares the file to the published record. Exits with 1 if they differ.
+
+Options:
+  --pit <uri>       Base URI of the PIT service (default: $FAIRRIS_PIT_URI or the configured one).
+  --token <token>   Bearer token for secured services (default: $FAIRRIS_AUTH_TOKEN or the configured one).
+  --handle          Resolve with the Handle System instead of the PIT service, i.e. objects registered
+                    elsewhere (resolver: $FAIRRIS_HANDLE_URI or the configured one).
+  --yaml            Print records as YAML instead of JSON.";
+
+struct Options {
+    pit: String,
+    credentials: Credentials,
+    /// Base URI of the Handle resolver, if resolving with it.
+    handle: Option<String>,
+    yaml: bool,
+    command: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options {
+            pit: std::env::var("FAIRRIS_PIT_URI").unwrap_or_else(|_| config::pit_base_uri().into()),
+            credentials: std::env::var("FAIRRIS_AUTH_TOKEN")
+                .map(Credentials::Bearer)
+                .unwrap_or_else(|_| Credentials::configured()),
+            handle: None,
+            yaml: false,
+            command: Vec::new(),
+        };
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--pit" => options.pit = args.next().ok_or("--pit needs a URI.")?,
+                "--token" => options.credentials = Credentials::Bearer(args.next().ok_or("--token needs a token.")?),
+                "--handle" => {
+                    let resolver = std::env::var("FAIRRIS_HANDLE_URI");
+                    options.handle = Some(resolver.unwrap_or_else(|_| config::handle_resolver_uri().into()));
+                }
+                "--yaml" => options.yaml = true,
+                "-h" | "--help" => return Err(USAGE.into()),
+                other if other.starts_with("--") => return Err(format!("Unknown option {}.\n\n{}", other, USAGE)),
+                _ => options.command.push(arg),
+            }
+        }
+        options.pit = options.pit.trim_end_matches('/').into();
+        Ok(options)
+    }
+}
+
+fn main() {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    };
+    match run(&options) {
+        Ok(code) => process::exit(code),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(2);
+        }
+    }
+}
+
+/// Runs the command and returns the exit code.
+fn run(options: &Options) -> Result<i32, String> {
+    let args: Vec<&str> = options.command.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["register", file] => {
+            let record = read_record(file)?;
+            print_record(&register(options, &record)?, options.yaml)?;
+        }
+        ["update", file] => {
+            let record = read_record(file)?;
+            if record.pid.is_empty() {
+                return Err(format!("{} has no PID to update.", file));
+            }
+            let updated = pit_api::parse_record(&send(options, pit_api::update_request(&options.pit, &record))?)?;
+            print_record(&updated, options.yaml)?;
+        }
+        ["resolve", pid] => print_record(&resolve(options, pid)?, options.yaml)?,
+        ["diff", file, rest @ ..] if rest.len() <= 1 => {
+            let local = read_record(file)?;
+            let pid = rest.first().copied().unwrap_or(&local.pid);
+            if pid.is_empty() {
+                return Err(format!("{} has no PID, give one to compare with.", file));
+            }
+            let published = resolve(options, pid)?;
+            let diff = local.diff(&published);
+            for entry in diff.iter() {
+                println!("{} ({})", entry.key, entry.name);
+                println!("  - {}", values(&entry.left));
+                println!("  + {}", values(&entry.right));
+            }
+            return Ok(if diff.is_empty() { 0 } else { 1 });
+        }
+        _ => return Err(format!("Unknown command.\n\n{}", USAGE)),
+    }
+    Ok(0)
+}
+
+fn register(options: &Options, record: &PidRecord) -> Result<PidRecord, String> {
+    pit_api::parse_record(&send(options, pit_api::register_request(&options.pit, record))?)
+}
+
+fn resolve(options: &Options, pid: &str) -> Result<PidRecord, String> {
+    match &options.handle {
+        Some(resolver) => {
+            let resolver = resolver.trim_end_matches('/');
+            // the resolver is public, and possibly not ours, so it gets no credentials.
+            let request = handle_api::resolve_request(resolver, pid);
+            handle_api::parse_record(&send_with(&Credentials::Anonymous, request)?)
+        }
+        None => pit_api::parse_record(&send(options, pit_api::resolve_request(&options.pit, pid))?),
+    }
+}
+
+/// Reads a record in the PIT format, YAML if the extension says so, JSON otherwise.
+fn read_record(file: &str) -> Result<PidRecord, String> {
+    let content = std::fs::read_to_string(file).map_err(|e| format!("Could not read {}: {}", file, e))?;
+    let is_yaml = matches!(
+        Path::new(file).extension().and_then(|e| e.to_str()),
+        Some("yaml") | Some("yml")
+    );
+    let record: PidRecord = if is_yaml {
+        serde_yaml::from_str(&content).map_err(|e| format!("{} is no valid record: {}", file, e))?
+    } else {
+        serde_json::from_str(&content).map_err(|e| format!("{} is no valid record: {}", file, e))?
+    };
+    for warning in check(&record) {
+        eprintln!("Warning: {}", warning);
+    }
+    Ok(record)
+}
+
+/// Reads the record with the model of the web application and reports what it would not
+/// read as given. Attributes the record does not have are not reported, the model only
+/// fills them with defaults.
+fn check(record: &PidRecord) -> Vec<String> {
+    let info = PidInfo::from_unregistered(record.clone());
+    let mut warnings: Vec<String> = record
+        .diff(&info.as_record())
+        .into_iter()
+        .filter(|entry| !entry.left.is_empty())
+        .map(|entry| {
+            if entry.right.is_empty() {
+                format!("attribute {} ({}) is unknown or invalid.", entry.key, entry.name)
+            } else {
+                format!("attribute {} ({}) is read as {}.", entry.key, entry.name, values(&entry.right))
+            }
+        })
+        .collect();
+    for location in info.locations.0.iter() {
+        if let Err(e) = location.validate() {
+            warnings.push(format!("{} {}", location.url, e));
+        }
+    }
+    warnings
+}
+
+fn print_record(record: &PidRecord, yaml: bool) -> Result<(), String> {
+    let text = if yaml {
+        serde_yaml::to_string(record).map_err(|e| e.to_string())?
+    } else {
+        serde_json::to_string_pretty(record).map_err(|e| e.to_string())?
+    };
+    println!("{}", text);
+    Ok(())
+}
+
+fn values(values: &[serde_json::Value]) -> String {
+    if values.is_empty() {
+        String::from("(none)")
+    } else {
+        values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Sends the request with the credentials. HTTP errors are responses as well, only connection problems are errors.
+fn send_with(credentials: &Credentials, request: ApiRequest) -> Result<ApiResponse, String> {
+    let pipeline = Pipeline::new(NativeTransport::with_timeout(config::request_timeout()))
+        .with(Authorization::new(credentials.clone()))
+        .with(Retry::new(config::request_attempts()).with_backoff(Duration::from_secs(1)));
+    transport::send_blocking(&pipeline, request)
+}
+
+/// Sends the request to the PIT service.
+fn send(options: &Options, request: ApiRequest) -> Result<ApiResponse, String> {
+    send_with(&options.credentials, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use fairris_mock_services::MockServer;
+
+    use super::*;
+
+    #[test]
+    fn registers_records_as_read() {
+        let server = MockServer::start("127.0.0.1:0").unwrap();
+        let options = Options::parse(vec!["--pit".to_string(), server.pit_base_uri()].into_iter()).unwrap();
+        let file = std::env::temp_dir().join(format!("fairris-cli-{}.yaml", process::id()));
+        std::fs::write(
+            &file,
+            "entries:\n  \
+               21.T11148/c692273deb2772da307f:\n    \
+                 - key: 21.T11148/c692273deb2772da307f\n      name: version\n      value: 1.0.0\n  \
+               21.T11148/unknown:\n    \
+                 - key: 21.T11148/unknown\n      name: unknown\n      value: kept\n",
+        )
+        .unwrap();
+
+        let record = read_record(file.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(record.entries.len(), 2);
+        assert_eq!(check(&record), vec!["attribute 21.T11148/unknown (unknown) is unknown or invalid."]);
+
+        let registered = register(&options, &record).unwrap();
+        assert!(!registered.pid.is_empty());
+        assert!(registered.diff(&record).is_empty(), "{:?}", registered.diff(&record));
+        assert!(resolve(&options, &registered.pid).unwrap().diff(&record).is_empty());
+    }
+}
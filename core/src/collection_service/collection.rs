@@ -19,7 +19,15 @@ pub struct Collection {
 
 impl Collection {
     pub fn get_id(&self) -> Option<&str> {
-        self.id.as_ref().map(|id| id.as_str())
+        self.id.as_deref()
+    }
+
+    /// A copy without id, so the collection service will create a new collection.
+    pub fn as_new(&self) -> Self {
+        Self {
+            id: None,
+            ..self.clone()
+        }
     }
 }
 
@@ -50,4 +58,23 @@ impl DerefMut for Collections {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
-}
\ No newline at end of file
+}
+
+impl From<Collections> for serde_json::Value {
+    fn from(cs: Collections) -> Self {
+        serde_json::Value::Array(
+            cs.iter()
+                .map(serde_json::to_value)
+                .filter(|result| {
+                    if result.is_ok() {
+                        true
+                    } else {
+                        log::error!("Own collection could not be serialized: {:?}", result);
+                        false
+                    }
+                })
+                .map(|ok| ok.unwrap())
+                .collect(),
+        )
+    }
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[serde(default)]
 pub struct Member {
     #[serde(skip_serializing)]
     id: String,
@@ -15,6 +16,24 @@ pub struct Member {
     mappings: Option<Mapping>,
 }
 
+impl Member {
+    pub fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn datatype(&self) -> &str {
+        &self.datatype
+    }
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
@@ -0,0 +1,9 @@
+//! Collections of the RDA collection API and their members.
+
+pub mod collection;
+pub mod collection_capabilities;
+pub mod collection_properties;
+pub mod member_item;
+
+pub type Etag = http::header::HeaderValue;
+pub type MaybeEtag = Option<Etag>;
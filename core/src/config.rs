@@ -0,0 +1,81 @@
+//! Endpoints of the services this client talks to.
+//!
+//! They can be set at compile time using environment variables,
+//! i.e. to point the client to local stand-ins during development and tests:
+//!
+//! `FAIRRIS_PIT_URI=http://localhost:9000 bash build.sh --dev`
+
+/// Base URI of the PIT service.
+pub fn pit_base_uri() -> &'static str {
+    option_env!("FAIRRIS_PIT_URI").unwrap_or("http://localhost:8090")
+}
+
+/// Base URI of the RDA collection API.
+pub fn collection_base_uri() -> &'static str {
+    option_env!("FAIRRIS_COLLECTION_URI").unwrap_or("http://localhost:8091/api/v1")
+}
+
+/// Base URI of the service resolving person (ORCID) and organization (ROR) identifiers to names.
+/// It is expected to answer `GET {base}/orcid/{id}` and `GET {base}/ror/{id}` with `{ "name": "..." }`.
+pub fn identity_lookup_uri() -> &'static str {
+    option_env!("FAIRRIS_IDENTITY_LOOKUP_URI").unwrap_or("http://localhost:8092")
+}
+
+/// Base URI of the data type registry, used to look up names and descriptions of PIDs.
+/// It is expected to answer `GET {base}/{pid}` with the type definition as JSON.
+pub fn dtr_base_uri() -> &'static str {
+    option_env!("FAIRRIS_DTR_URI").unwrap_or("https://dtr-test.pidconsortium.net/objects")
+}
+
+/// Base URI of the Handle System REST API, used to resolve PIDs the PIT service does not know.
+/// It is expected to answer `GET {base}/api/handles/{handle}`.
+pub fn handle_resolver_uri() -> &'static str {
+    option_env!("FAIRRIS_HANDLE_URI").unwrap_or("https://hdl.handle.net")
+}
+
+/// How long a request may take before it counts as failed, in milliseconds.
+pub fn request_timeout() -> std::time::Duration {
+    let ms = option_env!("FAIRRIS_TIMEOUT_MS").and_then(|ms| ms.parse().ok()).unwrap_or(15_000);
+    std::time::Duration::from_millis(ms)
+}
+
+/// How often idempotent requests are sent at most, if the service is unreachable.
+pub fn request_attempts() -> u32 {
+    option_env!("FAIRRIS_REQUEST_ATTEMPTS").and_then(|n| n.parse().ok()).unwrap_or(3)
+}
+
+/// Publishes attributes of draft types, whose PIDs are placeholders until the types are
+/// registered (see `data_type_registry::draft_types`). Only PIT services which do not
+/// validate records, like the mock services, accept them. Otherwise they are kept locally.
+pub fn publish_draft_types() -> bool {
+    matches!(option_env!("FAIRRIS_DRAFT_TYPES"), Some("1") | Some("true"))
+}
+
+/// The publisher written into exported metadata, i.e. DataCite, which requires one.
+pub fn metadata_publisher() -> &'static str {
+    option_env!("FAIRRIS_PUBLISHER").filter(|publisher| !publisher.is_empty()).unwrap_or("Fairris")
+}
+
+/// A static bearer token for the PIT and collection services, i.e. of a service account.
+pub fn auth_token() -> Option<&'static str> {
+    option_env!("FAIRRIS_AUTH_TOKEN").filter(|token| !token.is_empty())
+}
+
+/// A static API key for the PIT and collection services, sent in `api_key_header`.
+pub fn api_key() -> Option<&'static str> {
+    option_env!("FAIRRIS_API_KEY").filter(|key| !key.is_empty())
+}
+
+pub fn api_key_header() -> &'static str {
+    option_env!("FAIRRIS_API_KEY_HEADER").unwrap_or("X-API-Key")
+}
+
+/// The OpenID Connect provider for logins. Without it, there is no login.
+pub fn oidc_issuer() -> Option<&'static str> {
+    option_env!("FAIRRIS_OIDC_ISSUER").filter(|issuer| !issuer.is_empty())
+}
+
+/// The id of this client at the OpenID Connect provider.
+pub fn oidc_client_id() -> &'static str {
+    option_env!("FAIRRIS_OIDC_CLIENT_ID").unwrap_or("fairris")
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+use super::{AttributeType, AttributeValue, Identifier, ProfileAttribute, ValueError, ValueKind};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contributor {
+    #[serde(rename = "21.T11148/3626040cadcac1571685", alias = "identifier")]
+    pub identifier: String,
+    #[serde(rename = "21.T11148/31cf58fed6ddd1b96102", alias = "role")]
+    pub role: String,
+}
+
+impl Contributor {
+    pub fn validate(&self) -> Result<Identifier, String> {
+        Identifier::parse(&self.identifier)
+    }
+}
+
+impl Default for Contributor {
+    fn default() -> Self {
+        Contributor {
+            identifier: String::new(),
+            role: CONTRIBUTOR_ROLES[0].into(),
+        }
+    }
+}
+
+/// Roles offered in the user interface. Records may contain other roles.
+pub const CONTRIBUTOR_ROLES: [&str; 6] = [
+    "author",
+    "institute",
+    "contactPerson",
+    "dataCollector",
+    "editor",
+    "rightsHolder",
+];
+
+impl AttributeType for Contributor {
+    const KIND: ValueKind = ValueKind::Object;
+
+    fn to_value(&self) -> AttributeValue {
+        AttributeValue::Object(json::to_value(self).expect("Contributors can always be serialized."))
+    }
+
+    fn from_value(value: AttributeValue) -> Result<Self, ValueError> {
+        let object = value.encode();
+        value
+            .into_object()
+            .and_then(|object| json::from_value(object).ok())
+            .ok_or(ValueError::Mismatch(ValueKind::Object, object))
+    }
+
+    /// Older records contain all contributors as a list in one entry.
+    fn from_values(value: AttributeValue) -> Result<Vec<Self>, ValueError> {
+        match value {
+            AttributeValue::Object(json::Value::Array(list)) => list
+                .into_iter()
+                .map(|object| Self::from_value(AttributeValue::Object(object)))
+                .collect(),
+            other => Self::from_value(other).map(|contributor| vec![contributor]),
+        }
+    }
+}
+
+/// An ordered list of contributors, one entry each. The order is kept when writing into a record.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/6534eca5d640dc878d87", name = "contributors", repeatable)]
+pub struct Contributors(pub Vec<Contributor>);
+
+/// The label of the attribute, not a list of the contributors.
+impl Display for Contributors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Contributors")
+    }
+}
@@ -0,0 +1,161 @@
+use std::{convert::TryFrom, fmt::Display, ops::{Deref, DerefMut}};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+use super::ProfileAttribute;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/aafd5fb4c7222e2d950a", name = "dateCreated")]
+pub struct DateCreated(pub DateTimeHandle);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/397d831aa3a9d18eb52c", name = "dateModified")]
+pub struct DateModified(pub DateTimeHandle);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateTimeHandle(DateTime<Utc>);
+
+impl Default for DateTimeHandle {
+    fn default() -> Self {
+        // TODO this default is for testing only. It should be empty and the component should handle the case of an invalid url properly.
+        DateTimeHandle(Utc::now())
+    }
+}
+
+impl Display for DateTimeHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_rfc3339())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    Empty,
+    /// The given text is not a (partial) ISO 8601 date.
+    Invalid(String),
+}
+
+impl Display for DateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateError::Empty => write!(f, "No date given."),
+            DateError::Invalid(s) => write!(
+                f,
+                "'{}' is not a valid date. Use ISO 8601, i.e. 2021-03-04T05:06:07+01:00, 2021-03-04 or 2021-03.",
+                s
+            ),
+        }
+    }
+}
+
+/// Formats of dates without timezone, from most to least precise.
+const LOCAL_DATE_TIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+];
+
+/// The timezone of dates without timezone.
+pub fn utc_offset() -> FixedOffset {
+    FixedOffset::east_opt(0).expect("UTC is a valid offset.")
+}
+
+impl From<DateTime<Utc>> for DateTimeHandle {
+    fn from(date: DateTime<Utc>) -> Self {
+        DateTimeHandle(date)
+    }
+}
+
+impl TryFrom<&String> for DateTimeHandle {
+    type Error = DateError;
+
+    /// Dates without timezone are interpreted as UTC.
+    fn try_from(s: &String) -> Result<Self, Self::Error> {
+        Self::parse(s, utc_offset())
+    }
+}
+
+impl DateTimeHandle {
+    pub fn now() -> Self {
+        DateTimeHandle(Utc::now())
+    }
+
+    /// Parses RFC 3339 dates as well as partial ISO 8601 dates.
+    /// Dates without time start at midnight, a missing month or day is the first one.
+    /// Dates without timezone are interpreted in the given timezone.
+    pub fn parse(s: &str, timezone: FixedOffset) -> Result<Self, DateError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(DateError::Empty);
+        }
+        if let Ok(date) = DateTime::parse_from_rfc3339(s) {
+            return Ok(DateTimeHandle(date.with_timezone(&Utc)));
+        }
+        let local = LOCAL_DATE_TIME_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+            .or_else(|| Self::parse_partial_date(s).and_then(|date| date.and_hms_opt(0, 0, 0)));
+        local
+            .and_then(|local| timezone.from_local_datetime(&local).single())
+            .map(|date| DateTimeHandle(date.with_timezone(&Utc)))
+            .ok_or_else(|| DateError::Invalid(s.to_owned()))
+    }
+
+    /// Parses `YYYY-MM-DD`, `YYYY-MM` and `YYYY`.
+    fn parse_partial_date(s: &str) -> Option<NaiveDate> {
+        let parts: Vec<&str> = s.split('-').collect();
+        let digits = [4, 2, 2];
+        let malformed = parts
+            .iter()
+            .zip(digits.iter())
+            .any(|(part, n)| part.len() != *n || !part.chars().all(|c| c.is_ascii_digit()));
+        if parts.len() > digits.len() || malformed {
+            return None;
+        }
+        let number = |i: usize| parts.get(i).map_or(Some(1), |part| part.parse::<u32>().ok());
+        NaiveDate::from_ymd_opt(number(0)? as i32, number(1)?, number(2)?)
+    }
+
+    /// RFC 3339 representation in the given timezone.
+    pub fn format_in(&self, timezone: FixedOffset) -> String {
+        self.0.with_timezone(&timezone).to_rfc3339()
+    }
+
+    /// The value for `<input type="datetime-local">` in the given timezone.
+    pub fn format_local_input(&self, timezone: FixedOffset) -> String {
+        self.0.with_timezone(&timezone).format("%Y-%m-%dT%H:%M").to_string()
+    }
+}
+
+// Makes inner value accessible and mutable with * operator.
+newtype_deref!(DateCreated, DateTimeHandle);
+newtype_deref!(DateModified, DateTimeHandle);
+newtype_deref!(DateTimeHandle, DateTime<Utc>);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTimeHandle {
+        DateTimeHandle(DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc))
+    }
+
+    #[test]
+    fn parses_partial_dates() {
+        let plus_two = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(DateTimeHandle::parse("2021-03-04T05:06:07+01:00", plus_two), Ok(utc("2021-03-04T04:06:07Z")));
+        assert_eq!(DateTimeHandle::parse("2021-03-04T05:06", plus_two), Ok(utc("2021-03-04T03:06:00Z")));
+        assert_eq!(DateTimeHandle::parse("2021-03-04", plus_two), Ok(utc("2021-03-03T22:00:00Z")));
+        assert_eq!(DateTimeHandle::parse(" 2021-03 ", utc_offset()), Ok(utc("2021-03-01T00:00:00Z")));
+        assert_eq!(DateTimeHandle::parse("2021", utc_offset()), Ok(utc("2021-01-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        let utc = utc_offset();
+        assert_eq!(DateTimeHandle::parse("", utc), Err(DateError::Empty));
+        for invalid in ["2021-13", "2021-02-30", "21-03-04", "2021-03-04-05", "2021-03-04T05", "yesterday"].iter() {
+            assert_eq!(DateTimeHandle::parse(invalid, utc), Err(DateError::Invalid(invalid.to_string())));
+        }
+    }
+}
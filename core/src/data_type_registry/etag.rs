@@ -0,0 +1,19 @@
+use super::ProfileAttribute;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/92e200311a56800b3e47", name = "etag")]
+pub struct Etag(pub String);
+
+impl Default for Etag {
+    fn default() -> Self {
+        Etag(r#"{ "sha256sum": "sha256 c50624fd5ddd2b9652b72e2d2eabcb31a54b777718ab6fb7e44b582c20239a7c" }"#.into())
+    }
+}
+
+impl Display for Etag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "etag")
+    }
+}
+
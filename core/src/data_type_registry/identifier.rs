@@ -0,0 +1,149 @@
+use std::fmt::Display;
+
+/// Identifiers of contributors: persons (ORCID) or organizations (ROR).
+/// Other identifiers (i.e. handles) are accepted, but can not be validated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Identifier {
+    /// Normalized to the form `0000-0002-1825-0097`.
+    Orcid(String),
+    /// Normalized to the form `04t3en479`.
+    Ror(String),
+    Other(String),
+}
+
+const ORCID_PREFIX: &str = "https://orcid.org/";
+const ROR_PREFIX: &str = "https://ror.org/";
+const CROCKFORD_BASE32: &str = "0123456789abcdefghjkmnpqrstvwxyz";
+
+impl Identifier {
+    /// Detects the kind of identifier and validates its checksum.
+    /// Inputs with a known prefix (or the typical shape of an ORCID) must be valid.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let without_scheme = input
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        if let Some(orcid) = without_scheme.strip_prefix("orcid.org/") {
+            Self::parse_orcid(orcid)
+        } else if let Some(ror) = without_scheme.strip_prefix("ror.org/") {
+            Self::parse_ror(ror)
+        } else if Self::looks_like_orcid(input) {
+            Self::parse_orcid(input)
+        } else if input.is_empty() {
+            Err("The identifier is empty.".into())
+        } else {
+            Ok(Identifier::Other(input.into()))
+        }
+    }
+
+    fn looks_like_orcid(input: &str) -> bool {
+        input.len() == 19
+            && input
+                .split('-')
+                .map(|block| block.len())
+                .eq([4, 4, 4, 4].iter().cloned())
+    }
+
+    fn parse_orcid(orcid: &str) -> Result<Self, String> {
+        let digits: Vec<char> = orcid.chars().filter(|c| *c != '-').collect();
+        let valid_shape = digits.len() == 16
+            && digits[..15].iter().all(|c| c.is_ascii_digit())
+            && (digits[15].is_ascii_digit() || digits[15] == 'X');
+        if !valid_shape {
+            return Err(format!("'{}' is not a valid ORCID.", orcid));
+        }
+        // ISO 7064 Mod 11-2
+        let total = digits[..15]
+            .iter()
+            .fold(0u32, |total, c| (total + c.to_digit(10).unwrap()) * 2);
+        let check = (12 - total % 11) % 11;
+        let expected = if check == 10 {
+            'X'
+        } else {
+            std::char::from_digit(check, 10).unwrap()
+        };
+        if digits[15] != expected {
+            return Err(format!("The checksum of ORCID '{}' is wrong.", orcid));
+        }
+        let normalized: String = digits
+            .chunks(4)
+            .map(|block| block.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("-");
+        Ok(Identifier::Orcid(normalized))
+    }
+
+    fn parse_ror(ror: &str) -> Result<Self, String> {
+        let ror = ror.to_lowercase();
+        // checked before slicing, which needs ASCII.
+        let valid_shape = ror.is_ascii()
+            && ror.len() == 9
+            && ror.starts_with('0')
+            && ror[..7].chars().all(|c| CROCKFORD_BASE32.contains(c))
+            && ror[7..].chars().all(|c| c.is_ascii_digit());
+        if !valid_shape {
+            return Err(format!("'{}' is not a valid ROR ID.", ror));
+        }
+        // ISO 7064 Mod 97-10 over the base32 decoded number
+        let number = ror[..7]
+            .chars()
+            .fold(0u64, |n, c| n * 32 + CROCKFORD_BASE32.find(c).unwrap() as u64);
+        let check = 98 - (number * 100) % 97;
+        if ror[7..].parse::<u64>() != Ok(check) {
+            return Err(format!("The checksum of ROR ID '{}' is wrong.", ror));
+        }
+        Ok(Identifier::Ror(ror))
+    }
+
+    /// The canonical representation which is written into records.
+    pub fn to_uri(&self) -> String {
+        match self {
+            Identifier::Orcid(id) => format!("{}{}", ORCID_PREFIX, id),
+            Identifier::Ror(id) => format!("{}{}", ROR_PREFIX, id),
+            Identifier::Other(id) => id.clone(),
+        }
+    }
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_uri())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orcid() {
+        assert_eq!(
+            Identifier::parse("https://orcid.org/0000-0002-1694-233X"),
+            Ok(Identifier::Orcid("0000-0002-1694-233X".into()))
+        );
+        assert!(Identifier::parse("0000-0002-1825-0097").is_ok());
+        assert!(Identifier::parse("0000-0002-1825-0098").is_err());
+    }
+
+    #[test]
+    fn ror() {
+        assert_eq!(
+            Identifier::parse("https://ror.org/04t3en479"),
+            Ok(Identifier::Ror("04t3en479".into()))
+        );
+        assert!(Identifier::parse("ror.org/04t3en478").is_err());
+    }
+
+    #[test]
+    fn ror_with_non_ascii_characters() {
+        assert!(Identifier::parse("ror.org/0abcdeé1").is_err());
+    }
+
+    #[test]
+    fn other() {
+        assert_eq!(
+            Identifier::parse("kitdm/author123"),
+            Ok(Identifier::Other("kitdm/author123".into()))
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+use strum::IntoEnumIterator;
+
+use crate::service_communication::{pit_record::PidRecordEntry, PidRecord};
+
+use super::{AttributeValue, HasProfileKey, Pid, ValueKind};
+use std::{ops::Deref, fmt::Display};
+
+/// Locations of an object, ordered by priority. The first one is the primary location.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Locations(pub Vec<Location>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub url: String,
+    pub protocol: Protocol,
+    /// The result of the last reachability check, if any.
+    pub check: Option<LocationCheck>,
+}
+
+/// How a location is accessed.
+#[derive(Clone, Copy, Debug, EnumIter, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Protocol {
+    #[serde(rename = "HTTP")]
+    Http,
+    S3,
+    /// An item of the RDA collection API.
+    CollectionApi,
+    Handle,
+}
+
+/// Associates protocols with their Display name (for the user interface).
+impl Display for Protocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Protocol::Http => write!(f, "HTTP"),
+            Protocol::S3 => write!(f, "S3"),
+            Protocol::CollectionApi => write!(f, "Collection API"),
+            Protocol::Handle => write!(f, "Handle"),
+        }
+    }
+}
+
+/// The answer to a HEAD request to a location.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationCheck {
+    /// The HTTP status. None, if the location could not be reached at all.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Date of the check (RFC 3339).
+    pub checked: String,
+}
+
+impl LocationCheck {
+    pub fn is_reachable(&self) -> bool {
+        self.status.is_some_and(|status| (200..400).contains(&status))
+    }
+}
+
+/// Protocol and check of a location, written next to the location itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LocationAccess {
+    location: String,
+    protocol: Protocol,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    check: Option<LocationCheck>,
+}
+
+impl Protocol {
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::iter().find(|p| p.to_string() == s)
+    }
+
+    /// Guesses the protocol from the URL.
+    pub fn infer(url: &str) -> Self {
+        let url = url.trim();
+        if url.starts_with("s3://") {
+            Protocol::S3
+        } else if url.starts_with(crate::config::collection_base_uri()) {
+            Protocol::CollectionApi
+        } else if url.contains("hdl.handle.net/") || (!url.contains("://") && Pid::parse_handle(url).is_some()) {
+            Protocol::Handle
+        } else {
+            Protocol::Http
+        }
+    }
+}
+
+impl Location {
+    pub fn new(url: String) -> Self {
+        let protocol = Protocol::infer(&url);
+        Self { url, protocol, check: None }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let url = self.url.trim();
+        if url.is_empty() {
+            return Err("The location is empty.".into());
+        }
+        if url.chars().any(char::is_whitespace) {
+            return Err("A location must not contain whitespace.".into());
+        }
+        let has_host = |scheme: &str| url.strip_prefix(scheme).is_some_and(|rest| !rest.is_empty() && !rest.starts_with('/'));
+        let valid = match self.protocol {
+            Protocol::Http | Protocol::CollectionApi => has_host("https://") || has_host("http://"),
+            Protocol::S3 => has_host("s3://") || has_host("https://"),
+            Protocol::Handle => Pid::parse_handle(url).is_some(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("Not a valid {} location.", self.protocol))
+        }
+    }
+
+    /// The URL to check the reachability with, if it can be checked.
+    pub fn check_url(&self) -> Option<String> {
+        self.validate().ok()?;
+        let url = self.url.trim();
+        match self.protocol {
+            Protocol::Http | Protocol::CollectionApi => Some(url.to_owned()),
+            Protocol::Handle => Some(Pid::from_url(url).to_url()),
+            Protocol::S3 => match url.strip_prefix("s3://") {
+                Some(path) => {
+                    let (bucket, key) = path.split_at(path.find('/').unwrap_or(path.len()));
+                    Some(format!("https://{}.s3.amazonaws.com{}", bucket, key))
+                }
+                None => Some(url.to_owned()),
+            },
+        }
+    }
+
+    /// Only protocols which can not be inferred and checks need to be written down.
+    fn access(&self) -> Option<LocationAccess> {
+        if self.protocol == Protocol::infer(&self.url) && self.check.is_none() {
+            return None;
+        }
+        Some(LocationAccess {
+            location: self.url.clone(),
+            protocol: self.protocol,
+            check: self.check.clone(),
+        })
+    }
+}
+
+impl Locations {
+    pub fn from_urls<I: IntoIterator<Item = String>>(urls: I) -> Self {
+        Self(urls.into_iter().map(Location::new).collect())
+    }
+
+    pub fn urls(&self) -> Vec<&str> {
+        self.0.iter().map(|l| l.url.as_str()).collect()
+    }
+
+    /// Key of the attribute which annotates locations with protocol and reachability.
+    /// FIXME this PID is not registered yet, so it is one of the `draft_types`.
+    pub fn get_access_key() -> Pid {
+        Pid("21.T11148/locationAccess".into())
+    }
+
+    pub fn get_access_key_name() -> &'static str {
+        "locationAccess"
+    }
+}
+
+/// The label of the attribute. Use `urls` to show the locations themselves.
+impl Display for Locations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Locations")
+    }
+}
+
+// Not derived: the locations are written under two keys, the URLs and their annotations
+// (`get_access_key`), which are merged again when reading.
+impl HasProfileKey for Locations {
+    fn get_key() -> Pid {
+        Pid("21.T11148/b8457812905b83046284".into())
+    }
+
+    fn get_key_name() -> &'static str {
+        "digitalObjectLocation"
+    }
+
+    fn write(&self, record: &mut PidRecord) {
+        for location in self.0.iter() {
+            record.add_value(
+                Self::get_key().deref().clone(),
+                Self::get_key_name().into(),
+                AttributeValue::Url(location.url.to_owned()),
+            )
+        }
+        for access in self.0.iter().filter_map(Location::access) {
+            record.add_value(
+                Self::get_access_key().deref().clone(),
+                Self::get_access_key_name().into(),
+                AttributeValue::Object(json::to_value(access).expect("Location access can always be serialized.")),
+            )
+        }
+    }
+}
+
+impl From<&PidRecordEntry> for Locations {
+    fn from(entry: &PidRecordEntry) -> Self {
+        if entry.key != *Self::get_key() {
+            return Self::default();
+        }
+        match entry.decode(ValueKind::Url) {
+            Ok(url) => Self::from_urls(vec![url.to_string()]),
+            Err(e) => {
+                log::error!("The given location could not be read: {}", e);
+                Self::default()
+            }
+        }
+    }
+}
+
+impl From<&PidRecord> for Locations {
+    fn from(record: &PidRecord) -> Self {
+        let mut locations = record
+            .entries
+            .get(&*Self::get_key())
+            .map(|list| {
+                let locations: Vec<String> = list
+                    .iter()
+                    .filter_map(|entry| entry.decode(ValueKind::Url).ok())
+                    .map(|url| url.to_string())
+                    .collect();
+                Locations::from_urls(locations)
+            })
+            .unwrap_or_default();
+        let accesses = record
+            .entries
+            .get(&*Self::get_access_key())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.decode(ValueKind::Object).ok().and_then(AttributeValue::into_object))
+            .filter_map(|value| json::from_value::<LocationAccess>(value).ok());
+        for access in accesses {
+            for location in locations.0.iter_mut().filter(|l| l.url == access.location) {
+                location.protocol = access.protocol;
+                location.check = access.check.clone();
+            }
+        }
+        locations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protocols_are_inferred_and_validated() {
+        assert_eq!(Protocol::infer("https://example.org/a.tiff"), Protocol::Http);
+        assert_eq!(Protocol::infer("s3://bucket/a.tiff"), Protocol::S3);
+        assert_eq!(Protocol::infer("https://hdl.handle.net/21.T11148/abc"), Protocol::Handle);
+        assert_eq!(Protocol::infer("21.T11148/abc"), Protocol::Handle);
+
+        assert!(Location::new("https://example.org/a".into()).validate().is_ok());
+        assert!(Location::new("https://".into()).validate().is_err());
+        assert!(Location::new("example.org/a b".into()).validate().is_err());
+        let mut wrong = Location::new("https://example.org/a".into());
+        wrong.protocol = Protocol::Handle;
+        assert!(wrong.validate().is_err());
+
+        assert_eq!(
+            Location::new("s3://bucket/dir/a.tiff".into()).check_url(),
+            Some("https://bucket.s3.amazonaws.com/dir/a.tiff".into())
+        );
+        assert_eq!(
+            Location::new("21.T11148/abc".into()).check_url(),
+            Some("https://hdl.handle.net/21.T11148/abc".into())
+        );
+    }
+
+    #[test]
+    fn annotations_roundtrip_through_the_record() {
+        let mut checked = Location::new("https://example.org/b".into());
+        checked.check = Some(LocationCheck {
+            status: Some(200),
+            content_type: Some("image/tiff".into()),
+            checked: "2021-03-04T05:06:07+00:00".into(),
+        });
+        let locations = Locations(vec![Location::new("https://example.org/a".into()), checked]);
+        let mut record = PidRecord::default();
+        locations.write(&mut record);
+        // locations with inferred protocol and without check are not annotated.
+        assert_eq!(record.entries[&Locations::get_access_key().0].len(), 1);
+        assert_eq!(Locations::from(&record), locations);
+
+        // the annotations are not published until their type is registered.
+        let published = record.without_draft_types();
+        assert!(!published.entries.contains_key(&Locations::get_access_key().0));
+        assert_eq!(Locations::from(&published).urls(), locations.urls());
+    }
+}
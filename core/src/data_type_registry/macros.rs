@@ -1,7 +1,3 @@
-#[macro_use]
-
-
-
 /// Error contains a copy of the given pid for later handling.
 /// This makes sense as it is not unlikely that the PID is valid, but
 /// can not be handled by the client. Err is in this case a marker for
@@ -37,11 +33,9 @@ macro_rules! try_from_entry {
                 if entry.key != *$given_type::get_key() {
                     return Err(None);
                 }
-                if let json::Value::String(s) = &entry.value {
-                    let pid: Pid = Pid(s.clone());
-                    $given_type::try_from(&pid).map_err(|e| Some(e))
-                } else {
-                    Err(None)
+                match entry.decode(ValueKind::Pid).map(AttributeValue::into_pid) {
+                    Ok(Some(pid)) => $given_type::try_from(&pid).map_err(|e| Some(e)),
+                    _ => Err(None),
                 }
             }
         }
@@ -0,0 +1,15 @@
+use crate::service_communication::MetadataObjectReference;
+
+use super::ProfileAttribute;
+use std::fmt::Display;
+
+/// A reference to a metadata document describing the object.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/134c84df7eca7bced374", name = "metadataObject")]
+pub struct MetadataObject(pub Option<MetadataObjectReference>);
+
+impl Display for MetadataObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Metadata object")
+    }
+}
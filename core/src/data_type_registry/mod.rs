@@ -0,0 +1,153 @@
+#[macro_use]
+mod macros;
+mod contributors;
+mod date_time;
+mod etag;
+mod identifier;
+mod locations;
+mod metadata_object;
+mod object_status;
+mod policy;
+mod profile;
+mod r#type;
+mod value;
+mod version;
+
+use std::{
+    fmt::Display,
+    ops::{Deref, DerefMut},
+};
+
+pub use contributors::*;
+pub use date_time::*;
+pub use etag::*;
+pub use identifier::*;
+pub use locations::*;
+pub use metadata_object::*;
+pub use object_status::*;
+pub use policy::*;
+pub use profile::*;
+pub use r#type::*;
+pub use value::*;
+pub use version::*;
+
+pub use fairris_derive::ProfileAttribute;
+
+use crate::service_communication::PidRecord;
+
+pub trait HasProfileKey {
+    /// Associates key-PID to a type.
+    fn get_key() -> Pid;
+    /// Associates key-name to a type.
+    fn get_key_name() -> &'static str;
+    /// Serializes the object into a record.
+    fn write(&self, record: &mut PidRecord);
+}
+
+impl<T, P> HasProfileKey for Result<T, Option<P>>
+where
+    T: HasProfileKey + Clone,
+    P: From<T> + Into<Pid> + Clone,
+{
+    fn get_key() -> Pid {
+        T::get_key()
+    }
+
+    fn get_key_name() -> &'static str {
+        T::get_key_name()
+    }
+
+    fn write(&self, record: &mut PidRecord) {
+        let maybe_pid: Option<Pid> = self
+            .as_ref()
+            .map(|ok| {
+                let pid: Option<Pid> = Some(P::from(ok.clone()).into());
+                pid
+            })
+            .unwrap_or_else(|pid| pid.clone().map(|p| p.into()));
+        if let Some(pid) = maybe_pid {
+            record.add_value(
+                Self::get_key().deref().clone(),
+                Self::get_key_name().into(),
+                AttributeValue::Pid(pid),
+            );
+        }
+    }
+}
+
+/// Types which are not registered in the data type registry yet. Their PIDs are placeholders,
+/// which a PIT service validating records rejects. Records only contain attributes of these
+/// types, or attributes referring to them, if `config::publish_draft_types()`.
+pub fn draft_types() -> Vec<Pid> {
+    vec![
+        Pid(policy::LIFECYCLE_KEY.0.into()),
+        Pid(policy::TOMBSTONE_KEY.0.into()),
+        Pid(policy::POLICY_TYPE.into()),
+        ObjectStatus::get_key(),
+        Tombstone::get_key(),
+        SupersededBy::get_key(),
+        IsNewVersionOf::get_key(),
+        HasNewVersion::get_key(),
+        Locations::get_access_key(),
+    ]
+}
+
+pub fn is_draft_type(pid: &str) -> bool {
+    draft_types().iter().any(|draft| draft.0 == pid)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pid(pub String);
+
+impl Deref for Pid {
+    type Target = String;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Pid {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Pid {
+    /// Makes handles resolvable using the global handle proxy.
+    pub fn to_url(&self) -> String {
+        if self.starts_with("http://") || self.starts_with("https://") {
+            self.0.clone()
+        } else {
+            format!("https://hdl.handle.net/{}", self.0)
+        }
+    }
+
+    /// Recognizes handles (prefix/suffix) and handle proxy URLs.
+    pub fn parse_handle(value: &str) -> Option<Self> {
+        let pid = Self::from_url(value.trim());
+        let slash = pid.find('/')?;
+        let (prefix, suffix) = (&pid[..slash], &pid[slash + 1..]);
+        let mut segments = prefix.split('.');
+        let naming_authority = segments
+            .next()
+            .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()));
+        let valid_prefix = naming_authority.is_some()
+            && segments.all(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric()));
+        let valid_suffix = !suffix.is_empty() && !suffix.chars().any(char::is_whitespace);
+        Some(pid).filter(|_| valid_prefix && valid_suffix)
+    }
+
+    /// Reverses `to_url` for handle proxy URLs. Other URLs are kept as they are.
+    pub fn from_url(url: &str) -> Self {
+        let pid = url
+            .trim_start_matches("https://hdl.handle.net/")
+            .trim_start_matches("http://hdl.handle.net/");
+        Pid(pid.into())
+    }
+}
+
+impl Display for Pid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.as_str())
+    }
+}
@@ -0,0 +1,111 @@
+use strum::IntoEnumIterator;
+
+use crate::service_communication::{pit_record::PidRecordEntry, PidRecord};
+
+use super::{AttributeValue, HasProfileKey, Pid, ProfileAttribute, ValueKind};
+use std::{fmt::Display, ops::Deref};
+
+// FIXME The types for status, tombstone and successor do not yet exist in the DTR,
+// so they are listed in `draft_types` and only published on request.
+
+/// Where an object is in its life. Active objects do not carry the attribute at all,
+/// so records of existing objects stay unchanged.
+#[derive(Clone, Copy, Debug, Default, EnumIter, PartialEq, Eq, Hash)]
+pub enum ObjectStatus {
+    #[default]
+    Active,
+    /// Still available, but should no longer be used.
+    Deprecated,
+    /// No longer available. Only the tombstone remains.
+    Withdrawn,
+}
+
+/// Associates statuses with their Display name (for the user interface).
+impl Display for ObjectStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjectStatus::Active => write!(f, "Active"),
+            ObjectStatus::Deprecated => write!(f, "Deprecated"),
+            ObjectStatus::Withdrawn => write!(f, "Withdrawn"),
+        }
+    }
+}
+
+impl ObjectStatus {
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::iter().find(|status| status.to_string().eq_ignore_ascii_case(s))
+    }
+
+    /// True if the object reached the end of its life.
+    pub fn is_tombstoned(&self) -> bool {
+        *self != ObjectStatus::Active
+    }
+
+    /// Style class to mark objects with this status in the user interface.
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            ObjectStatus::Active => "status-active",
+            ObjectStatus::Deprecated => "status-deprecated",
+            ObjectStatus::Withdrawn => "status-withdrawn",
+        }
+    }
+}
+
+// Not derived: the attribute is the enum itself, and active objects are written without it.
+impl HasProfileKey for ObjectStatus {
+    fn get_key() -> Pid {
+        Pid("21.T11148/objectStatus".into())
+    }
+
+    fn get_key_name() -> &'static str {
+        "objectStatus"
+    }
+
+    fn write(&self, record: &mut PidRecord) {
+        if self.is_tombstoned() {
+            record.add_value(
+                Self::get_key().deref().clone(),
+                Self::get_key_name().into(),
+                AttributeValue::String(self.to_string()),
+            )
+        }
+    }
+}
+
+impl From<&PidRecordEntry> for ObjectStatus {
+    fn from(entry: &PidRecordEntry) -> Self {
+        let value = entry.decode(ValueKind::String).ok();
+        match value.as_ref().and_then(AttributeValue::as_str).and_then(ObjectStatus::parse) {
+            Some(status) => status,
+            None => {
+                log::error!("Unknown object status: {}", entry.value);
+                ObjectStatus::default()
+            }
+        }
+    }
+}
+
+impl_from_record_single_entry!(ObjectStatus);
+
+/// A text explaining why the object was deprecated or withdrawn.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/tombstoneText", name = "tombstone")]
+pub struct Tombstone(pub Option<String>);
+
+impl Display for Tombstone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Tombstone")
+    }
+}
+
+/// The PID of the object replacing this one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/supersededBy", name = "supersededBy")]
+pub struct SupersededBy(pub Option<Pid>);
+
+impl Display for SupersededBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Superseded by")
+    }
+}
+
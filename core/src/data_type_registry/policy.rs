@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::service_communication::PidRecord;
+
+use super::{AttributeValue, HasProfileKey, Pid, ProfileAttribute, ValueKind};
+use std::fmt::Display;
+
+/// A reference to a policy object (an FDO itself), which describes
+/// license and lifecycle of this object.
+#[derive(Debug, Clone, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/8074aed799118ac263ad", name = "digitalObjectPolicy", kind = "Pid")]
+pub struct Policy(pub String);
+
+impl Display for Policy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Policy")
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        // TODO this PID is not a real policy object. It's for the visuals only.
+        Policy("21.T11148/37d0f4689c6ea3301787".into())
+    }
+}
+
+/// How often the object is expected to change.
+#[derive(Clone, Copy, Debug, Default, EnumIter, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Lifecycle {
+    #[default]
+    Static,
+    RegularUpdates,
+    IrregularUpdates,
+}
+
+/// Associates lifecycles with their Display name (for the user interface).
+impl Display for Lifecycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lifecycle::Static => write!(f, "static"),
+            Lifecycle::RegularUpdates => write!(f, "regular updates"),
+            Lifecycle::IrregularUpdates => write!(f, "irregular updates"),
+        }
+    }
+}
+
+impl Lifecycle {
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::iter().find(|l| l.to_string() == s)
+    }
+}
+
+/// SPDX identifiers offered in the user interface, with their full names.
+/// Policies with other SPDX identifiers can still be resolved and displayed.
+pub const SPDX_LICENSES: [(&str, &str); 12] = [
+    ("CC0-1.0", "Creative Commons Zero v1.0 Universal"),
+    ("CC-BY-4.0", "Creative Commons Attribution 4.0 International"),
+    ("CC-BY-SA-4.0", "Creative Commons Attribution Share Alike 4.0 International"),
+    ("CC-BY-NC-4.0", "Creative Commons Attribution Non Commercial 4.0 International"),
+    ("CC-BY-ND-4.0", "Creative Commons Attribution No Derivatives 4.0 International"),
+    ("ODbL-1.0", "Open Data Commons Open Database License v1.0"),
+    ("MIT", "MIT License"),
+    ("Apache-2.0", "Apache License 2.0"),
+    ("BSD-3-Clause", "BSD 3-Clause \"New\" or \"Revised\" License"),
+    ("MPL-2.0", "Mozilla Public License 2.0"),
+    ("GPL-3.0-or-later", "GNU General Public License v3.0 or later"),
+    ("LGPL-3.0-or-later", "GNU Lesser General Public License v3.0 or later"),
+];
+
+pub fn spdx_license_name(id: &str) -> Option<&'static str> {
+    SPDX_LICENSES
+        .iter()
+        .find(|(spdx, _)| *spdx == id)
+        .map(|(_, name)| *name)
+}
+
+/// The content of a policy object.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PolicyDefinition {
+    pub lifecycle: Lifecycle,
+    /// SPDX identifier of the license.
+    pub license: String,
+    /// Text to show when the object is no longer available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tombstone: Option<String>,
+}
+
+impl Default for PolicyDefinition {
+    fn default() -> Self {
+        Self {
+            lifecycle: Lifecycle::default(),
+            license: SPDX_LICENSES[1].0.into(),
+            tombstone: None,
+        }
+    }
+}
+
+const LICENSE_KEY: (&str, &str) = ("21.T11148/dc54ae4b6807f5887fda", "license");
+// FIXME PIDs for lifecycle and tombstone types and for the policy type do not yet exist in the DTR.
+pub(super) const LIFECYCLE_KEY: (&str, &str) = ("21.T11148/lifecycle", "lifecycle");
+pub(super) const TOMBSTONE_KEY: (&str, &str) = ("21.T11148/tombstone", "tombstone");
+pub(super) const POLICY_TYPE: &str = "21.T11148/Policy";
+
+impl PolicyDefinition {
+    /// The record of a policy object with this content.
+    /// Lifecycle, tombstone and type are drafts, see `draft_types`.
+    pub fn to_record(&self) -> PidRecord {
+        let mut record = PidRecord::default();
+        super::Profile::default().write(&mut record);
+        record.add_value(
+            super::DigitalObjectType::get_key().0,
+            super::DigitalObjectType::get_key_name().into(),
+            AttributeValue::Pid(Pid(POLICY_TYPE.into())),
+        );
+        record.add_value(
+            LICENSE_KEY.0.into(),
+            LICENSE_KEY.1.into(),
+            AttributeValue::Url(format!("https://spdx.org/licenses/{}", self.license)),
+        );
+        record.add_value(
+            LIFECYCLE_KEY.0.into(),
+            LIFECYCLE_KEY.1.into(),
+            AttributeValue::String(self.lifecycle.to_string()),
+        );
+        if let Some(tombstone) = &self.tombstone {
+            record.add_value(TOMBSTONE_KEY.0.into(), TOMBSTONE_KEY.1.into(), AttributeValue::String(tombstone.clone()));
+        }
+        record
+    }
+
+    /// Reads the content of a resolved policy object.
+    /// Returns None if the record does not contain a license.
+    pub fn from_record(record: &PidRecord) -> Option<Self> {
+        let value = |key: &str| {
+            record
+                .entries
+                .get(key)
+                .and_then(|list| list.first())
+                .and_then(|entry| entry.decode(ValueKind::String).ok())
+                .map(|value| value.to_string())
+        };
+        let license = value(LICENSE_KEY.0)?;
+        let license = license
+            .trim_start_matches("https://spdx.org/licenses/")
+            .trim_start_matches("http://spdx.org/licenses/")
+            .trim_end_matches(".html")
+            .to_owned();
+        Some(Self {
+            lifecycle: value(LIFECYCLE_KEY.0)
+                .and_then(|l| Lifecycle::parse(&l))
+                .unwrap_or_default(),
+            license,
+            tombstone: value(TOMBSTONE_KEY.0),
+        })
+    }
+
+    /// A human readable summary of this policy.
+    pub fn summary(&self) -> String {
+        let license = match spdx_license_name(&self.license) {
+            Some(name) => format!("{} ({})", name, self.license),
+            None => self.license.clone(),
+        };
+        let mut summary = format!("License: {}. Lifecycle: {}.", license, self.lifecycle);
+        if let Some(tombstone) = &self.tombstone {
+            summary.push_str(&format!(" Tombstone: \"{}\"", tombstone));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_record_roundtrip() {
+        let definition = PolicyDefinition {
+            lifecycle: Lifecycle::RegularUpdates,
+            license: "CC0-1.0".into(),
+            tombstone: Some("Withdrawn on request of the authors.".into()),
+        };
+        assert_eq!(PolicyDefinition::from_record(&definition.to_record()), Some(definition));
+        assert_eq!(PolicyDefinition::from_record(&PidRecord::default()), None);
+    }
+
+    #[test]
+    fn policy_records_without_draft_types_keep_profile_and_license() {
+        let record = PolicyDefinition::default().to_record().without_draft_types();
+        let mut keys: Vec<&str> = record.entries.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        let mut expected = vec![super::super::Profile::get_key().0, LICENSE_KEY.0.to_owned()];
+        expected.sort_unstable();
+        assert_eq!(keys, expected);
+        assert_eq!(
+            PolicyDefinition::from_record(&record).map(|d| d.license),
+            Some(PolicyDefinition::default().license)
+        );
+    }
+}
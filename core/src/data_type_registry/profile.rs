@@ -1,11 +1,10 @@
 use std::{ops::Deref, convert::TryFrom, fmt::Display};
 
 use strum::IntoEnumIterator;
-use serde_json as json;
 
 use crate::service_communication::{pit_record::PidRecordEntry, PidRecord};
 
-use super::{HasProfileKey, Pid};
+use super::{AttributeValue, HasProfileKey, Pid, ValueKind};
 
 /// A list of profiles that are known to this client.\
 /// Note: If a profile is added, there is only a need to adjust two things:
@@ -13,8 +12,9 @@ use super::{HasProfileKey, Pid};
 /// - the Display::fmt() implementation to associate a human readable name for the UI.
 ///
 /// Fortunately, the compiler will remember you to do so.
-#[derive(Clone, Copy, Debug, EnumIter, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, EnumIter, PartialEq, Eq)]
 pub enum Profile {
+    #[default]
     Testbed,
 }
 
@@ -38,6 +38,8 @@ impl Display for Profile {
     }
 }
 
+// Not derived: the attribute is the enum itself, and unknown profile PIDs are kept
+// as `Err(Some(pid))` (see `MaybeProfile`) instead of becoming a default.
 impl HasProfileKey for Profile {
     fn get_key() -> Pid {
         Pid("21.T11148/076759916209e5d62bd5".into())
@@ -48,16 +50,10 @@ impl HasProfileKey for Profile {
     }
     
     fn write(&self, record: &mut PidRecord) {
-        record.add_attribute(
+        record.add_value(
             Self::get_key().deref().clone(),
             Self::get_key_name().into(),
-            Pid::from(self).deref().to_owned().into());
-    }
-}
-
-impl Default for Profile {
-    fn default() -> Self {
-        Profile::Testbed
+            AttributeValue::Pid(Pid::from(self)));
     }
 }
 
@@ -1,12 +1,11 @@
 use std::{ops::Deref, convert::TryFrom, fmt::Display};
 
-use serde_json as json;
 
-use super::{HasProfileKey, Pid};
+use super::{AttributeValue, HasProfileKey, Pid, ValueKind};
 use crate::service_communication::{pit_record::PidRecordEntry, PidRecord};
 use strum::IntoEnumIterator;
 
-#[derive(Clone, Copy, Debug, EnumIter, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, EnumIter, PartialEq, Eq)]
 pub enum DigitalObjectType {
     // a collection (api) item with all related objects to replicate the results.
     Publication,
@@ -20,6 +19,7 @@ pub enum DigitalObjectType {
     // a collection (api) item collecting ManuscriptPages.
     Manuscript,
     // a manuscript page of any format with any kind of additional metadata.
+    #[default]
     ManuscriptPage,
     // one or multiple annotations belonging all to the same unit.
     // might be a collection (api) item.
@@ -44,7 +44,7 @@ impl From<&DigitalObjectType> for Pid {
     }
 }
 
-/// Associates profiles with their Display name (for the user interface).
+/// Names of the types as shown in the user interface. Annotations use their W3C class.
 impl Display for DigitalObjectType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -59,6 +59,8 @@ impl Display for DigitalObjectType {
     }
 }
 
+// Not derived: the attribute is the enum itself, and unknown type PIDs are kept
+// as `Err(Some(pid))` (see `try_from_all`) instead of becoming a default.
 impl HasProfileKey for DigitalObjectType {
     fn get_key() -> Pid {
         Pid("21.T11148/c83481d4bf467110e7c9".into())
@@ -69,20 +71,14 @@ impl HasProfileKey for DigitalObjectType {
     }
 
     fn write(&self, record: &mut PidRecord) {
-        record.add_attribute(
+        record.add_value(
             Self::get_key().deref().clone(),
             DigitalObjectType::get_key_name().into(),
-            Pid::from(self).deref().to_owned().into(),
+            AttributeValue::Pid(Pid::from(self)),
         )
     }
 }
 
-impl Default for DigitalObjectType {
-    fn default() -> Self {
-        DigitalObjectType::ManuscriptPage
-    }
-}
-
 try_from_all!(DigitalObjectType, Pid);
 
 impl From<DigitalObjectType> for Pid {
@@ -0,0 +1,370 @@
+use std::{convert::TryFrom, fmt::Display};
+
+use serde_json as json;
+
+use super::{DateError, DateTimeHandle, Pid};
+
+/// The basic types of attribute values in the data type registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    String,
+    Number,
+    Boolean,
+    /// An RFC 3339 date.
+    Date,
+    /// A handle, i.e. a reference to another object or a type.
+    Pid,
+    Url,
+    /// A JSON object or list.
+    Object,
+}
+
+impl Display for ValueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueKind::String => write!(f, "string"),
+            ValueKind::Number => write!(f, "number"),
+            ValueKind::Boolean => write!(f, "boolean"),
+            ValueKind::Date => write!(f, "date"),
+            ValueKind::Pid => write!(f, "PID"),
+            ValueKind::Url => write!(f, "URL"),
+            ValueKind::Object => write!(f, "object"),
+        }
+    }
+}
+
+/// A typed attribute value.
+///
+/// The PIT service only parses json strings as values, so every value is encoded
+/// into a string when it is written into a record (`encode`). Reading (`decode`)
+/// accepts these strings as well as plain JSON values, as other clients may write them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttributeValue {
+    String(String),
+    Number(json::Number),
+    Boolean(bool),
+    Date(DateTimeHandle),
+    Pid(Pid),
+    Url(String),
+    /// A JSON object or list.
+    Object(json::Value),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValueError {
+    /// The value can not be read as the expected kind.
+    Mismatch(ValueKind, json::Value),
+    Date(DateError),
+}
+
+impl Display for ValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueError::Mismatch(kind, value) => write!(f, "{} is not a valid {}.", value, kind),
+            ValueError::Date(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl AttributeValue {
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            AttributeValue::String(_) => ValueKind::String,
+            AttributeValue::Number(_) => ValueKind::Number,
+            AttributeValue::Boolean(_) => ValueKind::Boolean,
+            AttributeValue::Date(_) => ValueKind::Date,
+            AttributeValue::Pid(_) => ValueKind::Pid,
+            AttributeValue::Url(_) => ValueKind::Url,
+            AttributeValue::Object(_) => ValueKind::Object,
+        }
+    }
+
+    /// The value as the PIT service expects it: always a json string.
+    pub fn encode(&self) -> json::Value {
+        json::Value::String(self.to_string())
+    }
+
+    /// Reads a value of the given kind from a record.
+    /// Scalars may be given as strings or as JSON values, objects as JSON or as json strings.
+    /// Strings can be read from any value but null.
+    /// Content is not validated here, i.e. locations and handles are checked by their attribute types.
+    pub fn decode(kind: ValueKind, value: &json::Value) -> Result<Self, ValueError> {
+        let mismatch = || ValueError::Mismatch(kind, value.clone());
+        match (kind, value) {
+            (ValueKind::String, json::Value::String(s)) => Ok(AttributeValue::String(s.clone())),
+            // i.e. checksums, which are json text in records of this client.
+            (ValueKind::String, json::Value::Null) => Err(mismatch()),
+            (ValueKind::String, _) => Ok(AttributeValue::String(value.to_string())),
+            (ValueKind::Number, json::Value::Number(n)) => Ok(AttributeValue::Number(n.clone())),
+            (ValueKind::Number, json::Value::String(s)) => {
+                s.trim().parse().map(AttributeValue::Number).map_err(|_| mismatch())
+            }
+            (ValueKind::Boolean, json::Value::Bool(b)) => Ok(AttributeValue::Boolean(*b)),
+            (ValueKind::Boolean, json::Value::String(s)) => match s.trim() {
+                "true" => Ok(AttributeValue::Boolean(true)),
+                "false" => Ok(AttributeValue::Boolean(false)),
+                _ => Err(mismatch()),
+            },
+            (ValueKind::Date, json::Value::String(s)) => {
+                DateTimeHandle::try_from(s).map(AttributeValue::Date).map_err(ValueError::Date)
+            }
+            (ValueKind::Pid, json::Value::String(s)) => Ok(AttributeValue::Pid(Pid(s.clone()))),
+            (ValueKind::Url, json::Value::String(s)) => Ok(AttributeValue::Url(s.clone())),
+            (ValueKind::Object, json::Value::Object(_)) | (ValueKind::Object, json::Value::Array(_)) => {
+                Ok(AttributeValue::Object(value.clone()))
+            }
+            (ValueKind::Object, json::Value::String(s)) => match json::from_str(s) {
+                Ok(object @ json::Value::Object(_)) | Ok(object @ json::Value::Array(_)) => {
+                    Ok(AttributeValue::Object(object))
+                }
+                _ => Err(mismatch()),
+            },
+            _ => Err(mismatch()),
+        }
+    }
+
+    /// The text of strings, PIDs and URLs.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttributeValue::String(s) | AttributeValue::Url(s) => Some(s),
+            AttributeValue::Pid(pid) => Some(pid),
+            _ => None,
+        }
+    }
+
+    pub fn into_pid(self) -> Option<Pid> {
+        match self {
+            AttributeValue::Pid(pid) => Some(pid),
+            _ => None,
+        }
+    }
+
+    pub fn into_date(self) -> Option<DateTimeHandle> {
+        match self {
+            AttributeValue::Date(date) => Some(date),
+            _ => None,
+        }
+    }
+
+    pub fn into_object(self) -> Option<json::Value> {
+        match self {
+            AttributeValue::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+}
+
+/// Types stored as one attribute value. Attributes wrapping them can be derived
+/// with `#[derive(ProfileAttribute)]`.
+pub trait AttributeType: Sized {
+    /// The kind values are read as.
+    const KIND: ValueKind;
+
+    fn to_value(&self) -> AttributeValue;
+
+    fn from_value(value: AttributeValue) -> Result<Self, ValueError>;
+
+    /// Reads all values of one entry of a repeatable attribute. By default, an entry has one value.
+    fn from_values(value: AttributeValue) -> Result<Vec<Self>, ValueError> {
+        Self::from_value(value).map(|value| vec![value])
+    }
+}
+
+impl AttributeType for String {
+    const KIND: ValueKind = ValueKind::String;
+
+    fn to_value(&self) -> AttributeValue {
+        AttributeValue::String(self.clone())
+    }
+
+    fn from_value(value: AttributeValue) -> Result<Self, ValueError> {
+        Ok(value.to_string())
+    }
+}
+
+impl AttributeType for Pid {
+    const KIND: ValueKind = ValueKind::Pid;
+
+    fn to_value(&self) -> AttributeValue {
+        AttributeValue::Pid(self.clone())
+    }
+
+    fn from_value(value: AttributeValue) -> Result<Self, ValueError> {
+        match value {
+            AttributeValue::Object(_) => Err(ValueError::Mismatch(ValueKind::Pid, value.encode())),
+            other => Ok(Pid(other.to_string())),
+        }
+    }
+}
+
+impl AttributeType for bool {
+    const KIND: ValueKind = ValueKind::Boolean;
+
+    fn to_value(&self) -> AttributeValue {
+        AttributeValue::Boolean(*self)
+    }
+
+    fn from_value(value: AttributeValue) -> Result<Self, ValueError> {
+        match value {
+            AttributeValue::Boolean(b) => Ok(b),
+            other => Err(ValueError::Mismatch(ValueKind::Boolean, other.encode())),
+        }
+    }
+}
+
+impl AttributeType for DateTimeHandle {
+    const KIND: ValueKind = ValueKind::Date;
+
+    fn to_value(&self) -> AttributeValue {
+        AttributeValue::Date(self.clone())
+    }
+
+    fn from_value(value: AttributeValue) -> Result<Self, ValueError> {
+        match value {
+            AttributeValue::Date(date) => Ok(date),
+            other => DateTimeHandle::try_from(&other.to_string()).map_err(ValueError::Date),
+        }
+    }
+}
+
+/// The string encoding of the value, as written into records.
+impl Display for AttributeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeValue::String(s) | AttributeValue::Url(s) => write!(f, "{}", s),
+            AttributeValue::Number(n) => write!(f, "{}", n),
+            AttributeValue::Boolean(b) => write!(f, "{}", b),
+            AttributeValue::Date(date) => write!(f, "{}", date.to_rfc3339()),
+            AttributeValue::Pid(pid) => write!(f, "{}", pid),
+            AttributeValue::Object(object) => write!(f, "{}", object),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data_type_registry::*,
+        service_communication::{pit_record::PidRecordEntry, MetadataContext, MetadataObjectReference, PidRecord, ResourceReference},
+    };
+
+    /// Writes the attribute and checks that only json strings reach the record.
+    fn written<T: HasProfileKey>(attribute: &T) -> PidRecord {
+        let mut record = PidRecord::default();
+        attribute.write(&mut record);
+        assert!(!record.entries.is_empty(), "{} was not written.", T::get_key_name());
+        for entry in record.entries.values().flatten() {
+            assert!(entry.value.is_string(), "{} is not encoded: {}", entry.name, entry.value);
+        }
+        record
+    }
+
+    fn entry(value: json::Value) -> PidRecordEntry {
+        PidRecordEntry {
+            value,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decodes_strings_and_plain_json() {
+        let decode = |kind, value: json::Value| AttributeValue::decode(kind, &value);
+        assert_eq!(decode(ValueKind::String, json::json!(2)), Ok(AttributeValue::String("2".into())));
+        assert_eq!(decode(ValueKind::Number, json::json!(" 3.5")).map(|n| n.to_string()), Ok("3.5".into()));
+        assert_eq!(decode(ValueKind::Number, json::json!(3)).map(|n| n.encode()), Ok(json::json!("3")));
+        assert_eq!(decode(ValueKind::Boolean, json::json!("true")), Ok(AttributeValue::Boolean(true)));
+        assert_eq!(decode(ValueKind::Boolean, json::json!(false)), Ok(AttributeValue::Boolean(false)));
+        assert_eq!(decode(ValueKind::Pid, json::json!("21.T11148/a")), Ok(AttributeValue::Pid(Pid("21.T11148/a".into()))));
+        assert_eq!(
+            decode(ValueKind::Object, json::json!(r#"{"a": [1]}"#)),
+            decode(ValueKind::Object, json::json!({"a": [1]}))
+        );
+        assert_eq!(
+            decode(ValueKind::Date, json::json!("2021-03-04")).map(|d| d.encode()),
+            Ok(json::json!("2021-03-04T00:00:00+00:00"))
+        );
+
+        assert!(matches!(decode(ValueKind::Date, json::json!("yesterday")), Err(ValueError::Date(_))));
+        for (kind, value) in [
+            (ValueKind::String, json::Value::Null),
+            (ValueKind::Number, json::json!("1.0.0")),
+            (ValueKind::Boolean, json::json!("yes")),
+            (ValueKind::Pid, json::json!(1)),
+            (ValueKind::Url, json::Value::Null),
+            (ValueKind::Object, json::json!("text")),
+        ] {
+            assert_eq!(decode(kind, value.clone()), Err(ValueError::Mismatch(kind, value)));
+        }
+    }
+
+    #[test]
+    fn every_attribute_roundtrips_through_a_record() {
+        assert_eq!(Profile::try_from(&written(&Profile::Testbed)), Ok(Profile::Testbed));
+        let paper = DigitalObjectType::Paper;
+        assert_eq!(DigitalObjectType::try_from(&written(&paper)), Ok(paper));
+
+        let mut checked = Location::new("https://example.org/b".into());
+        checked.check = Some(LocationCheck {
+            status: Some(404),
+            content_type: None,
+            checked: "2021-03-04T05:06:07+00:00".into(),
+        });
+        let locations = Locations(vec![Location::new("21.T11148/a".into()), checked]);
+        assert_eq!(Locations::from(&written(&locations)), locations);
+
+        let date = DateTimeHandle::parse("2021-03-04T05:06:07.5+01:00", utc_offset()).unwrap();
+        let created = DateCreated(date.clone());
+        assert_eq!(DateCreated::from(&written(&created)), created);
+        let modified = DateModified(date);
+        assert_eq!(DateModified::from(&written(&modified)), modified);
+
+        assert_eq!(Etag::from(&written(&Etag::default())), Etag::default());
+        assert_eq!(Policy::from(&written(&Policy::default())), Policy::default());
+        let version = Version("2.1.0-rc.1".into());
+        assert_eq!(Version::from(&written(&version)), version);
+
+        let metadata = MetadataObject(Some(MetadataObjectReference {
+            context: MetadataContext::Annotating,
+            resource: ResourceReference::Handle("21.T11148/annotations".into()),
+        }));
+        assert_eq!(MetadataObject::from(&written(&metadata)), metadata);
+        let contributors = Contributors(vec![
+            Contributor {
+                identifier: "https://orcid.org/0000-0002-1825-0097".into(),
+                role: "author".into(),
+            },
+            Contributor {
+                identifier: "https://ror.org/04t3en479".into(),
+                role: "institute".into(),
+            },
+        ]);
+        assert_eq!(Contributors::from(&written(&contributors)), contributors);
+        assert_eq!(written(&contributors).entries[&Contributors::get_key().0].len(), 2);
+
+        let status = ObjectStatus::Withdrawn;
+        assert_eq!(ObjectStatus::from(&written(&status)), status);
+        let tombstone = Tombstone(Some("Withdrawn on request of the authors.".into()));
+        assert_eq!(Tombstone::from(&written(&tombstone)), tombstone);
+        let successor = Some(Pid("21.T11148/next".into()));
+        assert_eq!(SupersededBy::from(&written(&SupersededBy(successor.clone()))), SupersededBy(successor.clone()));
+        assert_eq!(HasNewVersion::from(&written(&HasNewVersion(successor.clone()))), HasNewVersion(successor));
+        let predecessor = Some(Pid("21.T11148/previous".into()));
+        assert_eq!(IsNewVersionOf::from(&written(&IsNewVersionOf(predecessor.clone()))), IsNewVersionOf(predecessor));
+    }
+
+    #[test]
+    fn attributes_accept_plain_json_of_other_clients() {
+        assert_eq!(Version::from(&entry(json::json!(2))), Version("2".into()));
+        let orcid = json::json!({"identifier": "https://orcid.org/0000-0002-1825-0097", "role": "author"});
+        assert_eq!(Contributors::from(&entry(orcid.clone())).0.len(), 1);
+        // older records of this client list all contributors in one entry.
+        let list = json::json!([orcid, {"identifier": "https://ror.org/04t3en479", "role": "institute"}]);
+        assert_eq!(Contributors::from(&entry(json::json!(list.to_string()))).0.len(), 2);
+        let checksum = json::json!({"sha256sum": "sha256 c506"});
+        assert_eq!(Etag::from(&entry(checksum.clone())), Etag(checksum.to_string()));
+        let reference = MetadataObject::from(&entry(json::json!("https://example.org/m.xml"))).0;
+        assert_eq!(reference.map(|m| m.resource), Some(ResourceReference::Url("https://example.org/m.xml".into())));
+        assert_eq!(SupersededBy::from(&entry(json::json!(1))), SupersededBy(None));
+    }
+}
@@ -0,0 +1,215 @@
+use strum::IntoEnumIterator;
+
+use super::{Pid, ProfileAttribute};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/c692273deb2772da307f", name = "version")]
+pub struct Version(pub String);
+
+/// Shows the name of the attribute. The version number itself is `.0`.
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Version")
+    }
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Version("1.0.0".into())
+    }
+}
+impl Version {
+    /// Parses the version as semantic version.
+    pub fn semver(&self) -> Result<SemVer, String> {
+        SemVer::parse(&self.0)
+    }
+
+    /// The version following this one.
+    pub fn bump(&self, bump: Bump) -> Result<Version, String> {
+        self.semver().map(|v| Version(v.bump(bump).to_string()))
+    }
+}
+
+/// Which part of a semantic version changes.
+#[derive(Clone, Copy, Debug, EnumIter, PartialEq, Eq)]
+pub enum Bump {
+    /// Incompatible changes.
+    Major,
+    /// Additions which keep compatibility.
+    Minor,
+    /// Corrections.
+    Patch,
+}
+
+/// Associates bumps with their Display name (for the user interface).
+impl Display for Bump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bump::Major => write!(f, "major"),
+            Bump::Minor => write!(f, "minor"),
+            Bump::Patch => write!(f, "patch"),
+        }
+    }
+}
+
+impl Bump {
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::iter().find(|b| b.to_string() == s)
+    }
+}
+
+/// A semantic version (https://semver.org) as MAJOR.MINOR.PATCH[-PRERELEASE][+BUILD].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SemVer {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre_release: Option<String>,
+    pub build: Option<String>,
+}
+
+impl SemVer {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        let (rest, build) = match s.find('+') {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+        let (core, pre_release) = match rest.find('-') {
+            Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            None => (rest, None),
+        };
+        let numbers: Vec<&str> = core.split('.').collect();
+        if numbers.len() != 3 {
+            return Err(format!("'{}' does not have the form MAJOR.MINOR.PATCH.", s));
+        }
+        let number = |n: &str| -> Result<u64, String> {
+            if n.is_empty() || !n.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("'{}' is not a number.", n));
+            }
+            if n.len() > 1 && n.starts_with('0') {
+                return Err(format!("'{}' has a leading zero.", n));
+            }
+            n.parse().map_err(|e| format!("'{}' is not a number: {}", n, e))
+        };
+        let identifiers = |part: Option<&str>, name: &str| -> Result<Option<String>, String> {
+            match part {
+                None => Ok(None),
+                Some(p) => {
+                    let valid = p.split('.').all(|id| {
+                        !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                    });
+                    if valid {
+                        Ok(Some(p.to_owned()))
+                    } else {
+                        Err(format!("'{}' is not a valid {}.", p, name))
+                    }
+                }
+            }
+        };
+        Ok(Self {
+            major: number(numbers[0])?,
+            minor: number(numbers[1])?,
+            patch: number(numbers[2])?,
+            pre_release: identifiers(pre_release, "pre-release")?,
+            build: identifiers(build, "build metadata")?,
+        })
+    }
+
+    /// The next version. Lower parts are reset to zero.
+    /// A pre-release becomes its release if the bumped part is the lowest set part,
+    /// i.e. 2.0.0-rc.1 bumped major is 2.0.0.
+    pub fn bump(&self, bump: Bump) -> Self {
+        let is_pre_release = self.pre_release.is_some();
+        let (major, minor, patch) = match bump {
+            Bump::Major if is_pre_release && self.minor == 0 && self.patch == 0 => {
+                (self.major, 0, 0)
+            }
+            Bump::Major => (self.major + 1, 0, 0),
+            Bump::Minor if is_pre_release && self.patch == 0 => (self.major, self.minor, 0),
+            Bump::Minor => (self.major, self.minor + 1, 0),
+            Bump::Patch if is_pre_release => (self.major, self.minor, self.patch),
+            Bump::Patch => (self.major, self.minor, self.patch + 1),
+        };
+        Self {
+            major,
+            minor,
+            patch,
+            pre_release: None,
+            build: None,
+        }
+    }
+}
+
+impl Display for SemVer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre_release) = &self.pre_release {
+            write!(f, "-{}", pre_release)?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
+    }
+}
+
+// FIXME The types for version relations do not yet exist in the DTR.
+// Until then, the relations are kept locally (see `draft_types`).
+
+/// The PID of the previous version of this object.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/isNewVersionOf", name = "isNewVersionOf")]
+pub struct IsNewVersionOf(pub Option<Pid>);
+
+impl Display for IsNewVersionOf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Is new version of")
+    }
+}
+
+/// The PID of the next version of this object.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/hasNewVersion", name = "hasNewVersion")]
+pub struct HasNewVersion(pub Option<Pid>);
+
+impl Display for HasNewVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Has new version")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_semantic_versions() {
+        let v = SemVer::parse("1.2.3-rc.1+build.5").unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (1, 2, 3));
+        assert_eq!(v.pre_release.as_deref(), Some("rc.1"));
+        assert_eq!(v.build.as_deref(), Some("build.5"));
+        assert_eq!(v.to_string(), "1.2.3-rc.1+build.5");
+
+        for invalid in ["1.0", "1.0.0.0", "01.0.0", "1.a.0", "1.0.0-", "1.0.0-rc..1", ""].iter() {
+            assert!(SemVer::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn bumps_follow_semver_rules() {
+        let bump = |v: &str, b: Bump| SemVer::parse(v).unwrap().bump(b).to_string();
+        assert_eq!(bump("1.2.3", Bump::Major), "2.0.0");
+        assert_eq!(bump("1.2.3", Bump::Minor), "1.3.0");
+        assert_eq!(bump("1.2.3", Bump::Patch), "1.2.4");
+        assert_eq!(bump("1.2.3+build", Bump::Patch), "1.2.4");
+        assert_eq!(bump("2.0.0-rc.1", Bump::Major), "2.0.0");
+        assert_eq!(bump("2.1.0-rc.1", Bump::Major), "3.0.0");
+        assert_eq!(bump("2.1.0-rc.1", Bump::Minor), "2.1.0");
+        assert_eq!(bump("2.1.1-rc.1", Bump::Minor), "2.2.0");
+        assert_eq!(bump("2.1.1-rc.1", Bump::Patch), "2.1.1");
+        assert_eq!(Version("1.0.0".into()).bump(Bump::Minor), Ok(Version("1.1.0".into())));
+        assert!(Version("first".into()).bump(Bump::Minor).is_err());
+    }
+}
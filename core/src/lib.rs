@@ -0,0 +1,23 @@
+//! The model of FAIR digital objects used by the FAIRRIS client: records, data types and
+//! profiles, their validation, and the requests to the services.
+//!
+//! It does not depend on yew or a browser, so it can be used by native tools and tests.
+//! The command line client (`fairris-cli`) is built with the feature `cli`.
+
+#[macro_use]
+extern crate strum;
+// code of `#[derive(ProfileAttribute)]` refers to this crate by name.
+extern crate self as fairris_core;
+
+pub mod app_state;
+pub mod auth;
+pub mod config;
+pub mod pidinfo;
+
+pub mod service_communication;
+pub mod data_type_registry;
+pub mod collection_service;
+
+pub mod metadata_mapping;
+pub mod ro_crate;
+pub mod relation_graph;
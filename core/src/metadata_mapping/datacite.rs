@@ -0,0 +1,280 @@
+//! DataCite metadata schema (kernel 4.x) as XML and as DataCite JSON.
+
+use serde_json as json;
+
+use crate::{config, data_type_registry::Identifier};
+
+use super::{xml, Description, Relation};
+
+const NAMESPACE: &str = "http://datacite.org/schema/kernel-4";
+const SCHEMA_LOCATION: &str = "http://schema.datacite.org/meta/kernel-4.4/metadata.xsd";
+
+/// Maps digital object types to the controlled list of DataCite resource types.
+fn resource_type_general(resource_type: Option<&str>) -> &'static str {
+    match resource_type {
+        Some("Publication") | Some("Paper") => "Text",
+        Some("Algorithm") | Some("Application") => "Software",
+        Some("Manuscript") => "Collection",
+        Some("ManuscriptPage") => "Image",
+        _ => "Other",
+    }
+}
+
+/// Written instead of creators if the object has none.
+const UNKNOWN_CREATOR: &str = ":unav";
+
+/// The DataCite nameType and the scheme (name and URI) of a creator identifier.
+fn name_scheme(identifier: &Identifier) -> Option<(&'static str, &'static str, &'static str)> {
+    match identifier {
+        Identifier::Orcid(_) => Some(("Personal", "ORCID", "https://orcid.org/")),
+        Identifier::Ror(_) => Some(("Organizational", "ROR", "https://ror.org/")),
+        Identifier::Other(_) => None,
+    }
+}
+
+/// Creators are only known by their identifier, so it is also used as their name.
+fn parse_creators<'a>(names: impl Iterator<Item = &'a str>) -> Vec<Identifier> {
+    names
+        .filter(|name| *name != UNKNOWN_CREATOR)
+        .filter_map(|name| Identifier::parse(name).ok())
+        .collect()
+}
+
+pub fn to_xml(d: &Description) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<resource xmlns=\"{}\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xsi:schemaLocation=\"{} {}\">\n",
+        NAMESPACE, NAMESPACE, SCHEMA_LOCATION
+    ));
+    if let Some(identifier) = &d.identifier {
+        out.push_str(&format!(
+            "  <identifier identifierType=\"Handle\">{}</identifier>\n",
+            xml::escape(identifier)
+        ));
+    }
+    out.push_str("  <creators>\n");
+    for creator in d.creators.iter() {
+        let uri = xml::escape(&creator.to_uri());
+        match name_scheme(creator) {
+            Some((name_type, scheme, scheme_uri)) => out.push_str(&format!(
+                "    <creator><creatorName nameType=\"{}\">{}</creatorName><nameIdentifier nameIdentifierScheme=\"{}\" schemeURI=\"{}\">{}</nameIdentifier></creator>\n",
+                name_type, uri, scheme, scheme_uri, uri
+            )),
+            None => out.push_str(&format!("    <creator><creatorName>{}</creatorName></creator>\n", uri)),
+        }
+    }
+    if d.creators.is_empty() {
+        out.push_str(&format!("    <creator><creatorName>{}</creatorName></creator>\n", UNKNOWN_CREATOR));
+    }
+    out.push_str("  </creators>\n");
+    out.push_str(&format!(
+        "  <titles>\n    <title>{}</title>\n  </titles>\n",
+        xml::escape(&d.title)
+    ));
+    out.push_str(&format!("  <publisher>{}</publisher>\n", xml::escape(config::metadata_publisher())));
+    out.push_str(&format!("  <publicationYear>{}</publicationYear>\n", xml::escape(&d.publication_year())));
+    out.push_str(&format!(
+        "  <resourceType resourceTypeGeneral=\"{}\">{}</resourceType>\n",
+        resource_type_general(d.resource_type.as_deref()),
+        xml::escape(d.resource_type.as_deref().unwrap_or(""))
+    ));
+    out.push_str("  <dates>\n");
+    if let Some(created) = &d.created {
+        out.push_str(&format!("    <date dateType=\"Created\">{}</date>\n", xml::escape(created)));
+    }
+    if let Some(modified) = &d.modified {
+        out.push_str(&format!("    <date dateType=\"Updated\">{}</date>\n", xml::escape(modified)));
+    }
+    out.push_str("  </dates>\n");
+    if !d.related.is_empty() {
+        out.push_str("  <relatedIdentifiers>\n");
+        for (url, relation) in d.related.iter() {
+            out.push_str(&format!(
+                "    <relatedIdentifier relatedIdentifierType=\"URL\" relationType=\"{}\">{}</relatedIdentifier>\n",
+                relation.as_str(),
+                xml::escape(url)
+            ));
+        }
+        out.push_str("  </relatedIdentifiers>\n");
+    }
+    if let Some(version) = &d.version {
+        out.push_str(&format!("  <version>{}</version>\n", xml::escape(version)));
+    }
+    if let Some(license) = &d.license {
+        out.push_str(&format!(
+            "  <rightsList>\n    <rights rightsURI=\"{}\"/>\n  </rightsList>\n",
+            xml::escape(license)
+        ));
+    }
+    if let Some(description) = &d.description {
+        out.push_str(&format!(
+            "  <descriptions>\n    <description descriptionType=\"Abstract\">{}</description>\n  </descriptions>\n",
+            xml::escape(description)
+        ));
+    }
+    out.push_str("</resource>\n");
+    out
+}
+
+pub fn from_xml(document: &str) -> Result<Description, String> {
+    let all = xml::parse(document)?;
+    if xml::elements(&all, "resource").next().is_none() {
+        return Err("Not a DataCite document: <resource> is missing.".into());
+    }
+    let date_of_type = |date_type: &str| {
+        xml::elements(&all, "date")
+            .find(|d| d.attributes.get("dateType").map(String::as_str) == Some(date_type))
+            .map(|d| d.text.clone())
+    };
+    let related = xml::elements(&all, "relatedIdentifier")
+        .filter_map(|e| {
+            let relation = e.attributes.get("relationType").and_then(|r| Relation::parse(r))?;
+            Some((e.text.clone(), relation))
+        })
+        .collect();
+    let license = xml::elements(&all, "rights").find_map(|r| r.attributes.get("rightsURI").cloned());
+    Ok(Description {
+        identifier: xml::text(&all, "identifier"),
+        title: xml::text(&all, "title").unwrap_or_default(),
+        description: xml::text(&all, "description"),
+        creators: parse_creators(
+            xml::elements(&all, "creatorName")
+                .map(|e| e.text.as_str()),
+        ),
+        resource_type: xml::text(&all, "resourceType").filter(|t| !t.is_empty()),
+        created: date_of_type("Created"),
+        modified: date_of_type("Updated"),
+        version: xml::text(&all, "version"),
+        license,
+        related,
+    })
+}
+
+pub fn to_json(d: &Description) -> String {
+    let mut dates = Vec::new();
+    if let Some(created) = &d.created {
+        dates.push(json::json!({ "date": created, "dateType": "Created" }));
+    }
+    if let Some(modified) = &d.modified {
+        dates.push(json::json!({ "date": modified, "dateType": "Updated" }));
+    }
+    let related: Vec<json::Value> = d
+        .related
+        .iter()
+        .map(|(url, relation)| {
+            json::json!({
+                "relatedIdentifier": url,
+                "relatedIdentifierType": "URL",
+                "relationType": relation.as_str(),
+            })
+        })
+        .collect();
+    let mut creators: Vec<json::Value> = d
+        .creators
+        .iter()
+        .map(|creator| match name_scheme(creator) {
+            Some((name_type, scheme, scheme_uri)) => json::json!({
+                "name": creator.to_uri(),
+                "nameType": name_type,
+                "nameIdentifiers": [{
+                    "nameIdentifier": creator.to_uri(),
+                    "nameIdentifierScheme": scheme,
+                    "schemeUri": scheme_uri,
+                }],
+            }),
+            None => json::json!({ "name": creator.to_uri() }),
+        })
+        .collect();
+    if creators.is_empty() {
+        creators.push(json::json!({ "name": UNKNOWN_CREATOR }));
+    }
+    let mut document = json::json!({
+        "schemaVersion": NAMESPACE,
+        "creators": creators,
+        "titles": [{ "title": d.title }],
+        "publisher": config::metadata_publisher(),
+        "publicationYear": d.publication_year(),
+        "types": {
+            "resourceTypeGeneral": resource_type_general(d.resource_type.as_deref()),
+            "resourceType": d.resource_type.clone().unwrap_or_default(),
+        },
+        "dates": dates,
+        "relatedIdentifiers": related,
+    });
+    if let Some(identifier) = &d.identifier {
+        document["identifiers"] = json::json!([{ "identifier": identifier, "identifierType": "Handle" }]);
+    }
+    if let Some(version) = &d.version {
+        document["version"] = version.as_str().into();
+    }
+    if let Some(license) = &d.license {
+        document["rightsList"] = json::json!([{ "rightsUri": license }]);
+    }
+    if let Some(description) = &d.description {
+        document["descriptions"] =
+            json::json!([{ "description": description, "descriptionType": "Abstract" }]);
+    }
+    json::to_string_pretty(&document).expect("DataCite JSON can always be serialized.")
+}
+
+pub fn from_json(document: &str) -> Result<Description, String> {
+    let value: json::Value = json::from_str(document).map_err(|e| e.to_string())?;
+    // Documents from the DataCite REST API wrap the metadata into data.attributes.
+    let d = value
+        .pointer("/data/attributes")
+        .unwrap_or(&value)
+        .as_object()
+        .ok_or("DataCite JSON must be an object.")?;
+    let string = |v: Option<&json::Value>| v.and_then(|v| v.as_str()).map(String::from);
+    let first_of = |key: &str, field: &str| {
+        d.get(key)
+            .and_then(|list| list.as_array())
+            .and_then(|list| list.iter().find_map(|e| string(e.get(field))))
+    };
+    let date_of_type = |date_type: &str| {
+        d.get("dates")
+            .and_then(|list| list.as_array())
+            .and_then(|list| {
+                list.iter()
+                    .find(|e| e.get("dateType").and_then(|t| t.as_str()) == Some(date_type))
+                    .and_then(|e| string(e.get("date")))
+            })
+    };
+    let related = d
+        .get("relatedIdentifiers")
+        .and_then(|list| list.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|e| {
+                    let relation = e
+                        .get("relationType")
+                        .and_then(|r| r.as_str())
+                        .and_then(Relation::parse)?;
+                    Some((string(e.get("relatedIdentifier"))?, relation))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Description {
+        identifier: first_of("identifiers", "identifier").or_else(|| string(d.get("doi"))),
+        title: first_of("titles", "title").unwrap_or_default(),
+        description: first_of("descriptions", "description"),
+        creators: parse_creators(
+            d.get("creators")
+                .and_then(|list| list.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|c| c.get("name").and_then(|n| n.as_str())),
+        ),
+        resource_type: d
+            .get("types")
+            .and_then(|t| string(t.get("resourceType")))
+            .filter(|t| !t.is_empty()),
+        created: date_of_type("Created"),
+        modified: date_of_type("Updated"),
+        version: string(d.get("version")),
+        license: first_of("rightsList", "rightsUri"),
+        related,
+    })
+}
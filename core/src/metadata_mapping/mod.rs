@@ -0,0 +1,382 @@
+//! Maps records (and their linked data) to common metadata formats and back.
+//!
+//! Supported are DataCite (kernel 4.x) as XML and JSON, and schema.org as JSON-LD.
+//! All formats are generated from and parsed into the same intermediate `Description`,
+//! so the mapping of record attributes is defined only once.
+
+mod datacite;
+mod schema_org;
+mod xml;
+
+use std::{convert::TryFrom, fmt::Display};
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    app_state::data::Data,
+    data_type_registry::*,
+    pidinfo::PidInfo,
+    service_communication::PidRecord,
+};
+
+#[derive(Clone, Copy, Debug, EnumIter, PartialEq, Eq)]
+pub enum MetadataFormat {
+    DataCiteXml,
+    DataCiteJson,
+    SchemaOrg,
+}
+
+/// Associates formats with their Display name (for the user interface).
+impl Display for MetadataFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetadataFormat::DataCiteXml => write!(f, "DataCite 4 (XML)"),
+            MetadataFormat::DataCiteJson => write!(f, "DataCite 4 (JSON)"),
+            MetadataFormat::SchemaOrg => write!(f, "schema.org (JSON-LD)"),
+        }
+    }
+}
+
+impl MetadataFormat {
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            MetadataFormat::DataCiteXml => "application/vnd.datacite.datacite+xml",
+            MetadataFormat::DataCiteJson => "application/vnd.datacite.datacite+json",
+            MetadataFormat::SchemaOrg => "application/ld+json",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            MetadataFormat::DataCiteXml => "datacite.xml",
+            MetadataFormat::DataCiteJson => "datacite.json",
+            MetadataFormat::SchemaOrg => "schema.jsonld",
+        }
+    }
+
+    /// Generates a metadata document for the given object.
+    pub fn generate(&self, info: &PidInfo, data: Option<&Data>) -> String {
+        let description = Description::new(info, data);
+        match self {
+            MetadataFormat::DataCiteXml => datacite::to_xml(&description),
+            MetadataFormat::DataCiteJson => datacite::to_json(&description),
+            MetadataFormat::SchemaOrg => schema_org::to_json_ld(&description),
+        }
+    }
+
+    /// Parses a metadata document into record attributes.
+    pub fn parse(&self, document: &str) -> Result<MappedAttributes, String> {
+        self.describe(document).map(MappedAttributes::from)
+    }
+
+    fn describe(&self, document: &str) -> Result<Description, String> {
+        match self {
+            MetadataFormat::DataCiteXml => datacite::from_xml(document),
+            MetadataFormat::DataCiteJson => datacite::from_json(document),
+            MetadataFormat::SchemaOrg => schema_org::from_json_ld(document),
+        }
+    }
+}
+
+/// How a related resource relates to the described object (DataCite relation types).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    /// Another location of the object itself.
+    IsIdenticalTo,
+    /// Data that is part of the object.
+    HasPart,
+    /// A document describing the object, i.e. an annotation.
+    IsDescribedBy,
+}
+
+impl Relation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Relation::IsIdenticalTo => "IsIdenticalTo",
+            Relation::HasPart => "HasPart",
+            Relation::IsDescribedBy => "IsDescribedBy",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "IsIdenticalTo" => Some(Relation::IsIdenticalTo),
+            "HasPart" => Some(Relation::HasPart),
+            "IsDescribedBy" => Some(Relation::IsDescribedBy),
+            _ => None,
+        }
+    }
+}
+
+/// Contributor roles which are mapped to creators.
+const CREATOR_ROLES: [&str; 2] = ["author", "institute"];
+
+/// The intermediate representation all formats are mapped from and to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Description {
+    pub identifier: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    /// Contributors who created the object, i.e. authors and their institutes.
+    pub creators: Vec<Identifier>,
+    /// Name of the digital object type.
+    pub resource_type: Option<String>,
+    pub created: Option<String>,
+    pub modified: Option<String>,
+    pub version: Option<String>,
+    /// URL of the policy/license.
+    pub license: Option<String>,
+    pub related: Vec<(String, Relation)>,
+}
+
+impl Description {
+    pub fn new(info: &PidInfo, data: Option<&Data>) -> Self {
+        let resource_type = info.digital_object_type.as_ref().ok().map(|t| t.to_string());
+        let mut related: Vec<(String, Relation)> = info
+            .locations
+            .0
+            .iter()
+            .filter(|l| !l.url.is_empty())
+            .map(|l| (l.url.clone(), Relation::IsIdenticalTo))
+            .collect();
+        let creators = info
+            .contributors
+            .0
+            .iter()
+            .filter(|c| CREATOR_ROLES.contains(&c.role.as_str()))
+            .filter_map(|c| c.validate().ok())
+            .collect();
+        let mut description = None;
+        match data {
+            Some(Data::AnnotatedImage(image)) => {
+                if !image.url.is_empty() {
+                    related.push((image.url.clone(), Relation::HasPart));
+                }
+                related.extend(
+                    image
+                        .annotation_urls
+                        .iter()
+                        .filter(|a| !a.is_empty())
+                        .map(|a| (a.clone(), Relation::IsDescribedBy)),
+                );
+            }
+            Some(Data::Collection(collection)) => description = collection.description.clone(),
+            None => {}
+        }
+        Self {
+            identifier: Some(info.pid().clone()).filter(|pid| !pid.is_empty()),
+            title: resource_type
+                .as_ref()
+                .map(|t| format!("{} {}", t, info.pid()))
+                .unwrap_or_else(|| info.pid().clone()),
+            description,
+            creators,
+            resource_type,
+            created: Some(info.date_created.to_rfc3339()),
+            modified: Some(info.date_modified.to_rfc3339()),
+            version: Some(info.version.0.clone()),
+            license: Some(Pid(info.policy.0.clone()).to_url()),
+            related,
+        }
+    }
+
+    /// The year of creation, or of the last modification. DataCite requires a year,
+    /// so documents without any dates get the current one.
+    fn publication_year(&self) -> String {
+        self.created
+            .iter()
+            .chain(self.modified.iter())
+            .find_map(|date| date.get(0..4))
+            .map(String::from)
+            .unwrap_or_else(|| chrono::Utc::now().format("%Y").to_string())
+    }
+}
+
+/// Record attributes that could be recovered from a metadata document.
+/// Attributes that were not found are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MappedAttributes {
+    pub pid: Option<String>,
+    pub digital_object_type: Option<MaybeDOType>,
+    pub locations: Option<Locations>,
+    pub date_created: Option<DateCreated>,
+    pub date_modified: Option<DateModified>,
+    pub version: Option<Version>,
+    pub policy: Option<Policy>,
+}
+
+impl From<Description> for MappedAttributes {
+    fn from(d: Description) -> Self {
+        let digital_object_type = d.resource_type.and_then(|name| {
+            let t = DigitalObjectType::iter().find(|t| t.to_string() == name);
+            if t.is_none() {
+                log::error!("Ignored unknown resource type '{}'.", name);
+            }
+            t.map(Ok)
+        });
+        let locations: Vec<String> = d
+            .related
+            .into_iter()
+            .filter(|(_, relation)| *relation == Relation::IsIdenticalTo)
+            .map(|(url, _)| url)
+            .collect();
+        let parse_date = |date: Option<String>| {
+            date.and_then(|s| {
+                DateTimeHandle::try_from(&s)
+                    .map_err(|e| log::error!("Could not parse date '{}': {}", s, e))
+                    .ok()
+            })
+        };
+        Self {
+            pid: d.identifier,
+            digital_object_type,
+            locations: Some(Locations::from_urls(locations)).filter(|l| !l.0.is_empty()),
+            date_created: parse_date(d.created).map(DateCreated),
+            date_modified: parse_date(d.modified).map(DateModified),
+            version: d.version.map(Version),
+            policy: d.license.map(|url| Policy(Pid::from_url(&url).0)),
+        }
+    }
+}
+
+impl MappedAttributes {
+    /// Overwrites the local state of the object with all recovered attributes.
+    pub fn apply(self, info: &mut PidInfo) {
+        if let Some(t) = self.digital_object_type {
+            info.digital_object_type = t;
+        }
+        if let Some(locations) = self.locations {
+            info.locations = locations;
+        }
+        if let Some(date) = self.date_created {
+            info.date_created = date;
+        }
+        if let Some(date) = self.date_modified {
+            info.date_modified = date;
+        }
+        if let Some(version) = self.version {
+            info.version = version;
+        }
+        if let Some(policy) = self.policy {
+            info.policy = policy;
+        }
+        info.update_state();
+    }
+
+    /// Writes all recovered attributes into the given record.
+    pub fn write(&self, record: &mut PidRecord) {
+        if let Some(pid) = &self.pid {
+            record.pid = pid.clone();
+        }
+        if let Some(t) = &self.digital_object_type {
+            t.write(record);
+        }
+        if let Some(locations) = &self.locations {
+            locations.write(record);
+        }
+        if let Some(date) = &self.date_created {
+            date.write(record);
+        }
+        if let Some(date) = &self.date_modified {
+            date.write(record);
+        }
+        if let Some(version) = &self.version {
+            version.write(record);
+        }
+        if let Some(policy) = &self.policy {
+            policy.write(record);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> PidInfo {
+        let mut info = PidInfo::default();
+        *info.pid_mut() = "21.T11148/example".into();
+        info.locations = Locations::from_urls(vec!["https://example.com/page.tiff".into()]);
+        info.version = Version("2.1.0".into());
+        info.contributors = Contributors(vec![
+            Contributor {
+                identifier: "https://orcid.org/0000-0002-1825-0097".into(),
+                role: "author".into(),
+            },
+            Contributor {
+                identifier: "https://ror.org/04t3en479".into(),
+                role: "institute".into(),
+            },
+            Contributor {
+                identifier: "https://orcid.org/0000-0002-1694-233X".into(),
+                role: "editor".into(),
+            },
+        ]);
+        info
+    }
+
+    #[test]
+    fn every_format_roundtrips() {
+        let info = example();
+        for format in MetadataFormat::iter() {
+            let document = format.generate(&info, None);
+            assert_eq!(
+                format.describe(&document).unwrap().creators,
+                vec![
+                    Identifier::Orcid("0000-0002-1825-0097".into()),
+                    Identifier::Ror("04t3en479".into())
+                ],
+                "{}",
+                format
+            );
+            let mapped = format.parse(&document).unwrap();
+            assert_eq!(mapped.pid.as_deref(), Some("21.T11148/example"), "{}", format);
+            assert_eq!(mapped.locations, Some(info.locations.clone()), "{}", format);
+            assert_eq!(mapped.version, Some(info.version.clone()), "{}", format);
+            assert_eq!(mapped.policy, Some(info.policy.clone()), "{}", format);
+            assert_eq!(
+                mapped.digital_object_type,
+                Some(info.digital_object_type.clone()),
+                "{}",
+                format
+            );
+            assert_eq!(
+                mapped.date_created.map(|d| d.to_rfc3339()),
+                Some(info.date_created.to_rfc3339()),
+                "{}",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn datacite_always_has_a_publication_year() {
+        let undated = Description::default();
+        let this_year = chrono::Utc::now().format("%Y").to_string();
+        assert_eq!(undated.publication_year(), this_year);
+        assert!(datacite::to_xml(&undated).contains(&format!("<publicationYear>{}</publicationYear>", this_year)));
+        let document: serde_json::Value = serde_json::from_str(&datacite::to_json(&undated)).unwrap();
+        assert_eq!(document["publicationYear"], this_year.as_str());
+        assert_eq!(document["publisher"], crate::config::metadata_publisher());
+
+        let modified = Description {
+            modified: Some("2021-03-04T05:06:07Z".into()),
+            ..Description::default()
+        };
+        assert_eq!(modified.publication_year(), "2021");
+        let created = Description {
+            created: Some("2020-01-02T03:04:05Z".into()),
+            ..modified
+        };
+        assert_eq!(created.publication_year(), "2020");
+    }
+
+    #[test]
+    fn unknown_resource_types_are_dropped() {
+        let description = Description {
+            resource_type: Some("Spreadsheet".into()),
+            ..Description::default()
+        };
+        assert_eq!(MappedAttributes::from(description).digital_object_type, None);
+    }
+}
@@ -0,0 +1,137 @@
+//! schema.org metadata as JSON-LD.
+
+use serde_json as json;
+
+use crate::data_type_registry::Identifier;
+
+use super::{Description, Relation};
+
+const CONTEXT: &str = "https://schema.org";
+
+pub fn to_json_ld(d: &Description) -> String {
+    let urls_of = |relation: Relation| -> Vec<&String> {
+        d.related
+            .iter()
+            .filter(|(_, r)| *r == relation)
+            .map(|(url, _)| url)
+            .collect()
+    };
+    let mut document = json::json!({
+        "@context": CONTEXT,
+        "@type": "Dataset",
+        "name": d.title,
+    });
+    if let Some(identifier) = &d.identifier {
+        document["@id"] = crate::data_type_registry::Pid(identifier.clone()).to_url().into();
+        document["identifier"] = identifier.as_str().into();
+    }
+    if let Some(description) = &d.description {
+        document["description"] = description.as_str().into();
+    }
+    if !d.creators.is_empty() {
+        let creators: Vec<json::Value> = d
+            .creators
+            .iter()
+            .map(|creator| match creator {
+                Identifier::Ror(_) => json::json!({ "@type": "Organization", "@id": creator.to_uri() }),
+                _ => json::json!({ "@type": "Person", "@id": creator.to_uri() }),
+            })
+            .collect();
+        document["creator"] = creators.into();
+    }
+    if let Some(resource_type) = &d.resource_type {
+        document["additionalType"] = resource_type.as_str().into();
+    }
+    if let Some(created) = &d.created {
+        document["dateCreated"] = created.as_str().into();
+    }
+    if let Some(modified) = &d.modified {
+        document["dateModified"] = modified.as_str().into();
+    }
+    if let Some(version) = &d.version {
+        document["version"] = version.as_str().into();
+    }
+    if let Some(license) = &d.license {
+        document["license"] = license.as_str().into();
+    }
+    let distribution: Vec<json::Value> = urls_of(Relation::IsIdenticalTo)
+        .into_iter()
+        .map(|url| json::json!({ "@type": "DataDownload", "contentUrl": url }))
+        .collect();
+    if !distribution.is_empty() {
+        document["distribution"] = distribution.into();
+    }
+    let parts: Vec<json::Value> = urls_of(Relation::HasPart)
+        .into_iter()
+        .map(|url| json::json!({ "@id": url }))
+        .collect();
+    if !parts.is_empty() {
+        document["hasPart"] = parts.into();
+    }
+    let descriptions: Vec<json::Value> = urls_of(Relation::IsDescribedBy)
+        .into_iter()
+        .map(|url| json::json!({ "@id": url }))
+        .collect();
+    if !descriptions.is_empty() {
+        document["subjectOf"] = descriptions.into();
+    }
+    json::to_string_pretty(&document).expect("JSON-LD can always be serialized.")
+}
+
+pub fn from_json_ld(document: &str) -> Result<Description, String> {
+    let value: json::Value = json::from_str(document).map_err(|e| e.to_string())?;
+    let d = value
+        .as_object()
+        .ok_or("schema.org JSON-LD must be an object.")?;
+    let string = |key: &str| d.get(key).and_then(|v| v.as_str()).map(String::from);
+    // values may be a single object or a list of objects
+    let list = |key: &str| -> Vec<json::Value> {
+        match d.get(key) {
+            Some(json::Value::Array(list)) => list.clone(),
+            Some(single) => vec![single.clone()],
+            None => Vec::new(),
+        }
+    };
+    let mut related: Vec<(String, Relation)> = list("distribution")
+        .iter()
+        .filter_map(|e| e.get("contentUrl").and_then(|u| u.as_str()))
+        .map(|url| (url.to_owned(), Relation::IsIdenticalTo))
+        .collect();
+    for (key, relation) in [("hasPart", Relation::HasPart), ("subjectOf", Relation::IsDescribedBy)].iter() {
+        related.extend(
+            list(key)
+                .iter()
+                .filter_map(|e| e.get("@id").and_then(|u| u.as_str()))
+                .map(|url| (url.to_owned(), *relation)),
+        );
+    }
+    let license = match d.get("license") {
+        Some(json::Value::Object(license)) => license
+            .get("@id")
+            .and_then(|l| l.as_str())
+            .map(String::from),
+        _ => string("license"),
+    };
+    Ok(Description {
+        identifier: string("identifier").or_else(|| {
+            string("@id").map(|id| crate::data_type_registry::Pid::from_url(&id).0)
+        }),
+        title: string("name").unwrap_or_default(),
+        description: string("description"),
+        creators: list("creator")
+            .iter()
+            .filter_map(|c| c.get("@id").and_then(|id| id.as_str()))
+            .filter_map(|id| Identifier::parse(id).ok())
+            .collect(),
+        resource_type: string("additionalType"),
+        created: string("dateCreated"),
+        modified: string("dateModified"),
+        version: d.get("version").and_then(|v| match v {
+            json::Value::String(s) => Some(s.clone()),
+            json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }),
+        license,
+        related,
+    })
+}
@@ -0,0 +1,100 @@
+//! Writing and reading flat metadata documents like DataCite, with quick-xml.
+
+use std::collections::HashMap;
+
+use quick_xml::{events::Event, Reader};
+
+pub fn escape(s: &str) -> String {
+    quick_xml::escape::escape(s).into_owned()
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Element {
+    /// The local name, without namespace prefix.
+    pub name: String,
+    pub attributes: HashMap<String, String>,
+    /// The text of the element and its children, unescaped and trimmed.
+    pub text: String,
+}
+
+/// All elements of the document, in document order, regardless of their nesting depth.
+pub fn parse(document: &str) -> Result<Vec<Element>, String> {
+    let mut reader = Reader::from_str(document);
+    let mut elements: Vec<Element> = Vec::new();
+    // indices of the elements which are not closed yet
+    let mut open: Vec<usize> = Vec::new();
+    let invalid = |reader: &Reader<&[u8]>, e: &dyn std::fmt::Display| {
+        format!("Invalid XML at position {}: {}", reader.buffer_position(), e)
+    };
+    loop {
+        let event = reader.read_event().map_err(|e| invalid(&reader, &e))?;
+        let text = match event {
+            Event::Start(ref tag) | Event::Empty(ref tag) => {
+                let mut attributes = HashMap::new();
+                for attribute in tag.attributes() {
+                    let attribute = attribute.map_err(|e| invalid(&reader, &e))?;
+                    let value = attribute.unescape_value().map_err(|e| invalid(&reader, &e))?;
+                    let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+                    attributes.insert(key, value.into_owned());
+                }
+                elements.push(Element {
+                    name: String::from_utf8_lossy(tag.local_name().as_ref()).into_owned(),
+                    attributes,
+                    text: String::new(),
+                });
+                if let Event::Start(_) = event {
+                    open.push(elements.len() - 1);
+                }
+                continue;
+            }
+            Event::End(_) => {
+                open.pop();
+                continue;
+            }
+            Event::Text(text) => text.unescape().map_err(|e| invalid(&reader, &e))?.into_owned(),
+            Event::CData(data) => String::from_utf8_lossy(&data.into_inner()).into_owned(),
+            Event::Eof => break,
+            // comments, processing instructions and the declaration
+            _ => continue,
+        };
+        for index in open.iter() {
+            elements[*index].text.push_str(&text);
+        }
+    }
+    for element in elements.iter_mut() {
+        element.text = element.text.trim().to_owned();
+    }
+    Ok(elements)
+}
+
+/// The elements with the given (local) name.
+pub fn elements<'a>(all: &'a [Element], name: &'a str) -> impl Iterator<Item = &'a Element> {
+    all.iter().filter(move |e| e.name == name)
+}
+
+/// The text of the first element with the given name.
+pub fn text(all: &[Element], name: &str) -> Option<String> {
+    elements(all, name).next().map(|e| e.text.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_comments_cdata_and_character_references() {
+        let document = r#"<resource><!-- <title>commented</title> -->
+            <title xml:lang="en">A &#38; B &#x263A;</title>
+            <description><![CDATA[1 < 2 & <b>bold</b>]]></description>
+            <rights rightsURI="https://example.org/?a=1&amp;b=2"/>
+        </resource>"#;
+        let all = parse(document).unwrap();
+        assert_eq!(elements(&all, "title").count(), 1);
+        assert_eq!(text(&all, "title").as_deref(), Some("A & B \u{263A}"));
+        assert_eq!(elements(&all, "title").next().unwrap().attributes.get("lang").map(String::as_str), Some("en"));
+        assert_eq!(text(&all, "description").as_deref(), Some("1 < 2 & <b>bold</b>"));
+        let rights = elements(&all, "rights").next().unwrap();
+        assert_eq!(rights.attributes.get("rightsURI").map(String::as_str), Some("https://example.org/?a=1&b=2"));
+        assert!(parse("<resource><title></resource>").is_err());
+    }
+}
@@ -1,8 +1,7 @@
 use std::convert::TryFrom;
 
 use crate::{app_state::data::DataID, data_type_registry::*, service_communication::pit_record::PidRecord};
-use serde_json as json;
-use yew::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PidInfo {
@@ -22,11 +21,21 @@ pub struct PidInfo {
     pub etag: Etag,
     pub policy: Policy,
     pub version: Version,
+    pub metadata_object: MetadataObject,
+    pub contributors: Contributors,
+    pub status: ObjectStatus,
+    pub tombstone: Tombstone,
+    pub superseded_by: SupersededBy,
+    pub is_new_version_of: IsNewVersionOf,
+    pub has_new_version: HasNewVersion,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum State {
     Unregistered,
+    /// The registration was sent, but it is unknown whether the service registered the
+    /// object, i.e. after a timeout. Check at the service before publishing it again.
+    RegistrationUnknown,
     Modified,
     Clean,
 }
@@ -44,6 +53,26 @@ impl PidInfo {
         Self::from(record, State::Modified)
     }
 
+    /// Restores an object from its published record and its local state,
+    /// i.e. after importing it from a workspace file.
+    pub fn from_parts(record: PidRecord, local: &PidRecord, state: State) -> Self {
+        let mut info = Self::from(local.clone(), state);
+        info.record = record;
+        info
+    }
+
+    /// The object after it was published: the published record, plus the local
+    /// attributes of draft types, which were not published.
+    pub fn from_published(record: PidRecord, local: &PidInfo) -> Self {
+        let mut merged = record.clone();
+        for (key, entries) in local.as_record().entries {
+            if is_draft_type(&key) {
+                merged.entries.entry(key).or_insert(entries);
+            }
+        }
+        Self::from_parts(record, &merged, State::Clean)
+    }
+
     fn from(record: PidRecord, state: State) -> Self {
         let profile: MaybeProfile = Profile::try_from(&record);
         let digital_object_type = DigitalObjectType::try_from(&record);
@@ -53,6 +82,13 @@ impl PidInfo {
         let etag = Etag::from(&record);
         let policy = Policy::from(&record);
         let version = Version::from(&record);
+        let metadata_object = MetadataObject::from(&record);
+        let contributors = Contributors::from(&record);
+        let status = ObjectStatus::from(&record);
+        let tombstone = Tombstone::from(&record);
+        let superseded_by = SupersededBy::from(&record);
+        let is_new_version_of = IsNewVersionOf::from(&record);
+        let has_new_version = HasNewVersion::from(&record);
         Self {
             record,
             state,
@@ -66,12 +102,21 @@ impl PidInfo {
             etag,
             policy,
             version,
+            metadata_object,
+            contributors,
+            status,
+            tombstone,
+            superseded_by,
+            is_new_version_of,
+            has_new_version,
         }
     }
 
     pub fn as_record(&self) -> PidRecord {
-        let mut record_state = PidRecord::default();
-        record_state.pid = self.record.pid.clone();
+        let mut record_state = PidRecord {
+            pid: self.record.pid.clone(),
+            ..Default::default()
+        };
 
         self.profile.write(&mut record_state);
         self.digital_object_type.write(&mut record_state);
@@ -81,16 +126,25 @@ impl PidInfo {
         self.etag.write(&mut record_state);
         self.policy.write(&mut record_state);
         self.version.write(&mut record_state);
+        self.metadata_object.write(&mut record_state);
+        self.contributors.write(&mut record_state);
+        self.status.write(&mut record_state);
+        self.tombstone.write(&mut record_state);
+        self.superseded_by.write(&mut record_state);
+        self.is_new_version_of.write(&mut record_state);
+        self.has_new_version.write(&mut record_state);
         record_state
     }
 
     pub fn update_state(&mut self) {
         let old_record = &self.record;
-        let new_record = &self.as_record();
+        // unpublished attributes are no changes to publish.
+        let new_record = &self.as_record().publishable();
         let changed = !old_record.same_content_like(new_record);
         if changed {
             match self.state {
                 State::Unregistered => {}
+                State::RegistrationUnknown => {}
                 State::Modified => {}
                 State::Clean => self.state = State::Modified,
             }
@@ -113,25 +167,20 @@ impl PidInfo {
         &mut self.record.pid
     }
 
+    /// The status of the published record, which may differ from the local `status`.
+    pub fn published_status(&self) -> ObjectStatus {
+        ObjectStatus::from(&self.record)
+    }
+
+    /// Whether the local `status` differs from the published one. This is the case after
+    /// changing it, and as long as the status is kept local as a draft type.
+    pub fn status_unpublished(&self) -> bool {
+        self.status != self.published_status()
+    }
+
+    /// Describes the local state, which may not be published yet.
     pub fn describe(&self) -> String {
-        self.record.describe()
-    }
-
-    pub fn view_record(&self) -> Html {
-        self.record
-            .entries
-            .keys()
-            .map(|property_pid| self.record.entries.get(property_pid).unwrap())
-            .flatten()
-            .map(|entry| {
-                html! {
-                    <>
-                        <p class="align-right">{ &entry.name } <br/> { &entry.key }</p>
-                        <p>{ json::to_string_pretty(&entry.value).unwrap() }</p>
-                    </>
-                }
-            })
-            .collect()
+        self.as_record().describe()
     }
 }
 
@@ -150,6 +199,13 @@ impl Default for PidInfo {
             etag: Etag::default(),
             policy: Policy::default(),
             version: Version::default(),
+            metadata_object: MetadataObject::default(),
+            contributors: Contributors::default(),
+            status: ObjectStatus::default(),
+            tombstone: Tombstone::default(),
+            superseded_by: SupersededBy::default(),
+            is_new_version_of: IsNewVersionOf::default(),
+            has_new_version: HasNewVersion::default(),
         }
     }
 }
@@ -0,0 +1,107 @@
+//! A force directed layout (Fruchterman and Reingold).
+//! It starts from a circle, so the result is the same for the same graph.
+
+use super::Graph;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Positions of all nodes (in the order of `graph.nodes`) within the given frame,
+/// keeping `margin` to the border.
+pub fn layout(graph: &Graph, width: f64, height: f64, margin: f64) -> Vec<Position> {
+    const ITERATIONS: usize = 200;
+    let n = graph.nodes.len();
+    let center = Position { x: width / 2.0, y: height / 2.0 };
+    if n == 0 {
+        return Vec::new();
+    }
+    if n == 1 {
+        return vec![center];
+    }
+    let inner_width = (width - 2.0 * margin).max(1.0);
+    let inner_height = (height - 2.0 * margin).max(1.0);
+    let radius = inner_width.min(inner_height) / 2.0;
+    let mut positions: Vec<Position> = (0..n)
+        .map(|i| {
+            let angle = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+            Position {
+                x: center.x + radius * angle.cos(),
+                y: center.y + radius * angle.sin(),
+            }
+        })
+        .collect();
+
+    // ideal distance between nodes
+    let k = (inner_width * inner_height / n as f64).sqrt();
+    let mut temperature = radius / 4.0;
+    let cooling = temperature / ITERATIONS as f64;
+    for _ in 0..ITERATIONS {
+        let mut displacement = vec![(0.0, 0.0); n];
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let (dx, dy, distance) = delta(positions[i], positions[j]);
+                let force = k * k / distance;
+                displacement[i].0 += dx / distance * force;
+                displacement[i].1 += dy / distance * force;
+                displacement[j].0 -= dx / distance * force;
+                displacement[j].1 -= dy / distance * force;
+            }
+        }
+        for edge in graph.edges.iter().filter(|e| e.from != e.to) {
+            let (dx, dy, distance) = delta(positions[edge.from], positions[edge.to]);
+            let force = distance * distance / k;
+            displacement[edge.from].0 -= dx / distance * force;
+            displacement[edge.from].1 -= dy / distance * force;
+            displacement[edge.to].0 += dx / distance * force;
+            displacement[edge.to].1 += dy / distance * force;
+        }
+        for (position, (dx, dy)) in positions.iter_mut().zip(displacement) {
+            let length = (dx * dx + dy * dy).sqrt().max(0.01);
+            let step = length.min(temperature);
+            position.x = (position.x + dx / length * step).max(margin).min(width - margin);
+            position.y = (position.y + dy / length * step).max(margin).min(height - margin);
+        }
+        temperature -= cooling;
+    }
+    positions
+}
+
+/// Vector from b to a and its length, which is never zero.
+fn delta(a: Position, b: Position) -> (f64, f64, f64) {
+    let (dx, dy) = (a.x - b.x, a.y - b.y);
+    let distance = (dx * dx + dy * dy).sqrt().max(0.01);
+    (dx, dy, distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data_type_registry::Pid, relation_graph::*};
+
+    #[test]
+    fn nodes_stay_in_frame_and_apart() {
+        let nodes: Vec<Node> = (0..6)
+            .map(|i| Node {
+                pid: Pid(format!("21.T11148/{}", i)),
+                label: i.to_string(),
+                origin: Origin::Unresolved,
+            })
+            .collect();
+        let edges = (1..6)
+            .map(|i| Edge { from: 0, to: i, name: "link".into() })
+            .collect();
+        let graph = Graph { nodes, edges };
+        let positions = layout(&graph, 400.0, 300.0, 20.0);
+        assert_eq!(positions, layout(&graph, 400.0, 300.0, 20.0));
+        assert_eq!(positions.len(), 6);
+        for (i, p) in positions.iter().enumerate() {
+            assert!(p.x >= 20.0 && p.x <= 380.0 && p.y >= 20.0 && p.y <= 280.0);
+            for q in positions.iter().skip(i + 1) {
+                assert!(delta(*p, *q).2 > 10.0);
+            }
+        }
+    }
+}
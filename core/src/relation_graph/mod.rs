@@ -0,0 +1,258 @@
+//! Objects reference each other through attribute values which are PIDs
+//! (profile, type, policy, metadata object, version links, ...), and through
+//! the members of their collections.
+//! This module extracts these references into a graph and lays it out for drawing.
+
+mod layout;
+
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
+
+use serde_json as json;
+
+pub use layout::{layout, Position};
+
+use crate::{
+    collection_service::member_item::Member,
+    data_type_registry::{DigitalObjectType, Pid, Profile},
+    service_communication::{MetadataObjectReference, PidRecord},
+};
+
+/// Where the information about a node comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Origin {
+    /// An object in the workspace.
+    Workspace,
+    /// A record resolved from the PIT service.
+    Resolved,
+    /// Only known as reference. Can be resolved on request.
+    Unresolved,
+    Resolving,
+    /// Not resolvable by the PIT service, i.e. a type in the data type registry.
+    Failed,
+}
+
+impl Origin {
+    /// Style class to mark nodes with this origin in the user interface.
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Origin::Workspace => "node-workspace",
+            Origin::Resolved => "node-resolved",
+            Origin::Unresolved => "node-unresolved",
+            Origin::Resolving => "node-resolving",
+            Origin::Failed => "node-failed",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    pub pid: Pid,
+    pub label: String,
+    pub origin: Origin,
+}
+
+/// A reference from the record of `from` to `to`, named after the attribute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl Graph {
+    /// Builds the graph from all records with known content.
+    /// `members` are the members of the collections of objects, by the PID of the object.
+    /// `origin_of` tells the origin of referenced PIDs without a record.
+    pub fn build<'a>(
+        records: impl IntoIterator<Item = (Pid, &'a PidRecord, Origin)>,
+        members: &HashMap<Pid, Vec<Member>>,
+        origin_of: impl Fn(&Pid) -> Origin,
+    ) -> Self {
+        let records: Vec<(Pid, &PidRecord, Origin)> = records.into_iter().collect();
+        let known: HashSet<&Pid> = records.iter().map(|(pid, _, _)| pid).collect();
+        let mut graph = Graph::default();
+        let mut index: HashMap<Pid, usize> = HashMap::new();
+        for (pid, record, origin) in records.iter() {
+            index.insert(pid.clone(), graph.nodes.len());
+            graph.nodes.push(Node {
+                pid: pid.clone(),
+                label: label(pid, Some(*record)),
+                origin: *origin,
+            });
+        }
+        for (pid, record, _) in records.iter() {
+            let members = members.get(pid).map(Vec::as_slice).unwrap_or_default();
+            for (name, target) in links(record, &known).into_iter().chain(member_links(members, &known)) {
+                let to = *index.entry(target.clone()).or_insert_with(|| {
+                    graph.nodes.push(Node {
+                        label: label(&target, None),
+                        origin: origin_of(&target),
+                        pid: target.clone(),
+                    });
+                    graph.nodes.len() - 1
+                });
+                let from = index[pid];
+                graph.edges.push(Edge { from, to, name });
+            }
+        }
+        graph
+    }
+
+    pub fn index_of(&self, pid: &Pid) -> Option<usize> {
+        self.nodes.iter().position(|node| node.pid == *pid)
+    }
+}
+
+/// All references to other objects in the record as (attribute name, PID).
+/// `known` PIDs are recognized even if they do not look like handles,
+/// i.e. unregistered objects in the workspace.
+pub fn links(record: &PidRecord, known: &HashSet<&Pid>) -> Vec<(String, Pid)> {
+    let mut links: Vec<(String, Pid)> = record
+        .entries
+        .values()
+        .flatten()
+        .filter_map(|entry| {
+            let target = match &entry.value {
+                json::Value::String(s) => as_pid(s, known).or_else(|| embedded_pid(s, known)),
+                other => embedded_reference(other, known),
+            }?;
+            Some((entry.name.clone(), target))
+        })
+        .filter(|(_, target)| target.0 != record.pid)
+        .collect();
+    // entries are stored in a map, so the order is not stable otherwise.
+    links.sort_by(|a, b| (&a.0, &a.1 .0).cmp(&(&b.0, &b.1 .0)));
+    links.dedup();
+    links
+}
+
+/// Members of a collection which are objects, as ("member", PID).
+/// Other members, i.e. plain files, are not part of the graph.
+pub fn member_links(members: &[Member], known: &HashSet<&Pid>) -> Vec<(String, Pid)> {
+    members
+        .iter()
+        .filter_map(|member| as_pid(member.location(), known))
+        .map(|pid| ("member".to_string(), pid))
+        .collect()
+}
+
+/// Recognizes handles (prefix/suffix), handle proxy URLs and known PIDs.
+fn as_pid(value: &str, known: &HashSet<&Pid>) -> Option<Pid> {
+    let pid = Pid::from_url(value.trim());
+    if known.contains(&pid) {
+        return Some(pid);
+    }
+    Pid::parse_handle(value)
+}
+
+/// Some attributes contain JSON objects as strings, i.e. the metadata object reference.
+fn embedded_pid(value: &str, known: &HashSet<&Pid>) -> Option<Pid> {
+    json::from_str::<json::Value>(value)
+        .ok()
+        .and_then(|v| embedded_reference(&v, known))
+}
+
+fn embedded_reference(value: &json::Value, known: &HashSet<&Pid>) -> Option<Pid> {
+    let reference = MetadataObjectReference::from_json(value)?;
+    as_pid(reference.resource.as_str(), known)
+}
+
+/// Names known profiles and types, and otherwise shows the PID.
+fn label(pid: &Pid, record: Option<&PidRecord>) -> String {
+    if let Ok(profile) = Profile::try_from(pid) {
+        return profile.to_string();
+    }
+    if let Ok(t) = DigitalObjectType::try_from(pid) {
+        return t.to_string();
+    }
+    let type_name = record
+        .and_then(|r| DigitalObjectType::try_from(r).ok())
+        .map(|t| format!("{} ", t))
+        .unwrap_or_default();
+    let short = pid.rsplit('/').next().unwrap_or(pid.as_str());
+    let short: String = if short.chars().count() > 12 {
+        short.chars().take(11).chain(std::iter::once('…')).collect()
+    } else {
+        short.into()
+    };
+    format!("{}{}", type_name, short)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_pid_valued_attributes() {
+        let mut record = PidRecord {
+            pid: "21.T11148/self".into(),
+            ..Default::default()
+        };
+        record.add_attribute("21.T11148/a".into(), "policy".into(), "21.T11148/policy".into());
+        record.add_attribute("21.T11148/b".into(), "location".into(), "https://example.com/x".into());
+        record.add_attribute("21.T11148/c".into(), "successor".into(), "https://hdl.handle.net/21.T11148/next".into());
+        record.add_attribute("21.T11148/d".into(), "draft".into(), "unregistered-7".into());
+        record.add_attribute("21.T11148/e".into(), "own".into(), "21.T11148/self".into());
+        record.add_attribute("21.T11148/f".into(), "text".into(), "1.0 / 2.0".into());
+
+        let draft = Pid("unregistered-7".into());
+        let mut known = HashSet::new();
+        known.insert(&draft);
+        let targets: Vec<String> = links(&record, &known).into_iter().map(|(_, pid)| pid.0).collect();
+        assert_eq!(targets, vec!["unregistered-7", "21.T11148/policy", "21.T11148/next"]);
+    }
+
+    #[test]
+    fn builds_nodes_for_referenced_pids() {
+        let mut a = PidRecord {
+            pid: "21.T11148/a".into(),
+            ..Default::default()
+        };
+        a.add_attribute("21.T11148/x".into(), "link".into(), "21.T11148/b".into());
+        let mut b = PidRecord {
+            pid: "21.T11148/b".into(),
+            ..Default::default()
+        };
+        b.add_attribute("21.T11148/x".into(), "link".into(), "21.T11148/c".into());
+        let graph = Graph::build(
+            vec![
+                (Pid(a.pid.clone()), &a, Origin::Workspace),
+                (Pid(b.pid.clone()), &b, Origin::Resolved),
+            ],
+            &HashMap::new(),
+            |_| Origin::Unresolved,
+        );
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 2);
+        let c = graph.index_of(&Pid("21.T11148/c".into())).unwrap();
+        assert_eq!(graph.nodes[c].origin, Origin::Unresolved);
+    }
+
+    #[test]
+    fn collection_members_are_edges() {
+        let a = PidRecord {
+            pid: "21.T11148/a".into(),
+            ..Default::default()
+        };
+        let members: Vec<Member> = json::from_value(json::json!([
+            { "id": "1", "location": "https://hdl.handle.net/21.T11148/m" },
+            { "id": "2", "location": "https://example.org/file.tiff" },
+        ]))
+        .unwrap();
+        let mut by_object = HashMap::new();
+        by_object.insert(Pid(a.pid.clone()), members);
+        let graph = Graph::build(vec![(Pid(a.pid.clone()), &a, Origin::Workspace)], &by_object, |_| Origin::Unresolved);
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges, vec![Edge { from: 0, to: 1, name: "member".into() }]);
+        assert_eq!(graph.nodes[1].pid, Pid("21.T11148/m".into()));
+    }
+}
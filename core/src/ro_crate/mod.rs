@@ -0,0 +1,296 @@
+//! Exports an FDO and its data as an RO-Crate (version 1.1).
+//!
+//! The kernel information profile attributes are mapped to schema.org properties
+//! of the root data entity. Locations and linked data become data entities,
+//! the members of a collection are parts of the collection entity.
+
+mod zip;
+
+pub use zip::ZipWriter;
+
+use serde_json as json;
+
+use crate::{
+    app_state::data::{AnnotatedImage, Data},
+    collection_service::{collection::Collection, member_item::Member},
+    data_type_registry::Pid,
+    pidinfo::{PidInfo, State},
+};
+
+pub const METADATA_FILE_NAME: &str = "ro-crate-metadata.json";
+pub const PREVIEW_FILE_NAME: &str = "ro-crate-preview.html";
+
+const CONTEXT: &str = "https://w3id.org/ro/crate/1.1/context";
+const SPECIFICATION: &str = "https://w3id.org/ro/crate/1.1";
+
+pub struct RoCrate {
+    root: json::Map<String, json::Value>,
+    entities: Vec<json::Value>,
+}
+
+impl RoCrate {
+    /// `members` are the members of the collection in `data`, if it is one.
+    pub fn new(info: &PidInfo, data: Option<&Data>, members: &[Member]) -> Self {
+        let mut crate_ = Self {
+            root: json::Map::new(),
+            entities: Vec::new(),
+        };
+        crate_.describe_root(info);
+        for location in info.locations.0.iter().filter(|l| !l.url.is_empty()) {
+            let url = &location.url;
+            // a checked content type is more reliable than the file extension.
+            let encoding_format = location
+                .check
+                .as_ref()
+                .and_then(|c| c.content_type.clone())
+                .unwrap_or_else(|| guess_encoding_format(url).to_owned());
+            crate_.add_part(json::json!({
+                "@id": url,
+                "@type": "File",
+                "name": url.rsplit('/').next().unwrap_or(url),
+                "encodingFormat": encoding_format,
+            }));
+        }
+        match data {
+            Some(Data::AnnotatedImage(image)) => crate_.add_annotated_image(image),
+            Some(Data::Collection(collection)) => crate_.add_collection(collection, members),
+            None => {}
+        }
+        crate_
+    }
+
+    fn describe_root(&mut self, info: &PidInfo) {
+        let root = &mut self.root;
+        root.insert("@id".into(), "./".into());
+        root.insert("@type".into(), "Dataset".into());
+        if !info.pid().is_empty() {
+            root.insert("identifier".into(), Pid(info.pid().clone()).to_url().into());
+        }
+        let name = info
+            .digital_object_type
+            .as_ref()
+            .map(|t| format!("{} {}", t, info.pid()))
+            .unwrap_or_else(|_| info.pid().clone());
+        root.insert("name".into(), name.into());
+        root.insert("description".into(), info.describe().into());
+        root.insert("dateCreated".into(), info.date_created.to_rfc3339().into());
+        // drafts are not published yet.
+        if matches!(info.state(), State::Modified | State::Clean) {
+            root.insert("datePublished".into(), info.date_created.to_rfc3339().into());
+        }
+        root.insert("dateModified".into(), info.date_modified.to_rfc3339().into());
+        root.insert("version".into(), info.version.0.clone().into());
+        // the policy object is no license, but describes how the object may be used.
+        root.insert(
+            "usageInfo".into(),
+            json::json!({ "@id": Pid(info.policy.0.clone()).to_url() }),
+        );
+        let profile = match &info.profile {
+            Ok(profile) => Some(Pid::from(profile)),
+            Err(maybe_pid) => maybe_pid.clone(),
+        };
+        if let Some(profile) = profile {
+            root.insert("conformsTo".into(), json::json!({ "@id": profile.to_url() }));
+        }
+        let object_type = match &info.digital_object_type {
+            Ok(t) => Some(Pid::from(t)),
+            Err(maybe_pid) => maybe_pid.clone(),
+        };
+        if let Some(object_type) = object_type {
+            root.insert("additionalType".into(), object_type.to_url().into());
+        }
+        root.insert("hasPart".into(), json::Value::Array(Vec::new()));
+    }
+
+    fn add_part(&mut self, entity: json::Value) {
+        let id = entity["@id"].clone();
+        if let Some(json::Value::Array(parts)) = self.root.get_mut("hasPart") {
+            parts.push(json::json!({ "@id": id }));
+        }
+        self.entities.push(entity);
+    }
+
+    fn add_annotated_image(&mut self, image: &AnnotatedImage) {
+        if !image.url.is_empty() {
+            self.add_part(json::json!({
+                "@id": image.url,
+                "@type": ["File", "ImageObject"],
+                "encodingFormat": guess_encoding_format(&image.url),
+            }));
+        }
+        for annotation in image.annotation_urls.iter().filter(|a| !a.is_empty()) {
+            self.add_part(json::json!({
+                "@id": annotation,
+                "@type": ["File", "CreativeWork"],
+                "additionalType": "https://www.w3.org/ns/oa#Annotation",
+                "about": { "@id": image.url },
+            }));
+        }
+    }
+
+    fn add_collection(&mut self, collection: &Collection, members: &[Member]) {
+        let id = collection
+            .get_id()
+            .map(|id| format!("#collection-{}", id))
+            .unwrap_or_else(|| "#collection".into());
+        let mut entity = json::json!({
+            "@id": id,
+            "@type": "Dataset",
+            "additionalType": "https://rd-alliance.org/groups/research-data-collections-wg",
+        });
+        if let Some(collection_id) = collection.get_id() {
+            entity["identifier"] = collection_id.into();
+        }
+        if let Some(description) = &collection.description {
+            entity["description"] = description.as_str().into();
+        }
+        let mut parts = Vec::new();
+        for member in members {
+            // members without location are only known to the collection service.
+            let member_id = match member.location() {
+                "" => format!("{}-member-{}", id, member.get_id()),
+                location => location.to_owned(),
+            };
+            let mut part = json::json!({
+                "@id": member_id,
+                "@type": "CreativeWork",
+                "identifier": member.get_id(),
+            });
+            if !member.datatype().is_empty() {
+                part["additionalType"] = member.datatype().into();
+            }
+            if !member.description().is_empty() {
+                part["description"] = member.description().into();
+            }
+            parts.push(json::json!({ "@id": member_id }));
+            self.entities.push(part);
+        }
+        entity["hasPart"] = json::Value::Array(parts);
+        self.add_part(entity);
+    }
+
+    /// The content of the `ro-crate-metadata.json` file.
+    pub fn metadata(&self) -> json::Value {
+        let descriptor = json::json!({
+            "@id": METADATA_FILE_NAME,
+            "@type": "CreativeWork",
+            "conformsTo": { "@id": SPECIFICATION },
+            "about": { "@id": "./" },
+        });
+        let mut graph = vec![descriptor, json::Value::Object(self.root.clone())];
+        graph.extend(self.entities.iter().cloned());
+        json::json!({
+            "@context": CONTEXT,
+            "@graph": graph,
+        })
+    }
+
+    pub fn metadata_string(&self) -> String {
+        json::to_string_pretty(&self.metadata()).expect("RO-Crate metadata can always be serialized.")
+    }
+
+    /// A human readable preview of the crate.
+    pub fn preview(&self) -> String {
+        let rows: String = self
+            .root
+            .iter()
+            .filter(|(key, _)| !key.starts_with('@'))
+            .map(|(key, value)| {
+                let value = match value {
+                    json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                format!("<tr><th>{}</th><td>{}</td></tr>", escape_html(key), escape_html(&value))
+            })
+            .collect();
+        format!(
+            "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>RO-Crate preview</title></head>\n<body><h1>{}</h1><table>{}</table></body></html>\n",
+            escape_html(self.root.get("name").and_then(|n| n.as_str()).unwrap_or("RO-Crate")),
+            rows
+        )
+    }
+
+    /// Bundles metadata and preview into a zip file.
+    pub fn to_zip(&self) -> Vec<u8> {
+        let mut zip = ZipWriter::default();
+        zip.add_file(METADATA_FILE_NAME, self.metadata_string().into_bytes())
+            .add_file(PREVIEW_FILE_NAME, self.preview().into_bytes());
+        zip.finish()
+    }
+}
+
+fn guess_encoding_format(url: &str) -> &'static str {
+    let extension = url
+        .rsplit('/')
+        .next()
+        .and_then(|file| file.rsplit_once('.'))
+        .map(|(_, extension)| extension.to_lowercase());
+    match extension.as_deref() {
+        Some("tif") | Some("tiff") => "image/tiff",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("json") => "application/json",
+        Some("jsonld") => "application/ld+json",
+        Some("xml") => "application/xml",
+        Some("pdf") => "application/pdf",
+        Some("txt") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_entity_is_described() {
+        let mut info = PidInfo::default();
+        *info.pid_mut() = "21.T11148/abc".into();
+        let metadata = RoCrate::new(&info, None, &[]).metadata();
+        let graph = metadata["@graph"].as_array().unwrap();
+        assert_eq!(graph[0]["about"]["@id"], "./");
+        assert_eq!(graph[1]["identifier"], "https://hdl.handle.net/21.T11148/abc");
+        assert_eq!(graph[1]["version"], "1.0.0");
+        assert!(graph[1].get("license").is_none());
+        assert!(graph[1]["usageInfo"]["@id"].is_string());
+        // the default object is an unregistered draft.
+        assert!(graph[1].get("datePublished").is_none());
+        let registered = PidInfo::from_registered(info.as_record());
+        let metadata = RoCrate::new(&registered, None, &[]).metadata();
+        assert!(metadata["@graph"][1]["datePublished"].is_string());
+        // every location is a part of the crate
+        assert_eq!(
+            graph[1]["hasPart"].as_array().unwrap().len(),
+            info.locations.0.iter().filter(|l| !l.url.is_empty()).count()
+        );
+    }
+
+    #[test]
+    fn collection_members_are_parts_of_the_collection() {
+        let collection: Collection = json::from_value(json::json!({ "id": "c1" })).unwrap();
+        let members: Vec<Member> = json::from_value(json::json!([
+            { "id": "m1", "location": "https://example.org/m1.tiff", "datatype": "image/tiff" },
+            { "id": "m2" },
+        ]))
+        .unwrap();
+        let data = Data::Collection(Box::new(collection));
+        let metadata = RoCrate::new(&PidInfo::default(), Some(&data), &members).metadata();
+        let graph = metadata["@graph"].as_array().unwrap();
+        let entity = |id: &str| graph.iter().find(|e| e["@id"] == id).cloned().unwrap();
+
+        let collection = entity("#collection-c1");
+        assert_eq!(
+            collection["hasPart"],
+            json::json!([{ "@id": "https://example.org/m1.tiff" }, { "@id": "#collection-c1-member-m2" }])
+        );
+        assert_eq!(entity("https://example.org/m1.tiff")["additionalType"], "image/tiff");
+        assert_eq!(entity("#collection-c1-member-m2")["identifier"], "m2");
+    }
+}
@@ -0,0 +1,131 @@
+//! A minimal ZIP writer which stores files without compression.
+//!
+//! RO-Crates are small, so compression is not worth an additional dependency.
+
+/// Collects files and serializes them into a ZIP archive.
+#[derive(Debug, Default)]
+pub struct ZipWriter {
+    entries: Vec<ZipEntry>,
+}
+
+#[derive(Debug)]
+struct ZipEntry {
+    name: String,
+    content: Vec<u8>,
+    crc: u32,
+}
+
+impl ZipWriter {
+    pub fn add_file<N: Into<String>>(&mut self, name: N, content: Vec<u8>) -> &mut Self {
+        let crc = crc32(&content);
+        self.entries.push(ZipEntry {
+            name: name.into(),
+            content,
+            crc,
+        });
+        self
+    }
+
+    pub fn finish(&self) -> Vec<u8> {
+        let mut archive = Vec::new();
+        let mut central_directory = Vec::new();
+        for entry in self.entries.iter() {
+            let offset = archive.len() as u32;
+            let name = entry.name.as_bytes();
+            let size = entry.content.len() as u32;
+
+            // local file header
+            push_u32(&mut archive, 0x0403_4b50);
+            push_u16(&mut archive, 20); // version needed to extract
+            push_u16(&mut archive, 0x0800); // flags: names are UTF-8
+            push_u16(&mut archive, 0); // method: stored
+            push_u16(&mut archive, 0); // modification time
+            push_u16(&mut archive, 0x0021); // modification date (1980-01-01)
+            push_u32(&mut archive, entry.crc);
+            push_u32(&mut archive, size);
+            push_u32(&mut archive, size);
+            push_u16(&mut archive, name.len() as u16);
+            push_u16(&mut archive, 0); // extra field length
+            archive.extend_from_slice(name);
+            archive.extend_from_slice(&entry.content);
+
+            // central directory header
+            push_u32(&mut central_directory, 0x0201_4b50);
+            push_u16(&mut central_directory, 20); // version made by
+            push_u16(&mut central_directory, 20); // version needed to extract
+            push_u16(&mut central_directory, 0x0800);
+            push_u16(&mut central_directory, 0);
+            push_u16(&mut central_directory, 0);
+            push_u16(&mut central_directory, 0x0021);
+            push_u32(&mut central_directory, entry.crc);
+            push_u32(&mut central_directory, size);
+            push_u32(&mut central_directory, size);
+            push_u16(&mut central_directory, name.len() as u16);
+            push_u16(&mut central_directory, 0); // extra field length
+            push_u16(&mut central_directory, 0); // comment length
+            push_u16(&mut central_directory, 0); // disk number
+            push_u16(&mut central_directory, 0); // internal attributes
+            push_u32(&mut central_directory, 0); // external attributes
+            push_u32(&mut central_directory, offset);
+            central_directory.extend_from_slice(name);
+        }
+        let directory_offset = archive.len() as u32;
+        let directory_size = central_directory.len() as u32;
+        archive.extend(central_directory);
+
+        // end of central directory record
+        let count = self.entries.len() as u16;
+        push_u32(&mut archive, 0x0605_4b50);
+        push_u16(&mut archive, 0);
+        push_u16(&mut archive, 0);
+        push_u16(&mut archive, count);
+        push_u16(&mut archive, count);
+        push_u32(&mut archive, directory_size);
+        push_u32(&mut archive, directory_offset);
+        push_u16(&mut archive, 0); // comment length
+        archive
+    }
+}
+
+fn push_u16(buffer: &mut Vec<u8>, value: u16) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+/// CRC-32 (IEEE) as required by the ZIP format.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (!(crc & 1)).wrapping_add(1);
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn archive_layout() {
+        let mut zip = ZipWriter::default();
+        zip.add_file("a.txt", b"hello".to_vec());
+        let archive = zip.finish();
+        assert_eq!(&archive[0..4], &[0x50, 0x4b, 0x03, 0x04]);
+        // 30 bytes local header + name + content, 46 bytes central header + name, 22 bytes end record
+        assert_eq!(archive.len(), 30 + 5 + 5 + 46 + 5 + 22);
+        let end = &archive[archive.len() - 22..];
+        assert_eq!(&end[0..4], &[0x50, 0x4b, 0x05, 0x06]);
+    }
+}
@@ -0,0 +1,103 @@
+//! Requests to and responses from the RDA collection API.
+//! The `CollectionService` agent sends them, the integration tests use them directly.
+
+use serde_json as json;
+
+use super::{ApiRequest, ApiResponse};
+use crate::collection_service::{collection::Collection, member_item::Member, Etag, MaybeEtag};
+
+/// POST: create one or more collections.
+pub fn create_collections_request(base: &str, collections: &[Collection]) -> ApiRequest {
+    let body = json::to_string(collections).expect("Could not serialize collections.");
+    http::Request::post(collections_uri(base))
+        .header("Content-Type", "application/json")
+        .body(Some(body))
+        .expect("Failed to build this request.")
+}
+
+/// PUT: update a registered collection. The ETag has to match the current one.
+pub fn push_collection_request(base: &str, collection: &Collection, etag: &Etag) -> Result<ApiRequest, String> {
+    let id = collection
+        .get_id()
+        .ok_or_else(|| "Tried to push (update) a collection without id (not registered).".to_string())?;
+    let body = json::to_string(collection).expect("Could not serialize collection.");
+    Ok(http::Request::put(collection_uri(base, id))
+        .header("Content-Type", "application/json")
+        .header("IF-Match", etag)
+        .body(Some(body))
+        .expect("Failed to build this request."))
+}
+
+pub fn pull_collection_request(base: &str, id: &str) -> ApiRequest {
+    http::Request::get(collection_uri(base, id))
+        .body(None)
+        .expect("Failed to build this request.")
+}
+
+pub fn members_request(base: &str, id: &str) -> ApiRequest {
+    http::Request::get(members_uri(base, id))
+        .body(None)
+        .expect("Failed to build this request.")
+}
+
+pub fn add_members_request(base: &str, id: &str, members: &[Member]) -> ApiRequest {
+    let body = json::to_string(members).expect("Could not serialize members.");
+    http::Request::post(members_uri(base, id))
+        .header("Content-Type", "application/json")
+        .body(Some(body))
+        .expect("Failed to build this request.")
+}
+
+pub fn parse_collections(response: &ApiResponse) -> Result<Vec<Collection>, String> {
+    successful(response)?;
+    json::from_str(response.body())
+        .map_err(|e| format!("Could not parse collections from collection service: {:?}", e))
+}
+
+/// A single collection and its ETag.
+pub fn parse_collection(response: &ApiResponse) -> Result<(Collection, MaybeEtag), String> {
+    successful(response)?;
+    let etag = response.headers().get("ETag").cloned();
+    json::from_str(response.body())
+        .map(|collection| (collection, etag))
+        .map_err(|e| format!("Could not parse collection from collection service: {:?}", e))
+}
+
+/// The collection service returns members as a page, which contains them as `contents`.
+pub fn parse_members(response: &ApiResponse) -> Result<Vec<Member>, String> {
+    successful(response)?;
+    let value: json::Value = json::from_str(response.body()).map_err(|e| e.to_string())?;
+    let list = value.get("contents").cloned().unwrap_or(value);
+    json::from_value(list).map_err(|e| format!("Could not parse members from collection service: {}", e))
+}
+
+fn successful(response: &ApiResponse) -> Result<(), String> {
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Response of collection service was not successful ({}): {}",
+            response.status(),
+            response.body()
+        ))
+    }
+}
+
+/// POST: create one or more collections.
+/// GET: get collections within this API.
+pub fn collections_uri(base: &str) -> String {
+    format!("{}/{}/", base, "collections")
+}
+
+/// GET: get the collection
+/// PUT: update the collections properties
+/// DELETE: delete the collection
+pub fn collection_uri(base: &str, id: &str) -> String {
+    format!("{}/{}/{}", base, "collections", id)
+}
+
+/// GET: get the members of the collection
+/// POST: add one or more members to the collection
+pub fn members_uri(base: &str, id: &str) -> String {
+    format!("{}/members", collection_uri(base, id))
+}
@@ -0,0 +1,224 @@
+//! Requests to and responses from the REST API of the Handle System (`api/handles/{handle}`).
+//!
+//! Handle records are lists of typed values. Values whose type is a PID are the attributes
+//! of an FDO record, keyed by that type, like the entries of a `PidRecord`. Other values,
+//! like `HS_ADMIN` or `URL`, are administrative and left out.
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+use super::{ApiRequest, ApiResponse, PidRecord};
+
+/// Success, as the `responseCode` of the Handle API.
+const SUCCESS: i64 = 1;
+const HANDLE_NOT_FOUND: i64 = 100;
+
+/// Indices which handles conventionally use for administrative values: `URL` and `HS_ADMIN`.
+const RESERVED_INDICES: [u32; 2] = [1, 100];
+
+/// Characters of a handle suffix which are escaped in URIs. Slashes stay, they are part of the suffix.
+const SUFFIX: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HandleValue {
+    pub index: u32,
+    #[serde(rename = "type")]
+    pub value_type: String,
+    pub data: HandleData,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HandleData {
+    pub format: String,
+    pub value: json::Value,
+}
+
+#[derive(Deserialize)]
+struct HandleResponse {
+    #[serde(rename = "responseCode")]
+    response_code: i64,
+    #[serde(default)]
+    handle: String,
+    #[serde(default)]
+    values: Vec<HandleValue>,
+}
+
+pub fn resolve_request(base: &str, handle: &str) -> ApiRequest {
+    http::Request::get(handle_uri(base, handle))
+        .header("Accept", "application/json")
+        .body(None)
+        .expect("Failed to build this request.")
+}
+
+/// Creates or replaces the handle with the PID of the record. The handle is replaced as a whole,
+/// so the administrative values of the `existing` handle, like `HS_ADMIN`, are sent again.
+pub fn update_request(base: &str, record: &PidRecord, existing: &[HandleValue]) -> ApiRequest {
+    let mut values: Vec<HandleValue> = existing.iter().filter(|v| !is_attribute(&v.value_type)).cloned().collect();
+    let taken: Vec<u32> = values.iter().map(|v| v.index).collect();
+    values.extend(to_values(record, &taken));
+    let body = json::json!({ "values": values }).to_string();
+    http::Request::put(format!("{}?overwrite=true", handle_uri(base, &record.pid)))
+        .header("Content-Type", "application/json")
+        .body(Some(body))
+        .expect("Failed to build this request.")
+}
+
+/// The URI of the handle. The prefix is a plain name, the suffix may contain any character.
+pub fn handle_uri(base: &str, handle: &str) -> String {
+    match handle.split_once('/') {
+        Some((prefix, suffix)) => format!("{}/api/handles/{}/{}", base, prefix, utf8_percent_encode(suffix, SUFFIX)),
+        None => format!("{}/api/handles/{}", base, utf8_percent_encode(handle, SUFFIX)),
+    }
+}
+
+/// The record of a resolved handle. Handle values have no names,
+/// so the type PID is used as name until the record is read into a `PidInfo`.
+pub fn parse_record(response: &ApiResponse) -> Result<PidRecord, String> {
+    parse_values(response).map(|(handle, values)| from_values(&handle, &values))
+}
+
+/// The resolved handle with all its values, including the administrative ones.
+pub fn parse_values(response: &ApiResponse) -> Result<(String, Vec<HandleValue>), String> {
+    let parsed: Option<HandleResponse> = json::from_str(response.body()).ok();
+    match parsed {
+        Some(parsed) if response.status().is_success() && parsed.response_code == SUCCESS => {
+            Ok((parsed.handle, parsed.values))
+        }
+        Some(parsed) if parsed.response_code == HANDLE_NOT_FOUND => {
+            Err(format!("Handle {} does not exist.", parsed.handle))
+        }
+        _ => Err(format!("HTTP error {}: {}", response.status(), response.body())),
+    }
+}
+
+pub fn from_values(handle: &str, values: &[HandleValue]) -> PidRecord {
+    let mut sorted: Vec<&HandleValue> = values.iter().filter(|v| is_attribute(&v.value_type)).collect();
+    sorted.sort_by_key(|v| v.index);
+    let mut record = PidRecord {
+        pid: handle.to_owned(),
+        ..Default::default()
+    };
+    for value in sorted {
+        record.add_attribute(value.value_type.clone(), value.value_type.clone(), value.data.value.clone());
+    }
+    record
+}
+
+/// The values of the record, in the order of their types. They are numbered from 1,
+/// skipping the reserved indices and those `taken` by other values of the handle.
+/// Values which are not strings are stored as JSON text, like the PIT service does.
+pub fn to_values(record: &PidRecord, taken: &[u32]) -> Vec<HandleValue> {
+    let mut keys: Vec<&String> = record.entries.keys().collect();
+    keys.sort();
+    let free = (1..).filter(|index| !RESERVED_INDICES.contains(index) && !taken.contains(index));
+    keys.into_iter()
+        .flat_map(|key| record.entries[key].iter())
+        .zip(free)
+        .map(|(entry, index)| HandleValue {
+            index,
+            value_type: entry.key.clone(),
+            data: HandleData {
+                format: "string".into(),
+                value: match &entry.value {
+                    json::Value::String(_) => entry.value.clone(),
+                    other => json::Value::String(other.to_string()),
+                },
+            },
+        })
+        .collect()
+}
+
+/// Attribute types are PIDs, i.e. `21.T11148/076759916209e5d62bd5`.
+fn is_attribute(value_type: &str) -> bool {
+    value_type.contains('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_map_to_entries_and_back() {
+        let response = http::Response::new(
+            r#"{"responseCode":1,"handle":"21.T11148/abc","values":[
+                {"index":2,"type":"21.T11148/c692273deb2772da307f","data":{"format":"string","value":"1.0.0"}},
+                {"index":1,"type":"URL","data":{"format":"string","value":"https://example.org"}},
+                {"index":3,"type":"21.T11148/b8457812905b83046284","data":{"format":"string","value":"https://example.org/a"}},
+                {"index":4,"type":"21.T11148/b8457812905b83046284","data":{"format":"string","value":"https://example.org/b"}},
+                {"index":100,"type":"HS_ADMIN","data":{"format":"admin","value":{"handle":"0.NA/21.T11148","index":200}}}
+            ]}"#
+            .to_string(),
+        );
+        let record = parse_record(&response).unwrap();
+        assert_eq!(record.pid, "21.T11148/abc");
+        assert_eq!(record.entries.len(), 2);
+        let locations = &record.entries["21.T11148/b8457812905b83046284"];
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[1].value, "https://example.org/b");
+
+        let values = to_values(&record, &[]);
+        assert_eq!(values.len(), 3);
+        assert_eq!(values.iter().map(|v| v.index).collect::<Vec<_>>(), [2, 3, 4]);
+        assert!(from_values(&record.pid, &values).diff(&record).is_empty());
+        assert_eq!(to_values(&record, &[3]).iter().map(|v| v.index).collect::<Vec<_>>(), [2, 4, 5]);
+    }
+
+    #[test]
+    fn updates_keep_the_administrative_values() {
+        let admin = HandleValue {
+            index: 100,
+            value_type: "HS_ADMIN".into(),
+            data: HandleData {
+                format: "admin".into(),
+                value: json::json!({ "handle": "0.NA/21.T11148", "index": 200 }),
+            },
+        };
+        let url = HandleValue {
+            index: 2,
+            value_type: "URL".into(),
+            data: HandleData {
+                format: "string".into(),
+                value: "https://example.org".into(),
+            },
+        };
+        let mut record = PidRecord {
+            pid: "21.T11148/abc".into(),
+            ..Default::default()
+        };
+        record.add_attribute("21.T11148/c692273deb2772da307f".into(), "version".into(), "1.0.0".into());
+        let request = update_request("http://localhost", &record, &[admin.clone(), url]);
+        let body: json::Value = json::from_str(request.body().as_deref().unwrap()).unwrap();
+        let values: Vec<HandleValue> = json::from_value(body["values"].clone()).unwrap();
+        assert!(values.contains(&admin));
+        let indices: Vec<u32> = values.iter().map(|v| v.index).collect();
+        assert_eq!(indices, [100, 2, 3]);
+    }
+
+    #[test]
+    fn suffixes_are_encoded() {
+        assert_eq!(
+            handle_uri("http://localhost", "21.T11148/a b/c?d#e%"),
+            "http://localhost/api/handles/21.T11148/a%20b/c%3Fd%23e%25"
+        );
+    }
+
+    #[test]
+    fn unknown_handles_are_errors() {
+        let response = http::Response::builder()
+            .status(404)
+            .body(r#"{"responseCode":100,"handle":"21.T11148/missing"}"#.to_string())
+            .unwrap();
+        assert_eq!(parse_record(&response).unwrap_err(), "Handle 21.T11148/missing does not exist.");
+    }
+}
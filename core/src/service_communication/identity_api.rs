@@ -0,0 +1,33 @@
+//! Requests to and responses from the service resolving ORCID and ROR identifiers
+//! to names (`{base}/orcid/{id}` and `{base}/ror/{id}`, answered with `{ "name": "..." }`).
+
+use serde_json as json;
+
+use super::{ApiRequest, ApiResponse};
+use crate::data_type_registry::Identifier;
+
+/// Only ORCID and ROR identifiers can be looked up.
+pub fn lookup_request(base: &str, identifier: &Identifier) -> Option<ApiRequest> {
+    let uri = match identifier {
+        Identifier::Orcid(id) => format!("{}/orcid/{}", base, id),
+        Identifier::Ror(id) => format!("{}/ror/{}", base, id),
+        Identifier::Other(_) => return None,
+    };
+    let request = http::Request::get(uri)
+        .header("Accept", "application/json")
+        .body(None)
+        .expect("Failed to build this request.");
+    Some(request)
+}
+
+pub fn parse_name(response: &ApiResponse) -> Result<String, String> {
+    if !response.status().is_success() {
+        return Err(format!("The identity service answered {}.", response.status()));
+    }
+    json::from_str::<json::Value>(response.body())
+        .map_err(|e| e.to_string())?
+        .get("name")
+        .and_then(|name| name.as_str())
+        .map(String::from)
+        .ok_or_else(|| "The response contains no name.".into())
+}
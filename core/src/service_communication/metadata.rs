@@ -0,0 +1,106 @@
+use super::primitive_types as primitive;
+use crate::data_type_registry::{AttributeType, AttributeValue, ValueError, ValueKind};
+
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+/// A reference to a metadata document, i.e. a DataCite or schema.org document
+/// describing the object, or an annotation.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct MetadataObjectReference {
+    pub context: MetadataContext,
+    pub resource: ResourceReference,
+}
+
+impl MetadataObjectReference {
+    pub fn as_value(&self) -> AttributeValue {
+        AttributeValue::Object(json::json!({
+            "relation": self.context,
+            "resource": self.resource.as_json()
+        }))
+    }
+
+    /// Parses a reference from a record value.
+    /// Accepts json strings (as written by this client), json objects and plain references.
+    pub fn from_json(value: &json::Value) -> Option<Self> {
+        match AttributeValue::decode(ValueKind::Object, value).map(AttributeValue::into_object) {
+            Ok(Some(json::Value::Object(map))) => {
+                let resource = map.get("resource").and_then(|r| r.as_str())?;
+                let context = map
+                    .get("relation")
+                    .and_then(|c| json::from_value(c.clone()).ok())
+                    .unwrap_or_default();
+                Some(Self {
+                    context,
+                    resource: ResourceReference::from(resource),
+                })
+            }
+            Ok(_) => None,
+            // a plain handle or URL
+            Err(_) => value.as_str().filter(|s| !s.is_empty()).map(|s| Self {
+                context: MetadataContext::default(),
+                resource: ResourceReference::from(s),
+            }),
+        }
+    }
+}
+
+/// References are read as text: a json object as written by this client, or a plain handle or URL.
+impl AttributeType for MetadataObjectReference {
+    const KIND: ValueKind = ValueKind::String;
+
+    fn to_value(&self) -> AttributeValue {
+        self.as_value()
+    }
+
+    fn from_value(value: AttributeValue) -> Result<Self, ValueError> {
+        let text = value.encode();
+        Self::from_json(&text).ok_or(ValueError::Mismatch(ValueKind::Object, text))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetadataContext {
+    #[serde(rename = "annotating")]
+    Annotating,
+    #[serde(rename = "ontology")]
+    Ontology,
+    #[default]
+    #[serde(rename = "describing")]
+    Describing,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResourceReference {
+    Handle(primitive::Pid),
+    Url(primitive::URL),
+}
+
+impl Default for ResourceReference {
+    fn default() -> Self {
+        Self::Url(String::new())
+    }
+}
+
+impl From<&str> for ResourceReference {
+    fn from(reference: &str) -> Self {
+        if reference.contains("://") {
+            Self::Url(reference.into())
+        } else {
+            Self::Handle(reference.into())
+        }
+    }
+}
+
+impl ResourceReference {
+    pub fn as_json(&self) -> json::Value {
+        json::Value::String(self.as_str().into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            ResourceReference::Url(url) => url.as_str(),
+            ResourceReference::Handle(handle) => handle.as_str(),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! This module contains serializable and deserializable types
+//! and conversions between types for communicating with other
+//! services using JSON.
+//!
+//! The `*_api` modules build the HTTP requests and parse the responses
+//! of the services, independent of how they are sent.
+
+#[macro_use]
+pub mod primitive_types;
+pub mod collection_api;
+pub mod handle_api;
+pub mod identity_api;
+pub mod pit_api;
+pub mod pit_record;
+pub mod transport;
+pub mod types;
+
+mod metadata;
+
+pub use pit_record::PidRecord;
+pub use metadata::*;
+
+/// A request to a service. Requests without body have `None`.
+pub type ApiRequest = http::Request<Option<String>>;
+/// A response of a service with its body as text.
+pub type ApiResponse = http::Response<String>;
+
+pub trait RecordEntry {
+    /// Writes content of entry representation into the given record.
+    fn write(&self, record: &mut PidRecord);
+}
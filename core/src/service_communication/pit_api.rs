@@ -0,0 +1,45 @@
+//! Requests to and responses from the `api/v1/pit/pid` endpoints of the PIT service.
+//! The `PitService` agent sends them, the integration tests use them directly.
+
+use serde_json as json;
+
+use super::{ApiRequest, ApiResponse, PidRecord};
+
+pub fn register_request(base: &str, record: &PidRecord) -> ApiRequest {
+    let body = json::to_string(record).expect("Records can always be serialized.");
+    http::Request::post(create_uri(base))
+        .header("Content-Type", "application/json")
+        .body(Some(body))
+        .expect("Failed to build this request.")
+}
+
+/// Updates the record with the PID given in the record.
+pub fn update_request(base: &str, record: &PidRecord) -> ApiRequest {
+    let body = json::to_string(record).expect("Records can always be serialized.");
+    http::Request::put(record_uri(base, &record.pid))
+        .header("Content-Type", "application/json")
+        .body(Some(body))
+        .expect("Failed to build this request.")
+}
+
+pub fn resolve_request(base: &str, pid: &str) -> ApiRequest {
+    http::Request::get(record_uri(base, pid))
+        .body(None)
+        .expect("Failed to build this request.")
+}
+
+pub fn parse_record(response: &ApiResponse) -> Result<PidRecord, String> {
+    if response.status().is_success() {
+        json::from_str(response.body()).map_err(|e| format!("Error parsing record: {:?}", e))
+    } else {
+        Err(format!("HTTP error {}: {}", response.status(), response.body()))
+    }
+}
+
+pub fn create_uri(base: &str) -> String {
+    format!("{}/{}/", base, "api/v1/pit/pid")
+}
+
+pub fn record_uri(base: &str, pid: &str) -> String {
+    format!("{}/{}/{}", base, "api/v1/pit/pid", pid)
+}
@@ -0,0 +1,220 @@
+//! This module contains a serializable record definition
+//! that is used by the PIT service.
+use super::primitive_types::Pid;
+use crate::data_type_registry::{is_draft_type, AttributeValue, ValueError, ValueKind};
+use ::std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+pub struct PidRecordEntry {
+    pub key: String,
+    pub name: String,
+    pub value: json::Value,
+}
+
+impl PidRecordEntry {
+    /// Reads the value of this entry as the given kind.
+    pub fn decode(&self, kind: ValueKind) -> Result<AttributeValue, ValueError> {
+        AttributeValue::decode(kind, &self.value)
+    }
+}
+
+/// A key whose values differ between two records.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RecordDiff {
+    pub key: String,
+    /// The name of the attribute, taken from whichever record has it.
+    pub name: String,
+    pub left: Vec<json::Value>,
+    pub right: Vec<json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct PidRecord {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub pid: Pid,
+    pub entries: HashMap<String, Vec<PidRecordEntry>>,
+}
+
+impl PidRecord {
+    pub fn add_attribute(&mut self, id: String, name: String, value: json::Value) {
+        let entry = PidRecordEntry {
+            key: id.clone(),
+            name,
+            value,
+        };
+        let values = self.entries.entry(id.clone()).or_default();
+        values.push(entry);
+    }
+
+    /// Adds a typed value, encoded for the PIT service.
+    pub fn add_value(&mut self, id: String, name: String, value: AttributeValue) {
+        self.add_attribute(id, name, value.encode())
+    }
+
+    /// A short summary of type, version and date of the object.
+    pub fn describe(&self) -> String {
+        use crate::data_type_registry::{DateCreated, DateModified, DigitalObjectType, HasProfileKey, Version};
+        use std::convert::TryFrom;
+
+        let has = |key: Pid| self.entries.get(&key).is_some_and(|list| !list.is_empty());
+        let mut parts = Vec::new();
+        match DigitalObjectType::try_from(self) {
+            Ok(t) => parts.push(t.to_string()),
+            Err(Some(pid)) => parts.push(format!("Type {}", pid.0)),
+            Err(None) => {}
+        }
+        if has(Version::get_key().0) {
+            parts.push(format!("version {}", Version::from(self).0));
+        }
+        let date = |d: &crate::data_type_registry::DateTimeHandle| d.to_rfc3339().chars().take(10).collect::<String>();
+        if has(DateModified::get_key().0) {
+            parts.push(format!("modified {}", date(&DateModified::from(self).0)));
+        } else if has(DateCreated::get_key().0) {
+            parts.push(format!("created {}", date(&DateCreated::from(self).0)));
+        }
+        if parts.is_empty() {
+            String::from("No description available")
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// A copy without attributes of draft types or referring to them, see `data_type_registry::draft_types`.
+    pub fn without_draft_types(&self) -> Self {
+        let mut record = self.clone();
+        record.entries.retain(|key, _| !is_draft_type(key));
+        for list in record.entries.values_mut() {
+            list.retain(|entry| !entry.value.as_str().is_some_and(is_draft_type));
+        }
+        record.entries.retain(|_, list| !list.is_empty());
+        record
+    }
+
+    /// The record as it is sent to the PIT service.
+    pub fn publishable(&self) -> Self {
+        if crate::config::publish_draft_types() {
+            self.clone()
+        } else {
+            self.without_draft_types()
+        }
+    }
+
+    pub fn same_content_like(&self, other: &Self) -> bool {
+        self.entries
+            .keys()
+            .chain(other.entries.keys())
+            .filter(|&key| self.entries.get(key) != other.entries.get(key))
+            .map(|x| log::debug!("Found difference: {}", x))
+            .count()
+            == 0
+    }
+
+    /// All keys with different values in both records, sorted by key. The PIDs are not compared.
+    pub fn diff(&self, other: &Self) -> Vec<RecordDiff> {
+        let values = |record: &Self, key: &String| -> Vec<json::Value> {
+            record.entries.get(key).map(|list| list.iter().map(|e| e.value.clone()).collect()).unwrap_or_default()
+        };
+        let mut keys: Vec<&String> = self.entries.keys().chain(other.entries.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        keys.into_iter()
+            .filter(|&key| values(self, key) != values(other, key))
+            .map(|key| RecordDiff {
+                key: key.clone(),
+                name: self
+                    .entries
+                    .get(key)
+                    .or_else(|| other.entries.get(key))
+                    .and_then(|list| list.first())
+                    .map(|e| e.name.clone())
+                    .unwrap_or_default(),
+                left: values(self, key),
+                right: values(other, key),
+            })
+            .collect()
+    }
+}
+
+impl PartialEq for PidRecord {
+    /// Two records are equal in this case, if they have the same PID (in case they both have a PID).
+    /// If the pids of both records are not empty, they will be used for comparison.
+    /// If the pids are empty, the content is compared.
+    fn eq(&self, other: &Self) -> bool {
+        match (self.pid.is_empty(), other.pid.is_empty()) {
+            (false, false) => self.pid == other.pid,
+            (true, true) => {
+                self.entries
+                    .keys()
+                    .chain(other.entries.keys())
+                    .filter(|&key| self.entries.get(key) != other.entries.get(key))
+                    .count()
+                    == 0
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for PidRecord {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_type_version_and_date() {
+        let mut record = PidRecord::default();
+        assert_eq!(record.describe(), "No description available");
+        record.add_attribute(
+            "21.T11148/c692273deb2772da307f".into(),
+            "version".into(),
+            "2.0.0".into(),
+        );
+        record.add_attribute(
+            "21.T11148/aafd5fb4c7222e2d950a".into(),
+            "dateCreated".into(),
+            "2021-03-04T10:00:00+00:00".into(),
+        );
+        assert_eq!(record.describe(), "version 2.0.0, created 2021-03-04");
+    }
+
+    #[test]
+    fn diff_lists_changed_added_and_removed_keys() {
+        let mut left = PidRecord::default();
+        left.add_attribute("a".into(), "version".into(), "1.0.0".into());
+        left.add_attribute("b".into(), "same".into(), "x".into());
+        left.add_attribute("c".into(), "removed".into(), "y".into());
+        let mut right = PidRecord {
+            pid: "sandboxed/1".into(),
+            ..Default::default()
+        };
+        right.add_attribute("a".into(), "version".into(), "2.0.0".into());
+        right.add_attribute("b".into(), "same".into(), "x".into());
+        right.add_attribute("d".into(), "added".into(), "z".into());
+
+        let diff = left.diff(&right);
+        let keys: Vec<&str> = diff.iter().map(|d| d.key.as_str()).collect();
+        assert_eq!(keys, ["a", "c", "d"]);
+        assert_eq!(diff[0].left, vec![json::Value::from("1.0.0")]);
+        assert_eq!(diff[0].right, vec![json::Value::from("2.0.0")]);
+        assert!(diff[1].right.is_empty());
+        assert_eq!(diff[2].name, "added");
+        assert!(left.diff(&left).is_empty());
+    }
+
+    #[test]
+    fn the_status_of_an_object_is_not_published() {
+        use crate::data_type_registry::{self as dtr, HasProfileKey, ObjectStatus, SupersededBy, Tombstone, Version};
+        let mut record = PidRecord::default();
+        Version("1.0.0".into()).write(&mut record);
+        ObjectStatus::Withdrawn.write(&mut record);
+        Tombstone(Some("Retracted.".into())).write(&mut record);
+        SupersededBy(Some(dtr::Pid("sandboxed/2".into()))).write(&mut record);
+
+        let published = record.without_draft_types();
+        let keys: Vec<&str> = published.entries.keys().map(String::as_str).collect();
+        assert_eq!(keys, [Version::get_key().0.as_str()]);
+    }
+}
@@ -32,18 +32,13 @@ pub type Pid = String;
 pub type PidProxy = String;
 pub type URL = String;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq)]
 pub enum Profile {
+    #[default]
     Testbed4infSimplified,
     Testbed4infRaw,
 }
 
-impl Default for Profile {
-    fn default() -> Self {
-        Self::Testbed4infSimplified
-    }
-}
-
 impl From<i32> for Profile {
     fn from(index: i32) -> Self {
         match index {
@@ -58,71 +53,11 @@ impl From<i32> for Profile {
 }
 
 #[derive(Default)]
-pub struct Policy {
-    pub lifecycle: Lifecycle,
-    pub license: License,
-    pub tombstone: Option<String>,
-}
-
-pub enum Lifecycle {
-    Static,
-    RegularUpdates,
-    IrregularUpdates,
-}
-
-impl Default for Lifecycle {
-    fn default() -> Self {
-        Self::Static
-    }
-}
-
-impl From<i32> for Lifecycle {
-    fn from(index: i32) -> Self {
-        match index {
-            0 => Self::Static,
-            1 => Self::RegularUpdates,
-            2 => Self::IrregularUpdates,
-            unknown => {
-                log::error!("Lifecycle index '{}' not implemented.", unknown);
-                Self::default()
-            },
-        }
-    }
-}
-
-pub enum License {
-    MIT,
-    Apache,
-    CcBy,
-}
-
-impl Default for License {
-    fn default() -> Self {
-        Self::MIT
-    }
-}
-
-impl From<i32> for License {
-    fn from(index: i32) -> Self {
-        match index {
-            0 => Self::MIT,
-            1 => Self::Apache,
-            2 => Self::CcBy,
-            _ => Self::default(),
-        }
-    }
-}
-
 pub enum HashAlgorithm {
+    #[default]
     Sha256sum,
 }
 
-impl Default for HashAlgorithm {
-    fn default() -> Self {
-        Self::Sha256sum
-    }
-}
-
 pub struct Checksum {
     pub algorithm: HashAlgorithm,
     pub value: String,
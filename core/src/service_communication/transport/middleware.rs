@@ -0,0 +1,149 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use http::{header::HeaderName, HeaderValue, Method};
+
+use super::{describe, is_transient, ApiRequest, Attempt, Middleware, Next};
+
+/// Sets a header on every request, unless the request already has it. I.e. for authorization.
+pub struct Header {
+    name: HeaderName,
+    value: HeaderValue,
+}
+
+impl Header {
+    pub fn new(name: &str, value: &str) -> Result<Self, String> {
+        Ok(Self {
+            name: HeaderName::from_bytes(name.as_bytes()).map_err(|e| format!("Invalid header name {}: {}", name, e))?,
+            value: HeaderValue::from_str(value).map_err(|e| format!("Invalid value for header {}: {}", name, e))?,
+        })
+    }
+
+    pub fn bearer(token: &str) -> Result<Self, String> {
+        Self::new("Authorization", &format!("Bearer {}", token))
+    }
+}
+
+impl Middleware for Header {
+    fn prepare(&self, request: &mut ApiRequest) {
+        request
+            .headers_mut()
+            .entry(self.name.clone())
+            .or_insert_with(|| self.value.clone());
+    }
+}
+
+/// Logs every attempt with its status and duration. Errors are warnings.
+pub struct Logging {
+    service: &'static str,
+}
+
+impl Logging {
+    pub fn new(service: &'static str) -> Self {
+        Self { service }
+    }
+}
+
+impl Middleware for Logging {
+    fn finished(&self, attempt: &Attempt) -> Next {
+        let request = describe(attempt.request);
+        let ms = attempt.elapsed.num_milliseconds();
+        match attempt.outcome {
+            Ok(response) if response.status().is_success() => {
+                log::debug!("{}: {} -> {} ({} ms)", self.service, request, response.status(), ms)
+            }
+            Ok(response) => log::warn!("{}: {} -> {} ({} ms)", self.service, request, response.status(), ms),
+            Err(e) => log::warn!("{}: {} failed after {} ms: {}", self.service, request, ms, e),
+        }
+        Next::Deliver
+    }
+}
+
+/// Sends idempotent requests again if the service is unreachable or temporarily unavailable.
+/// Registrations (POST) are never repeated, as they could create an object twice.
+pub struct Retry {
+    attempts: u32,
+    backoff: Duration,
+}
+
+impl Retry {
+    /// The longest delay between two attempts.
+    pub const MAX_DELAY: Duration = Duration::from_secs(60);
+
+    /// Retries immediately. `attempts` includes the first one.
+    pub fn new(attempts: u32) -> Self {
+        Self {
+            attempts,
+            backoff: Duration::from_secs(0),
+        }
+    }
+
+    /// Waits before each retry, starting with `first` and doubling the delay each time.
+    pub fn with_backoff(mut self, first: Duration) -> Self {
+        self.backoff = first;
+        self
+    }
+
+    /// The delay after the given (failed) attempt.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.backoff.checked_mul(factor).map_or(Self::MAX_DELAY, |d| d.min(Self::MAX_DELAY))
+    }
+
+    pub fn is_idempotent(method: &Method) -> bool {
+        matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+        )
+    }
+}
+
+impl Middleware for Retry {
+    fn finished(&self, attempt: &Attempt) -> Next {
+        if attempt.number < self.attempts && Self::is_idempotent(attempt.request.method()) && is_transient(attempt.outcome) {
+            Next::Retry(self.delay(attempt.number))
+        } else {
+            Next::Deliver
+        }
+    }
+}
+
+/// The duration of one attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timed {
+    pub request: String,
+    pub attempt: u32,
+    /// `None` if there was no response.
+    pub status: Option<u16>,
+    pub milliseconds: i64,
+}
+
+/// Keeps the durations of the latest attempts. Clones share them.
+#[derive(Debug, Clone, Default)]
+pub struct Timing {
+    records: Rc<RefCell<Vec<Timed>>>,
+}
+
+impl Timing {
+    const LIMIT: usize = 100;
+
+    /// The latest attempts, oldest first.
+    pub fn records(&self) -> Vec<Timed> {
+        self.records.borrow().clone()
+    }
+}
+
+impl Middleware for Timing {
+    fn finished(&self, attempt: &Attempt) -> Next {
+        let mut records = self.records.borrow_mut();
+        if records.len() == Self::LIMIT {
+            records.remove(0);
+        }
+        records.push(Timed {
+            request: describe(attempt.request),
+            attempt: attempt.number,
+            status: attempt.outcome.as_ref().ok().map(|r| r.status().as_u16()),
+            milliseconds: attempt.elapsed.num_milliseconds(),
+        });
+        Next::Deliver
+    }
+}
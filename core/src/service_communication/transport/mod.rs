@@ -0,0 +1,288 @@
+//! Sending requests to the services.
+//!
+//! The service clients hand their requests to a `Transport`, which calls back with the outcome.
+//! A `Pipeline` wraps a transport with `Middleware`, so all clients share the same handling
+//! of headers, logging and retries. Transports exist for the browser (in the web application),
+//! natively (feature `native`) and for tests (`Replay`).
+
+mod middleware;
+#[cfg(feature = "native")]
+mod native;
+mod replay;
+
+pub use middleware::*;
+#[cfg(feature = "native")]
+pub use native::NativeTransport;
+pub use replay::*;
+
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use super::{ApiRequest, ApiResponse};
+
+/// The response of a service, or why there is none, i.e. the service is unreachable.
+/// HTTP errors are responses.
+pub type Outcome = Result<ApiResponse, String>;
+
+/// Receives the outcome of a request.
+pub type Done = Box<dyn FnOnce(Outcome)>;
+
+pub trait Transport {
+    /// Sends the request and calls `done` with the outcome, exactly once.
+    fn send(&self, request: ApiRequest, done: Done);
+
+    /// Calls `then` after the delay, i.e. before a retry. Transports which can not wait continue immediately.
+    fn wait(&self, _delay: Duration, then: Box<dyn FnOnce()>) {
+        then()
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Rc<T> {
+    fn send(&self, request: ApiRequest, done: Done) {
+        (**self).send(request, done)
+    }
+
+    fn wait(&self, delay: Duration, then: Box<dyn FnOnce()>) {
+        (**self).wait(delay, then)
+    }
+}
+
+/// A finished attempt to send a request, as seen by the middleware.
+pub struct Attempt<'a> {
+    pub request: &'a ApiRequest,
+    pub outcome: &'a Outcome,
+    /// Counts from 1.
+    pub number: u32,
+    pub elapsed: chrono::Duration,
+}
+
+/// What should happen after an attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Next {
+    Deliver,
+    /// Sends the request again after the delay.
+    Retry(Duration),
+}
+
+pub trait Middleware {
+    /// Changes the request before it is sent, i.e. adds headers.
+    fn prepare(&self, _request: &mut ApiRequest) {}
+
+    /// Sees every finished attempt. If any middleware asks for it, the request is sent again,
+    /// after the longest delay asked for.
+    fn finished(&self, _attempt: &Attempt) -> Next {
+        Next::Deliver
+    }
+}
+
+/// A transport with middleware, applied in the order they were added.
+#[derive(Clone)]
+pub struct Pipeline {
+    transport: Rc<dyn Transport>,
+    middleware: Vec<Rc<dyn Middleware>>,
+}
+
+impl Pipeline {
+    pub fn new(transport: impl Transport + 'static) -> Self {
+        Self {
+            transport: Rc::new(transport),
+            middleware: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Rc::new(middleware));
+        self
+    }
+
+    fn attempt(self, request: ApiRequest, number: u32, done: Done) {
+        let started = chrono::Utc::now();
+        let transport = self.transport.clone();
+        transport.send(
+            copy_request(&request),
+            Box::new(move |outcome| {
+                let attempt = Attempt {
+                    request: &request,
+                    outcome: &outcome,
+                    number,
+                    elapsed: chrono::Utc::now() - started,
+                };
+                // all middleware sees the attempt, even if one already asked for a retry.
+                let next = self
+                    .middleware
+                    .iter()
+                    .map(|m| m.finished(&attempt))
+                    .fold(Next::Deliver, |next, wish| match (next, wish) {
+                        (Next::Retry(a), Next::Retry(b)) => Next::Retry(a.max(b)),
+                        (Next::Deliver, wish) => wish,
+                        (next, Next::Deliver) => next,
+                    });
+                match next {
+                    Next::Retry(delay) => {
+                        let transport = self.transport.clone();
+                        transport.wait(delay, Box::new(move || self.attempt(request, number + 1, done)))
+                    }
+                    Next::Deliver => done(outcome),
+                }
+            }),
+        );
+    }
+}
+
+impl Transport for Pipeline {
+    fn send(&self, mut request: ApiRequest, done: Done) {
+        for middleware in self.middleware.iter() {
+            middleware.prepare(&mut request);
+        }
+        self.clone().attempt(request, 1, done);
+    }
+}
+
+/// Unreachable services and gateway problems may go away, other errors will not.
+pub fn is_transient(outcome: &Outcome) -> bool {
+    match outcome {
+        Ok(response) => matches!(response.status().as_u16(), 502..=504),
+        Err(_) => true,
+    }
+}
+
+/// Why the request failed, if the failure is transient, so the request may succeed later.
+pub fn transient_failure(outcome: &Outcome) -> Option<String> {
+    match outcome {
+        Err(e) => Some(e.clone()),
+        Ok(response) if is_transient(outcome) => Some(format!("The service answered {}.", response.status())),
+        Ok(_) => None,
+    }
+}
+
+/// Starts the failures of requests which provably never reached the service.
+const NOT_SENT: &str = "Not sent: ";
+
+/// The failure of a request which could not be sent or whose connection failed,
+/// so the service did not see it. Transports report other failures, like timeouts, as they are.
+pub fn not_sent(reason: impl std::fmt::Display) -> String {
+    format!("{}{}", NOT_SENT, reason)
+}
+
+/// Why the request failed, if it provably never reached the service.
+/// Only these requests can be sent again without checking what the service did with them.
+pub fn unsent_failure(outcome: &Outcome) -> Option<String> {
+    match outcome {
+        Err(e) if e.starts_with(NOT_SENT) => Some(e.clone()),
+        _ => None,
+    }
+}
+
+/// Requests can not be cloned, as their body could be anything. Ours are text.
+pub fn copy_request(request: &ApiRequest) -> ApiRequest {
+    let mut copy = http::Request::new(request.body().clone());
+    *copy.method_mut() = request.method().clone();
+    *copy.uri_mut() = request.uri().clone();
+    *copy.version_mut() = request.version();
+    *copy.headers_mut() = request.headers().clone();
+    copy
+}
+
+/// The outcome of a request sent with a transport which answers before `send` returns,
+/// like `NativeTransport` and `Replay`.
+pub fn send_blocking(transport: &impl Transport, request: ApiRequest) -> Outcome {
+    let result = Rc::new(RefCell::new(None));
+    let slot = result.clone();
+    transport.send(request, Box::new(move |outcome| *slot.borrow_mut() = Some(outcome)));
+    let outcome = result.borrow_mut().take();
+    outcome.unwrap_or_else(|| Err("The transport did not answer synchronously.".into()))
+}
+
+/// Describes the request like `GET http://localhost/api/v1/pit/pid/x`.
+pub fn describe(request: &ApiRequest) -> String {
+    format!("{} {}", request.method(), request.uri())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(uri: &str) -> ApiRequest {
+        http::Request::get(uri).body(None).unwrap()
+    }
+
+    fn respond(status: u16) -> Exchange {
+        Exchange {
+            method: "GET".into(),
+            uri: "http://localhost/a".into(),
+            body: None,
+            status,
+            headers: Default::default(),
+            response: String::new(),
+        }
+    }
+
+    #[test]
+    fn middleware_prepares_requests_and_retries() {
+        let replay = Replay::new(vec![respond(503), respond(503), respond(200)]);
+        let timing = Timing::default();
+        let pipeline = Pipeline::new(replay.clone())
+            .with(Header::bearer("secret").unwrap())
+            .with(Retry::new(3))
+            .with(timing.clone());
+
+        let response = send_blocking(&pipeline, get("http://localhost/a")).unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(replay.sent().len(), 3);
+        assert_eq!(replay.last_headers().get("authorization").unwrap(), "Bearer secret");
+        let attempts: Vec<u32> = timing.records().iter().map(|t| t.attempt).collect();
+        assert_eq!(attempts, [1, 2, 3]);
+    }
+
+    #[test]
+    fn retries_back_off_exponentially() {
+        let replay = Replay::new(vec![respond(503), respond(504), respond(503), respond(200)]);
+        let pipeline = Pipeline::new(replay.clone()).with(Retry::new(4).with_backoff(Duration::from_millis(100)));
+        assert_eq!(send_blocking(&pipeline, get("http://localhost/a")).unwrap().status(), 200);
+        let waited: Vec<u128> = replay.waited().iter().map(Duration::as_millis).collect();
+        assert_eq!(waited, [100, 200, 400]);
+    }
+
+    #[test]
+    fn retries_stop_after_the_limit() {
+        let replay = Replay::new(vec![respond(503), respond(503), respond(200)]);
+        let pipeline = Pipeline::new(replay.clone()).with(Retry::new(2));
+        assert_eq!(send_blocking(&pipeline, get("http://localhost/a")).unwrap().status(), 503);
+        assert_eq!(replay.sent().len(), 2);
+    }
+
+    #[test]
+    fn registrations_are_not_retried() {
+        let replay = Replay::default();
+        let pipeline = Pipeline::new(replay.clone()).with(Retry::new(3));
+        let post = http::Request::post("http://localhost/a").body(Some("{}".into())).unwrap();
+        assert!(send_blocking(&pipeline, post).is_err());
+        assert_eq!(replay.sent(), ["POST http://localhost/a"]);
+    }
+
+    #[test]
+    fn only_unsent_requests_are_known_to_be_unseen() {
+        let unreachable: Outcome = Err(not_sent("connection refused"));
+        let timeout: Outcome = Err("POST http://localhost/a timed out after 10 s.".into());
+        let gateway: Outcome = Ok(http::Response::builder().status(504).body(String::new()).unwrap());
+        assert!(unsent_failure(&unreachable).is_some());
+        assert!(unsent_failure(&timeout).is_none());
+        assert!(unsent_failure(&gateway).is_none());
+        assert!([unreachable, timeout, gateway].iter().all(|o| transient_failure(o).is_some()));
+    }
+
+    #[test]
+    fn recordings_can_be_replayed() {
+        let original = Replay::new(vec![respond(200), respond(404)]);
+        let recording = Recording::new(original);
+        send_blocking(&recording, get("http://localhost/a")).unwrap();
+        send_blocking(&recording, get("http://localhost/a")).unwrap();
+        let json = recording.to_json();
+
+        let replay = Replay::from_json(&json).unwrap();
+        assert_eq!(send_blocking(&replay, get("http://localhost/a")).unwrap().status(), 200);
+        assert_eq!(send_blocking(&replay, get("http://localhost/a")).unwrap().status(), 404);
+        // every exchange answers once.
+        assert!(send_blocking(&replay, get("http://localhost/a")).is_err());
+        assert!(send_blocking(&replay, get("http://localhost/b")).is_err());
+    }
+}
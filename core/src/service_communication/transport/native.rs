@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use super::{not_sent, ApiRequest, Done, Outcome, Transport};
+
+/// Sends requests natively and blocks until the response arrived, so `done` is called
+/// before `send` returns.
+pub struct NativeTransport {
+    agent: ureq::Agent,
+}
+
+impl Default for NativeTransport {
+    fn default() -> Self {
+        Self { agent: ureq::Agent::new() }
+    }
+}
+
+impl NativeTransport {
+    /// Requests fail if they take longer than `timeout`.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(timeout).build(),
+        }
+    }
+
+    /// Errors before the request was written, i.e. the connection could not be established.
+    fn is_unsent(error: &ureq::Error) -> bool {
+        use ureq::ErrorKind::*;
+        matches!(
+            error.kind(),
+            InvalidUrl | UnknownScheme | Dns | InsecureRequestHttpsOnly | ConnectionFailed | InvalidProxyUrl | ProxyConnect
+        )
+    }
+
+    fn call(&self, request: &ApiRequest) -> Outcome {
+        let mut call = self.agent.request(request.method().as_str(), &request.uri().to_string());
+        for (name, value) in request.headers() {
+            call = call.set(name.as_str(), value.to_str().map_err(|e| e.to_string())?);
+        }
+        let result = match request.body() {
+            Some(body) => call.send_string(body),
+            None => call.call(),
+        };
+        let response = match result {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            // the error names the URI already.
+            Err(e) if Self::is_unsent(&e) => return Err(not_sent(format!("{} {}", request.method(), e))),
+            Err(e) => return Err(format!("{} {}", request.method(), e)),
+        };
+        let mut builder = http::Response::builder().status(response.status());
+        for name in response.headers_names() {
+            if let Some(value) = response.header(&name) {
+                builder = builder.header(name.as_str(), value);
+            }
+        }
+        let body = response.into_string().map_err(|e| e.to_string())?;
+        builder.body(body).map_err(|e| e.to_string())
+    }
+}
+
+impl Transport for NativeTransport {
+    fn send(&self, request: ApiRequest, done: Done) {
+        done(self.call(&request))
+    }
+
+    fn wait(&self, delay: Duration, then: Box<dyn FnOnce()>) {
+        std::thread::sleep(delay);
+        then()
+    }
+}
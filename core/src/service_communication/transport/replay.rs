@@ -0,0 +1,156 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+use super::{copy_request, describe, not_sent, ApiRequest, ApiResponse, Done, Outcome, Transport};
+
+/// A request and the response to it. Lists of them are stored as JSON.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Exchange {
+    pub method: String,
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    pub response: String,
+}
+
+impl Exchange {
+    pub fn new(request: &ApiRequest, response: &ApiResponse) -> Self {
+        Self {
+            method: request.method().to_string(),
+            uri: request.uri().to_string(),
+            body: request.body().clone(),
+            status: response.status().as_u16(),
+            headers: response
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+                .collect(),
+            response: response.body().clone(),
+        }
+    }
+
+    fn answers(&self, request: &ApiRequest) -> bool {
+        self.method == request.method().as_str() && self.uri == request.uri().to_string()
+    }
+
+    fn to_response(&self) -> Outcome {
+        let mut response = http::Response::builder().status(self.status);
+        for (name, value) in self.headers.iter() {
+            response = response.header(name.as_str(), value.as_str());
+        }
+        response.body(self.response.clone()).map_err(|e| e.to_string())
+    }
+}
+
+/// Answers requests with recorded exchanges of the same method and URI, each exchange once,
+/// in the order they were recorded. Requests without an exchange fail like an unreachable service.
+/// It does not wait, but remembers the delays. Clones share the exchanges.
+#[derive(Debug, Clone, Default)]
+pub struct Replay {
+    exchanges: Rc<RefCell<Vec<Exchange>>>,
+    sent: Rc<RefCell<Vec<(String, http::HeaderMap)>>>,
+    waited: Rc<RefCell<Vec<Duration>>>,
+}
+
+impl Replay {
+    pub fn new(exchanges: Vec<Exchange>) -> Self {
+        Self {
+            exchanges: Rc::new(RefCell::new(exchanges)),
+            ..Self::default()
+        }
+    }
+
+    pub fn from_json(exchanges: &str) -> Result<Self, String> {
+        json::from_str(exchanges)
+            .map(Self::new)
+            .map_err(|e| format!("Invalid recording: {}", e))
+    }
+
+    /// All requests sent to this transport, like `GET http://localhost/a`.
+    pub fn sent(&self) -> Vec<String> {
+        self.sent.borrow().iter().map(|(request, _)| request.clone()).collect()
+    }
+
+    /// The headers of the latest request.
+    pub fn last_headers(&self) -> http::HeaderMap {
+        self.sent.borrow().last().map(|(_, headers)| headers.clone()).unwrap_or_default()
+    }
+
+    /// The delays the transport was asked to wait.
+    pub fn waited(&self) -> Vec<Duration> {
+        self.waited.borrow().clone()
+    }
+
+    /// The exchanges which did not answer a request yet.
+    pub fn remaining(&self) -> Vec<Exchange> {
+        self.exchanges.borrow().clone()
+    }
+}
+
+impl Transport for Replay {
+    fn send(&self, request: ApiRequest, done: Done) {
+        self.sent.borrow_mut().push((describe(&request), request.headers().clone()));
+        let position = self.exchanges.borrow().iter().position(|e| e.answers(&request));
+        let outcome = match position {
+            Some(index) => self.exchanges.borrow_mut().remove(index).to_response(),
+            // like an unreachable service.
+            None => Err(not_sent(format!("No recorded response for {}.", describe(&request)))),
+        };
+        done(outcome);
+    }
+
+    fn wait(&self, delay: Duration, then: Box<dyn FnOnce()>) {
+        self.waited.borrow_mut().push(delay);
+        then()
+    }
+}
+
+/// Passes requests to another transport and records the exchanges, so they can be replayed.
+#[derive(Clone)]
+pub struct Recording<T> {
+    inner: T,
+    exchanges: Rc<RefCell<Vec<Exchange>>>,
+}
+
+impl<T: Transport> Recording<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            exchanges: Default::default(),
+        }
+    }
+
+    pub fn exchanges(&self) -> Vec<Exchange> {
+        self.exchanges.borrow().clone()
+    }
+
+    pub fn to_json(&self) -> String {
+        json::to_string_pretty(&*self.exchanges.borrow()).expect("Exchanges can always be serialized.")
+    }
+}
+
+impl<T: Transport> Transport for Recording<T> {
+    fn send(&self, request: ApiRequest, done: Done) {
+        let copy = copy_request(&request);
+        let exchanges = self.exchanges.clone();
+        self.inner.send(
+            request,
+            Box::new(move |outcome| {
+                // without a response, there is nothing to replay.
+                if let Ok(response) = &outcome {
+                    exchanges.borrow_mut().push(Exchange::new(&copy, response));
+                }
+                done(outcome)
+            }),
+        );
+    }
+
+    fn wait(&self, delay: Duration, then: Box<dyn FnOnce()>) {
+        self.inner.wait(delay, then)
+    }
+}
@@ -0,0 +1,109 @@
+use serde_json as json;
+
+use super::pit_record::PidRecord;
+use super::primitive_types as primitive;
+use super::RecordEntry;
+use crate::data_type_registry::{AttributeValue, Pid};
+use std::ops::{Deref, DerefMut};
+
+impl RecordEntry for primitive::Profile {
+    fn write(&self, record: &mut PidRecord) {
+        use primitive::Profile;
+        let id = "21.T11148/076759916209e5d62bd5".into();
+        let name = "profilePid".into();
+        let profile = match self {
+            Profile::Testbed4infSimplified | Profile::Testbed4infRaw => {
+                Pid("21.T11148/61fd3446879407065218".into())
+            }
+        };
+        record.add_value(id, name, AttributeValue::Pid(profile));
+    }
+}
+
+#[derive(Debug, Default)]
+pub enum DataType {
+    #[default]
+    Tiff,
+    Png,
+    Pid(primitive::Pid),
+}
+
+impl From<i32> for DataType {
+    fn from(index: i32) -> Self {
+        match index {
+            0 => Self::Tiff,
+            1 => Self::Png,
+            2 => Self::Pid(String::new()),
+            unknown => {
+                log::error!("Profile index '{}' not implemented.", unknown);
+                Self::default()
+            }
+        }
+    }
+}
+
+impl DataType {
+    pub fn as_value(&self) -> AttributeValue {
+        match self {
+            DataType::Tiff => AttributeValue::String("tiff".into()),
+            DataType::Png => AttributeValue::String("png".into()),
+            DataType::Pid(pid) => AttributeValue::Pid(Pid(pid.clone())),
+        }
+    }
+}
+
+impl RecordEntry for DataType {
+    fn write(&self, record: &mut PidRecord) {
+        let id = "21.T11148/c83481d4bf467110e7c9".into();
+        let name = "digitalObjectType".into();
+        record.add_value(id, name, self.as_value());
+    }
+}
+
+impl DataType {
+    pub fn write_str(record: &mut PidRecord, datatype: &str) {
+        let id = "21.T11148/c83481d4bf467110e7c9".into();
+        let name = "digitalObjectType".into();
+        record.add_value(id, name, AttributeValue::String(datatype.into()));
+    }
+}
+
+impl RecordEntry for primitive::Checksum {
+    fn write(&self, record: &mut PidRecord) {
+        let id = "21.T11148/92e200311a56800b3e47".into();
+        let name = "etag".into();
+        let value = json::json!({ "sha256sum": format!("sha256: {}", self.value) });
+        record.add_value(id, name, AttributeValue::Object(value));
+    }
+}
+
+impl RecordEntry for primitive::ObjectLocation {
+    fn write(&self, record: &mut PidRecord) {
+        Self::write_str(record, self.as_str())
+    }
+}
+
+impl primitive::ObjectLocation {
+    pub fn write_str(record: &mut PidRecord, location: &str) {
+        let id = "21.T11148/b8457812905b83046284".into();
+        let name = "digitalObjectLocation".into();
+        record.add_value(id, name, AttributeValue::Url(location.into()));
+    }
+}
+
+// Below this line are proper newtypes that wrap primitive types properly so they can be assigned different pids.
+// TODO all of the above should be below there. Wrap everything in newtypes (or type in cases like version).
+// Dates and the version are attribute types in `data_type_registry`, derived with `ProfileAttribute`.
+
+#[derive(Default)]
+pub struct LicenseString(String);
+
+impl RecordEntry for LicenseString {
+    fn write(&self, record: &mut PidRecord) {
+        let id = "21.T11148/dc54ae4b6807f5887fda".into();
+        let name = "license".into();
+        record.add_value(id, name, AttributeValue::Url(self.0.clone()));
+    }
+}
+
+newtype_deref!(LicenseString, String);
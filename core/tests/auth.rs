@@ -0,0 +1,69 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use chrono::Utc;
+use fairris_core::{
+    auth::{oidc, Authorization, Credentials},
+    service_communication::{pit_api, transport::Middleware, PidRecord},
+};
+use fairris_mock_services::oidc::{STATIC_TOKEN, SUBJECT};
+
+fn register(base: &str, authorization: &Authorization) -> u16 {
+    let mut request = pit_api::register_request(base, &PidRecord::default());
+    authorization.prepare(&mut request);
+    common::send(&request).status().as_u16()
+}
+
+#[test]
+fn secured_services_need_credentials() {
+    let server = common::start_secured();
+    let base = server.pit_base_uri();
+    assert_eq!(register(&base, &Authorization::default()), 401);
+    assert_eq!(register(&base, &Authorization::new(Credentials::Bearer("wrong".into()))), 401);
+    assert_eq!(register(&base, &Authorization::new(Credentials::Bearer(STATIC_TOKEN.into()))), 201);
+    let api_key = Credentials::ApiKey {
+        header: "X-API-Key".into(),
+        key: STATIC_TOKEN.into(),
+    };
+    assert_eq!(register(&base, &Authorization::new(api_key)), 201);
+}
+
+#[test]
+fn login_with_pkce_and_refresh() {
+    let server = common::start_secured();
+    let client = oidc::Client {
+        issuer: server.oidc_issuer(),
+        client_id: "fairris".into(),
+        redirect_uri: "http://localhost:8080/".into(),
+        scope: "openid".into(),
+    };
+    let provider = oidc::parse_provider(&common::send(&oidc::discovery_request(&client.issuer))).unwrap();
+    let (uri, pending) = oidc::start_login(&client, &provider);
+
+    // the mock approves immediately and redirects back with the code.
+    let redirect = common::send(&http::Request::get(uri).body(None).unwrap());
+    assert_eq!(redirect.status(), 302);
+    let location = redirect.headers()["Location"].to_str().unwrap();
+    let query = location.split_once('?').unwrap().1;
+    let code = oidc::parse_redirect(query, &pending).unwrap().unwrap();
+
+    let response = common::send(&oidc::token_request(&client, &pending, &code));
+    let session = oidc::parse_session(&response, &provider.token_endpoint, None, Utc::now()).unwrap();
+    assert_eq!(session.identity.subject, SUBJECT);
+    assert!(session.identity.contributor().is_some());
+    // codes can be used once.
+    assert_eq!(common::send(&oidc::token_request(&client, &pending, &code)).status(), 400);
+
+    let authorization = Authorization::new(Credentials::Bearer(session.access_token.clone()));
+    assert_eq!(register(&server.pit_base_uri(), &authorization), 201);
+
+    let response = common::send(&oidc::refresh_request(&client, &session).unwrap());
+    let refreshed = oidc::parse_session(&response, &provider.token_endpoint, Some(&session), Utc::now()).unwrap();
+    assert_ne!(refreshed.access_token, session.access_token);
+    assert_eq!(refreshed.identity, session.identity);
+    authorization.set(Credentials::Bearer(refreshed.access_token));
+    assert_eq!(register(&server.pit_base_uri(), &authorization), 201);
+    // refresh tokens can be used once.
+    assert_eq!(common::send(&oidc::refresh_request(&client, &session).unwrap()).status(), 400);
+}
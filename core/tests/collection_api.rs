@@ -0,0 +1,74 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use fairris_core::{
+    collection_service::{collection::Collection, member_item::Member},
+    service_communication::collection_api,
+};
+use serde_json::json;
+
+fn collection(description: &str) -> Collection {
+    let mut collection = Collection::default();
+    collection.description = Some(description.into());
+    collection
+}
+
+#[test]
+fn register_pull_and_push_with_etags() {
+    let server = common::start();
+    let base = server.collection_base_uri();
+
+    let request = collection_api::create_collections_request(&base, &[collection("a"), collection("b")]);
+    let created = collection_api::parse_collections(&common::send(&request)).unwrap();
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0].description.as_deref(), Some("a"));
+    let id = created[0].get_id().expect("Registered collections have an id.");
+
+    let pulled = common::send(&collection_api::pull_collection_request(&base, id));
+    let (mut pulled, etag) = collection_api::parse_collection(&pulled).unwrap();
+    assert_eq!(pulled, created[0]);
+    let etag = etag.expect("Collections have an ETag.");
+
+    pulled.description = Some("changed".into());
+    let request = collection_api::push_collection_request(&base, &pulled, &etag).unwrap();
+    let (pushed, new_etag) = collection_api::parse_collection(&common::send(&request)).unwrap();
+    assert_eq!(pushed, pulled);
+    assert_ne!(new_etag, Some(etag.clone()));
+
+    // the old ETag is outdated now.
+    let request = collection_api::push_collection_request(&base, &pulled, &etag).unwrap();
+    let response = common::send(&request);
+    assert_eq!(response.status(), 412);
+    assert!(collection_api::parse_collection(&response).is_err());
+}
+
+#[test]
+fn unregistered_collections_can_not_be_pushed() {
+    let etag = http::HeaderValue::from_static("\"0\"");
+    assert!(collection_api::push_collection_request("http://localhost", &collection("a"), &etag).is_err());
+}
+
+#[test]
+fn add_and_read_members() {
+    let server = common::start();
+    let base = server.collection_base_uri();
+
+    let request = collection_api::create_collections_request(&base, &[collection("a")]);
+    let created = collection_api::parse_collections(&common::send(&request)).unwrap();
+    let id = created[0].get_id().unwrap();
+
+    let members: Vec<Member> = serde_json::from_value(json!([
+        { "location": "https://example.org/1", "datatype": "image/tiff" },
+        { "location": "https://example.org/2", "datatype": "image/tiff" },
+    ]))
+    .unwrap();
+    let response = common::send(&collection_api::add_members_request(&base, id, &members));
+    assert!(response.status().is_success());
+
+    let fetched = collection_api::parse_members(&common::send(&collection_api::members_request(&base, id))).unwrap();
+    assert_eq!(serde_json::to_value(&fetched).unwrap(), serde_json::to_value(&members).unwrap());
+
+    let response = common::send(&collection_api::members_request(&base, "unknown"));
+    assert!(collection_api::parse_members(&response).is_err());
+}
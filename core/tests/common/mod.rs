@@ -0,0 +1,60 @@
+//! Sends API requests natively to the mock services.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+};
+
+use fairris_core::service_communication::{ApiRequest, ApiResponse};
+use fairris_mock_services::MockServer;
+
+#[allow(dead_code)]
+pub fn start() -> MockServer {
+    MockServer::start("127.0.0.1:0").expect("Could not start mock services.")
+}
+
+/// Mock services which require a token.
+#[allow(dead_code)]
+pub fn start_secured() -> MockServer {
+    MockServer::start_secured("127.0.0.1:0").expect("Could not start mock services.")
+}
+
+/// A minimal HTTP/1.1 client. The mock closes the connection after each response.
+pub fn send(request: &ApiRequest) -> ApiResponse {
+    let uri = request.uri();
+    let authority = uri.authority().expect("Requests need an absolute URI.").as_str();
+    let mut stream = TcpStream::connect(authority).expect("Could not connect to mock services.");
+
+    let body = request.body().clone().unwrap_or_default();
+    let target = uri.path_and_query().map_or("/", |target| target.as_str());
+    let mut head = format!("{} {} HTTP/1.1\r\nHost: {}\r\n", request.method(), target, authority);
+    for (name, value) in request.headers() {
+        head.push_str(&format!("{}: {}\r\n", name, value.to_str().expect("Header is not ASCII.")));
+    }
+    head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", body.len()));
+    stream.write_all(head.as_bytes()).unwrap();
+    stream.write_all(body.as_bytes()).unwrap();
+
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let status: u16 = line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .expect("Response has no status.");
+    let mut response = http::Response::builder().status(status);
+    loop {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            response = response.header(name.trim(), value.trim());
+        }
+    }
+    let mut body = String::new();
+    reader.read_to_string(&mut body).unwrap();
+    response.body(body).unwrap()
+}
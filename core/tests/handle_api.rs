@@ -0,0 +1,74 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use fairris_core::{
+    pidinfo::PidInfo,
+    service_communication::{handle_api, pit_api, PidRecord},
+};
+
+fn record(version: &str) -> PidRecord {
+    let mut record = PidRecord::default();
+    record.add_attribute(
+        "21.T11148/c692273deb2772da307f".into(),
+        "version".into(),
+        version.into(),
+    );
+    record.add_attribute(
+        "21.T11148/b8457812905b83046284".into(),
+        "digitalObjectLocation".into(),
+        "https://example.org/a".into(),
+    );
+    record
+}
+
+#[test]
+fn pit_records_resolve_as_handles() {
+    let server = common::start();
+    let response = common::send(&pit_api::register_request(&server.pit_base_uri(), &record("1.0.0")));
+    let registered = pit_api::parse_record(&response).unwrap();
+
+    let response = common::send(&handle_api::resolve_request(&server.handle_base_uri(), &registered.pid));
+    let resolved = handle_api::parse_record(&response).unwrap();
+    assert_eq!(resolved.pid, registered.pid);
+    assert!(resolved.diff(&registered).is_empty());
+    // reading it into the model restores the names.
+    let normalized = PidInfo::from_registered(resolved).as_record();
+    assert_eq!(normalized.entries["21.T11148/c692273deb2772da307f"][0].name, "version");
+}
+
+#[test]
+fn handles_written_elsewhere_can_be_compared() {
+    let server = common::start();
+    let mut original = record("1.0.0");
+    original.pid = "21.T11148/elsewhere".into();
+    let response = common::send(&handle_api::update_request(&server.handle_base_uri(), &original, &[]));
+    assert_eq!(response.status(), 201);
+
+    let response = common::send(&handle_api::resolve_request(&server.handle_base_uri(), &original.pid));
+    let resolved = handle_api::parse_record(&response).unwrap();
+    let diff = record("2.0.0").diff(&resolved);
+    assert_eq!(diff.len(), 1);
+    assert_eq!(diff[0].right, ["1.0.0"]);
+
+    let response = common::send(&handle_api::resolve_request(&server.handle_base_uri(), "21.T11148/missing"));
+    assert!(handle_api::parse_record(&response).is_err());
+}
+
+#[test]
+fn handles_with_special_characters_are_written_and_resolved() {
+    let server = common::start();
+    let mut original = record("1.0.0");
+    original.pid = "21.T11148/with space?#%".into();
+    let response = common::send(&handle_api::update_request(&server.handle_base_uri(), &original, &[]));
+    assert_eq!(response.status(), 201);
+
+    let response = common::send(&handle_api::resolve_request(&server.handle_base_uri(), &original.pid));
+    let (handle, existing) = handle_api::parse_values(&response).unwrap();
+    assert_eq!(handle, original.pid);
+    assert!(existing.iter().any(|v| v.value_type == "HS_ADMIN"));
+    let mut updated = record("2.0.0");
+    updated.pid = original.pid.clone();
+    let response = common::send(&handle_api::update_request(&server.handle_base_uri(), &updated, &existing));
+    assert_eq!(response.status(), 200);
+}
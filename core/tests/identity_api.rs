@@ -0,0 +1,31 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use fairris_core::{data_type_registry::Identifier, service_communication::identity_api};
+
+#[test]
+fn people_and_organizations_are_looked_up() {
+    let server = common::start();
+    let base = server.identity_lookup_uri();
+    let lookup = |identifier: &Identifier| {
+        let request = identity_api::lookup_request(&base, identifier).expect("ORCIDs and RORs can be looked up.");
+        identity_api::parse_name(&common::send(&request))
+    };
+
+    let user = Identifier::parse(fairris_mock_services::oidc::SUBJECT).unwrap();
+    assert_eq!(lookup(&user), Ok(fairris_mock_services::oidc::NAME.to_string()));
+    let organization = Identifier::parse("https://ror.org/05gq02987").unwrap();
+    assert_eq!(lookup(&organization), Ok("Brown University".to_string()));
+
+    let unknown = Identifier::parse("0000-0001-5109-3700").unwrap();
+    assert!(lookup(&unknown).is_err());
+    let request = http::Request::put(format!("{}/orcid/0000-0001-5109-3700", base))
+        .header("Content-Type", "application/json")
+        .body(Some(r#"{ "name": "Jane Doe" }"#.to_string()))
+        .unwrap();
+    assert_eq!(common::send(&request).status(), 204);
+    assert_eq!(lookup(&unknown), Ok("Jane Doe".to_string()));
+
+    assert!(identity_api::lookup_request(&base, &Identifier::Other("someone".into())).is_none());
+}
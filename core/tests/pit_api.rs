@@ -0,0 +1,52 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use fairris_core::service_communication::{pit_api, PidRecord};
+
+fn record(version: &str) -> PidRecord {
+    let mut record = PidRecord::default();
+    record.add_attribute(
+        "21.T11148/c692273deb2772da307f".into(),
+        "version".into(),
+        version.into(),
+    );
+    record
+}
+
+#[test]
+fn register_resolve_and_update() {
+    let server = common::start();
+    let base = server.pit_base_uri();
+
+    let response = common::send(&pit_api::register_request(&base, &record("1.0.0")));
+    assert_eq!(response.status(), 201);
+    assert!(response.headers().contains_key("ETag"));
+    let mut registered = pit_api::parse_record(&response).unwrap();
+    assert!(!registered.pid.is_empty());
+    assert!(registered.same_content_like(&record("1.0.0")));
+
+    let resolved = pit_api::parse_record(&common::send(&pit_api::resolve_request(&base, &registered.pid))).unwrap();
+    assert_eq!(resolved, registered);
+    assert!(resolved.same_content_like(&registered));
+
+    registered.entries = record("2.0.0").entries;
+    let updated = pit_api::parse_record(&common::send(&pit_api::update_request(&base, &registered))).unwrap();
+    assert_eq!(updated.pid, registered.pid);
+    let resolved = pit_api::parse_record(&common::send(&pit_api::resolve_request(&base, &registered.pid))).unwrap();
+    assert!(resolved.same_content_like(&record("2.0.0")));
+}
+
+#[test]
+fn unknown_pids_are_errors() {
+    let server = common::start();
+    let base = server.pit_base_uri();
+
+    let response = common::send(&pit_api::resolve_request(&base, "sandboxed/unknown"));
+    assert_eq!(response.status(), 404);
+    assert!(pit_api::parse_record(&response).is_err());
+
+    let mut unknown = record("1.0.0");
+    unknown.pid = "sandboxed/unknown".into();
+    assert!(pit_api::parse_record(&common::send(&pit_api::update_request(&base, &unknown))).is_err());
+}
@@ -0,0 +1,70 @@
+use fairris_core::{
+    data_type_registry::{HasProfileKey, Pid, ProfileAttribute, ValueError, ValueKind},
+    service_communication::PidRecord,
+};
+use serde_json as json;
+
+/// Keywords of the object, one entry each.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/keyword", name = "keyword", repeatable)]
+struct Keywords(Vec<String>);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/reviewed", name = "reviewed")]
+struct Reviewed(bool);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/derivedFrom", name = "derivedFrom")]
+struct DerivedFrom(Option<Pid>);
+
+/// A PID, which is kept as text.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+#[attribute(key = "21.T11148/source", name = "source", kind = "Pid")]
+struct Source(String);
+
+#[test]
+fn derived_attributes_roundtrip_through_a_record() {
+    let keywords = Keywords(vec!["manuscript".into(), "1.0".into(), "page".into()]);
+    let mut record = PidRecord::default();
+    keywords.write(&mut record);
+    Reviewed(true).write(&mut record);
+    DerivedFrom(Some(Pid("21.T11148/original".into()))).write(&mut record);
+    DerivedFrom(None).write(&mut record);
+    Source("21.T11148/source".into()).write(&mut record);
+
+    assert_eq!(Keywords::get_key(), Pid("21.T11148/keyword".into()));
+    assert_eq!(Keywords::get_key_name(), "keyword");
+    assert_eq!(record.entries[&Keywords::get_key().0].len(), 3);
+    assert_eq!(record.entries[&DerivedFrom::get_key().0].len(), 1);
+    assert_eq!(record.entries[&Reviewed::get_key().0][0].value, json::json!("true"));
+
+    assert_eq!(Keywords::from(&record), keywords);
+    assert_eq!(Reviewed::from(&record), Reviewed(true));
+    assert_eq!(DerivedFrom::from(&record), DerivedFrom(Some(Pid("21.T11148/original".into()))));
+    assert_eq!(Source::from(&record), Source("21.T11148/source".into()));
+    assert_eq!(Keywords::invalid_value(&record), None);
+
+    let empty = PidRecord::default();
+    assert_eq!(Keywords::from(&empty), Keywords::default());
+    assert_eq!(DerivedFrom::from(&empty), DerivedFrom(None));
+}
+
+#[test]
+fn invalid_values_are_reported() {
+    let mut record = PidRecord::default();
+    record.add_attribute(Reviewed::get_key().0, "reviewed".into(), "maybe".into());
+    record.add_attribute(Keywords::get_key().0, "keyword".into(), "page".into());
+    record.add_attribute(Keywords::get_key().0, "keyword".into(), json::Value::Null);
+    record.add_attribute(Source::get_key().0, "source".into(), json::json!(7));
+
+    assert_eq!(Reviewed::from(&record), Reviewed::default());
+    assert_eq!(
+        Reviewed::invalid_value(&record),
+        Some(ValueError::Mismatch(ValueKind::Boolean, json::json!("maybe")))
+    );
+    // invalid entries of repeatable attributes are left out.
+    assert_eq!(Keywords::from(&record), Keywords(vec!["page".into()]));
+    assert!(Keywords::invalid_value(&record).is_some());
+    // read as PID, not as string.
+    assert!(Source::parse_entry(&record.entries[&Source::get_key().0][0]).is_err());
+}
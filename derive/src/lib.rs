@@ -0,0 +1,252 @@
+//! Derive macros for the attribute types of `fairris-core`.
+//!
+//! `#[derive(ProfileAttribute)]` implements `HasProfileKey`, `From<&PidRecordEntry>` and
+//! `From<&PidRecord>` for a newtype around an `AttributeType`, as well as `parse_entry`
+//! and `invalid_value` for fallible parsing:
+//!
+//! ```ignore
+//! /// The PID of the object replacing this one.
+//! #[derive(Debug, Clone, Default, PartialEq, Eq, ProfileAttribute)]
+//! #[attribute(key = "21.T11148/supersededBy", name = "supersededBy")]
+//! pub struct SupersededBy(pub Option<Pid>);
+//! ```
+//!
+//! The field decides how often the attribute occurs in a record:
+//! - `T`: once. A missing or invalid value is the default.
+//! - `Option<T>`: at most once. Nothing is written for `None`.
+//! - `Vec<T>` with `repeatable`: once per element, in order. An entry may also contain
+//!   several values, see `AttributeType::from_values`.
+//!
+//! `kind = "Pid"` reads values as another `ValueKind` than the one of `T`,
+//! i.e. PIDs which are kept as `String`.
+//!
+//! Attributes which are enums (profile, type, status) or span several keys (locations)
+//! implement the traits by hand.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, DeriveInput, Lit, Meta, NestedMeta};
+
+#[proc_macro_derive(ProfileAttribute, attributes(attribute))]
+pub fn derive_profile_attribute(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// The content of `#[attribute(...)]`.
+struct Options {
+    key: String,
+    name: String,
+    repeatable: bool,
+    kind: Option<syn::Ident>,
+}
+
+/// How often the attribute occurs in a record, with the type of one value.
+enum Occurrence<'a> {
+    Once(&'a syn::Type),
+    Optional(&'a syn::Type),
+    Repeatable(&'a syn::Type),
+}
+
+fn parse_options(input: &DeriveInput) -> syn::Result<Options> {
+    let attribute = input
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident("attribute"))
+        .ok_or_else(|| syn::Error::new(input.ident.span(), "Missing #[attribute(key = \"...\", name = \"...\")]."))?;
+    let list = match attribute.parse_meta()? {
+        Meta::List(list) => list,
+        other => return Err(syn::Error::new(other.span(), "Expected #[attribute(key = \"...\", name = \"...\")].")),
+    };
+    let (mut key, mut name, mut repeatable, mut kind) = (None, None, false, None);
+    for nested in list.nested.iter() {
+        match nested {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("repeatable") => repeatable = true,
+            NestedMeta::Meta(Meta::NameValue(pair)) => {
+                let value = match &pair.lit {
+                    Lit::Str(s) => s,
+                    other => return Err(syn::Error::new(other.span(), "Expected a string.")),
+                };
+                if pair.path.is_ident("key") {
+                    key = Some(value.value());
+                } else if pair.path.is_ident("name") {
+                    name = Some(value.value());
+                } else if pair.path.is_ident("kind") {
+                    kind = Some(syn::Ident::new(&value.value(), value.span()));
+                } else {
+                    return Err(syn::Error::new(pair.path.span(), "Unknown option. Use key, name, kind or repeatable."));
+                }
+            }
+            other => return Err(syn::Error::new(other.span(), "Unknown option. Use key, name, kind or repeatable.")),
+        }
+    }
+    let missing = |option: &str| syn::Error::new(list.span(), format!("The attribute needs a {}.", option));
+    Ok(Options {
+        key: key.ok_or_else(|| missing("key"))?,
+        name: name.ok_or_else(|| missing("name"))?,
+        repeatable,
+        kind,
+    })
+}
+
+/// The type argument of `Option<T>` or `Vec<T>`.
+fn type_argument<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != wrapper {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.first()? {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn occurrence<'a>(input: &'a DeriveInput, options: &Options) -> syn::Result<Occurrence<'a>> {
+    let field = match &input.data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields.unnamed.first(),
+            _ => None,
+        },
+        _ => None,
+    }
+    .ok_or_else(|| syn::Error::new(input.ident.span(), "Attributes must be a newtype, i.e. `struct Version(pub String);`."))?;
+    if options.repeatable {
+        type_argument(&field.ty, "Vec")
+            .map(Occurrence::Repeatable)
+            .ok_or_else(|| syn::Error::new(field.ty.span(), "Repeatable attributes must contain a Vec."))
+    } else if let Some(inner) = type_argument(&field.ty, "Option") {
+        Ok(Occurrence::Optional(inner))
+    } else {
+        Ok(Occurrence::Once(&field.ty))
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let options = parse_options(input)?;
+    let occurrence = occurrence(input, &options)?;
+    let ident = &input.ident;
+    let (key, name) = (&options.key, &options.name);
+    let registry = quote!(::fairris_core::data_type_registry);
+    let record = quote!(::fairris_core::service_communication::PidRecord);
+    let entry = quote!(::fairris_core::service_communication::pit_record::PidRecordEntry);
+
+    let value_type = match occurrence {
+        Occurrence::Once(ty) | Occurrence::Optional(ty) | Occurrence::Repeatable(ty) => ty,
+    };
+    let kind = match &options.kind {
+        Some(kind) => quote!(#registry::ValueKind::#kind),
+        None => quote!(<#value_type as #registry::AttributeType>::KIND),
+    };
+    let add = |value: TokenStream2| {
+        quote! {
+            record.add_value(
+                #key.into(),
+                #name.into(),
+                #registry::AttributeType::to_value(#value),
+            );
+        }
+    };
+    let write = match occurrence {
+        Occurrence::Once(_) => add(quote!(&self.0)),
+        Occurrence::Optional(_) => {
+            let add = add(quote!(value));
+            quote!(if let Some(value) = &self.0 { #add })
+        }
+        Occurrence::Repeatable(_) => {
+            let add = add(quote!(value));
+            quote!(for value in self.0.iter() { #add })
+        }
+    };
+    let parse = match occurrence {
+        Occurrence::Once(_) => quote!(|value| <#value_type as #registry::AttributeType>::from_value(value).map(#ident)),
+        Occurrence::Optional(_) => {
+            quote!(|value| <#value_type as #registry::AttributeType>::from_value(value).map(|value| #ident(Some(value))))
+        }
+        // one entry may contain several values.
+        Occurrence::Repeatable(_) => quote!(|value| <#value_type as #registry::AttributeType>::from_values(value).map(#ident)),
+    };
+    let from_record = match occurrence {
+        Occurrence::Once(_) | Occurrence::Optional(_) => quote! {
+            record
+                .entries
+                .get(#key)
+                .and_then(|list| list.first())
+                .map(Self::from)
+                .unwrap_or_default()
+        },
+        // invalid values are left out.
+        Occurrence::Repeatable(_) => quote! {
+            #ident(
+                record
+                    .entries
+                    .get(#key)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entry| Self::parse_entry(entry).ok())
+                    .flat_map(|attribute| attribute.0)
+                    .collect(),
+            )
+        },
+    };
+
+    Ok(quote! {
+        impl #registry::HasProfileKey for #ident {
+            fn get_key() -> #registry::Pid {
+                #registry::Pid(#key.into())
+            }
+
+            fn get_key_name() -> &'static str {
+                #name
+            }
+
+            fn write(&self, record: &mut #record) {
+                #write
+            }
+        }
+
+        impl #ident {
+            /// Reads the value of one entry of the record.
+            pub fn parse_entry(entry: &#entry) -> Result<Self, #registry::ValueError> {
+                entry
+                    .decode(#kind)
+                    .and_then(#parse)
+            }
+
+            /// The error, if the record contains a value which can not be parsed.
+            pub fn invalid_value(record: &#record) -> Option<#registry::ValueError> {
+                record
+                    .entries
+                    .get(#key)
+                    .into_iter()
+                    .flatten()
+                    .find_map(|entry| Self::parse_entry(entry).err())
+            }
+        }
+
+        /// Invalid values are replaced by the default. Use `invalid_value`
+        /// to tell the user about it.
+        impl From<&#entry> for #ident {
+            fn from(entry: &#entry) -> Self {
+                Self::parse_entry(entry).unwrap_or_else(|e| {
+                    ::log::error!("The {} could not be read: {}", #name, e);
+                    Self::default()
+                })
+            }
+        }
+
+        impl From<&#record> for #ident {
+            fn from(record: &#record) -> Self {
+                #from_record
+            }
+        }
+    })
+}
@@ -0,0 +1,127 @@
+//! The collection and member endpoints of the RDA collection API.
+
+use serde_json::{json, Value};
+
+use crate::{
+    http::{Request, Response},
+    store::{etag, precondition, Store},
+};
+
+pub const BASE: &str = "/api/v1/collections";
+
+pub fn handle(store: &mut Store, request: &Request, path: &str) -> Response {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", []) => Response::json(200, &page(store.collections.values().cloned().collect())),
+        ("POST", []) => create(store, request),
+        ("GET", [id]) => match store.collections.get(*id) {
+            Some(collection) => Response::json(200, collection).with_etag(etag(collection)),
+            None => not_found(id),
+        },
+        ("PUT", [id]) => update(store, request, id),
+        ("DELETE", [id]) => delete(store, request, id),
+        ("GET", [id, "members"]) => match store.members.get(*id) {
+            Some(members) => Response::json(200, &page(members.clone())),
+            None => not_found(id),
+        },
+        ("POST", [id, "members"]) => add_members(store, request, id),
+        _ => Response::error(405, "Method not allowed for this resource."),
+    }
+}
+
+/// Lists are returned as a single page.
+fn page(contents: Vec<Value>) -> Value {
+    json!({ "contents": contents })
+}
+
+fn not_found(id: &str) -> Response {
+    Response::error(404, &format!("Collection {} does not exist.", id))
+}
+
+/// Accepts a single object or an array of objects.
+fn read_objects(request: &Request) -> Result<Vec<Value>, Response> {
+    let objects = match request.json()? {
+        Value::Array(objects) => objects,
+        object => vec![object],
+    };
+    if objects.iter().all(Value::is_object) {
+        Ok(objects)
+    } else {
+        Err(Response::error(400, "Expected an object or an array of objects."))
+    }
+}
+
+fn create(store: &mut Store, request: &Request) -> Response {
+    let collections = match read_objects(request) {
+        Ok(collections) => collections,
+        Err(response) => return response,
+    };
+    let mut created = Vec::new();
+    for mut collection in collections {
+        let id = match collection.get("id").and_then(Value::as_str) {
+            Some(id) if !id.is_empty() && !store.collections.contains_key(id) => id.to_owned(),
+            _ => format!("collection-{}", store.next_id()),
+        };
+        collection["id"] = Value::String(id.clone());
+        store.collections.insert(id.clone(), collection.clone());
+        store.members.insert(id, Vec::new());
+        created.push(collection);
+    }
+    Response::json(201, &Value::Array(created))
+}
+
+fn update(store: &mut Store, request: &Request, id: &str) -> Response {
+    let current = match store.collections.get(id) {
+        Some(collection) => collection,
+        None => return not_found(id),
+    };
+    if let Err(status) = precondition(request.header("If-Match"), current, true) {
+        return Response::error(status, "Updates need the current ETag in If-Match.");
+    }
+    let mut collection = match request.json() {
+        Ok(collection) if collection.is_object() => collection,
+        Ok(_) => return Response::error(400, "Expected a collection object."),
+        Err(response) => return response,
+    };
+    collection["id"] = Value::String(id.to_owned());
+    let response = Response::json(200, &collection).with_etag(etag(&collection));
+    store.collections.insert(id.to_owned(), collection);
+    response
+}
+
+fn delete(store: &mut Store, request: &Request, id: &str) -> Response {
+    let current = match store.collections.get(id) {
+        Some(collection) => collection,
+        None => return not_found(id),
+    };
+    if let Err(status) = precondition(request.header("If-Match"), current, false) {
+        return Response::error(status, "The collection was changed in the meantime.");
+    }
+    store.collections.remove(id);
+    store.members.remove(id);
+    Response::empty(204)
+}
+
+fn add_members(store: &mut Store, request: &Request, id: &str) -> Response {
+    if !store.members.contains_key(id) {
+        return not_found(id);
+    }
+    let members = match read_objects(request) {
+        Ok(members) => members,
+        Err(response) => return response,
+    };
+    let mut added = Vec::new();
+    for mut member in members {
+        let has_id = matches!(member.get("id").and_then(Value::as_str), Some(id) if !id.is_empty());
+        if !has_id {
+            member["id"] = Value::String(format!("member-{}", store.next_id()));
+        }
+        added.push(member);
+    }
+    store
+        .members
+        .get_mut(id)
+        .expect("Existence was checked before.")
+        .extend(added.iter().cloned());
+    Response::json(201, &Value::Array(added))
+}
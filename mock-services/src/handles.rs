@@ -0,0 +1,84 @@
+//! The `api/handles` endpoints of the Handle System REST API.
+//!
+//! They serve the records of the PIT mock as handle values, as a PIT service
+//! backed by a Handle server would. Handles written here are visible to the PIT mock as well.
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    http::{Request, Response},
+    oidc,
+    store::Store,
+};
+
+pub const BASE: &str = "/api/handles";
+
+pub fn handle(store: &mut Store, request: &Request, handle: &str) -> Response {
+    match (request.method.as_str(), handle) {
+        ("GET", handle) if !handle.is_empty() => resolve(store, handle),
+        ("PUT", handle) if !handle.is_empty() => {
+            if store.secured && !oidc::authorized(store, request) {
+                return Response::error(401, "A valid token is required.");
+            }
+            write(store, request, handle)
+        }
+        _ => Response::error(405, "Method not allowed for this resource."),
+    }
+}
+
+fn resolve(store: &Store, handle: &str) -> Response {
+    let record = match store.records.get(handle) {
+        Some(record) => record,
+        None => return Response::json(404, &json!({ "responseCode": 100, "handle": handle })),
+    };
+    let mut keys: Vec<&String> = record["entries"].as_object().map(|e| e.keys().collect()).unwrap_or_default();
+    keys.sort();
+    let mut values: Vec<Value> = keys
+        .into_iter()
+        .flat_map(|key| record["entries"][key].as_array().cloned().unwrap_or_default())
+        // like the client, the indices of URL and HS_ADMIN are left free.
+        .zip((2..).filter(|index| *index != 100))
+        .map(|(entry, index): (Value, u32)| {
+            let value = match &entry["value"] {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            json!({ "index": index, "type": entry["key"], "data": { "format": "string", "value": value } })
+        })
+        .collect();
+    values.push(json!({
+        "index": 100,
+        "type": "HS_ADMIN",
+        "data": { "format": "admin", "value": { "handle": "0.NA/sandboxed", "index": 200, "permissions": "011111110011" } },
+    }));
+    Response::json(200, &json!({ "responseCode": 1, "handle": handle, "values": values }))
+}
+
+/// Replaces the handle with the given values, keeping those with a PID as type.
+fn write(store: &mut Store, request: &Request, handle: &str) -> Response {
+    let body = match request.json() {
+        Ok(body) => body,
+        Err(response) => return response,
+    };
+    let values = match body["values"].as_array() {
+        Some(values) => values,
+        None => return Response::json(400, &json!({ "responseCode": 2, "message": "Expected a list of values." })),
+    };
+    let mut entries = Map::new();
+    for value in values {
+        let value_type = value["type"].as_str().unwrap_or_default();
+        if !value_type.contains('/') {
+            continue;
+        }
+        let entry = json!({ "key": value_type, "name": value_type, "value": value["data"]["value"] });
+        entries
+            .entry(value_type)
+            .or_insert_with(|| Value::Array(Vec::new()))
+            .as_array_mut()
+            .expect("Entries are lists.")
+            .push(entry);
+    }
+    let record = json!({ "pid": handle, "entries": entries });
+    let created = store.records.insert(handle.to_owned(), record).is_none();
+    Response::json(if created { 201 } else { 200 }, &json!({ "responseCode": 1, "handle": handle }))
+}
@@ -0,0 +1,199 @@
+//! Just enough HTTP/1.1 to talk to the client and the integration tests.
+//! Every connection carries a single request and is closed after the response.
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+};
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, Default)]
+pub struct Request {
+    pub method: String,
+    /// The path without query.
+    pub path: String,
+    /// The query without `?`, still encoded.
+    pub query: String,
+    /// Header names are lowercase.
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn read(stream: &mut impl BufRead) -> io::Result<Self> {
+        let mut line = String::new();
+        stream.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_uppercase();
+        let target = parts.next().unwrap_or_default();
+        let mut target = target.splitn(2, '?');
+        let path = target.next().unwrap_or_default().to_owned();
+        let query = target.next().unwrap_or_default().to_owned();
+
+        let mut headers = HashMap::new();
+        loop {
+            line.clear();
+            if stream.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+            }
+        }
+
+        let length = headers
+            .get("content-length")
+            .and_then(|l| l.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        stream.read_exact(&mut body)?;
+        let body = String::from_utf8(body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self {
+            method,
+            path,
+            query,
+            headers,
+            body,
+        })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    /// A parameter of the query.
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        form_decode(&self.query).remove(name)
+    }
+
+    /// Parses a form (`application/x-www-form-urlencoded`) body.
+    pub fn form(&self) -> HashMap<String, String> {
+        form_decode(&self.body)
+    }
+
+    /// Parses the body, or answers with "400 Bad Request".
+    pub fn json(&self) -> Result<Value, Response> {
+        serde_json::from_str(&self.body)
+            .map_err(|e| Response::error(400, &format!("Body is not valid JSON: {}", e)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn empty(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    pub fn json(status: u16, value: &Value) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".into(), "application/json".into())],
+            body: value.to_string(),
+        }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".into(), "text/plain".into())],
+            body: message.to_owned(),
+        }
+    }
+
+    pub fn with_etag(mut self, etag: String) -> Self {
+        self.headers.push(("ETag".into(), etag));
+        self
+    }
+
+    pub fn redirect(location: &str) -> Self {
+        Self {
+            status: 302,
+            headers: vec![("Location".into(), location.to_owned())],
+            body: String::new(),
+        }
+    }
+
+    pub fn write(&self, stream: &mut impl Write) -> io::Result<()> {
+        write!(stream, "HTTP/1.1 {} {}\r\n", self.status, reason(self.status))?;
+        for (name, value) in self.headers.iter() {
+            write!(stream, "{}: {}\r\n", name, value)?;
+        }
+        // the client is served from another origin during development.
+        write!(stream, "Access-Control-Allow-Origin: *\r\n")?;
+        write!(stream, "Access-Control-Allow-Methods: GET, POST, PUT, DELETE, HEAD, OPTIONS\r\n")?;
+        write!(stream, "Access-Control-Allow-Headers: Content-Type, If-Match, Authorization, X-API-Key\r\n")?;
+        write!(stream, "Access-Control-Expose-Headers: ETag\r\n")?;
+        write!(stream, "Content-Length: {}\r\n", self.body.len())?;
+        write!(stream, "Connection: close\r\n\r\n")?;
+        stream.write_all(self.body.as_bytes())?;
+        stream.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        302 => "Found",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        412 => "Precondition Failed",
+        428 => "Precondition Required",
+        _ => "Unknown",
+    }
+}
+
+/// Decodes `a=1&b=x%20y`. Invalid escapes are kept as they are.
+pub fn form_decode(input: &str) -> HashMap<String, String> {
+    input
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut pair = pair.splitn(2, '=');
+            let name = percent_decode(pair.next().unwrap_or_default(), true);
+            (name, percent_decode(pair.next().unwrap_or_default(), true))
+        })
+        .collect()
+}
+
+/// Decodes a path segment, where `+` is no space.
+pub fn path_decode(input: &str) -> String {
+    percent_decode(input, false)
+}
+
+fn percent_decode(input: &str, plus_is_space: bool) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[i], escaped) {
+            (_, Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', None) if plus_is_space => decoded.push(b' '),
+            (byte, None) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
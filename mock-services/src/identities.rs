@@ -0,0 +1,54 @@
+//! A stand-in for the service resolving ORCID and ROR identifiers to names
+//! (`config::identity_lookup_uri`): `/orcid/{id}` and `/ror/{id}`.
+//!
+//! It knows the user of the mock login and the organization of that user.
+//! Other names can be set with `PUT` and `{ "name": "..." }`.
+
+use serde_json::json;
+
+use crate::{
+    http::{Request, Response},
+    oidc,
+    store::Store,
+};
+
+pub const KINDS: [&str; 2] = ["/orcid/", "/ror/"];
+
+/// The (example) organization of the mock user.
+pub const ORGANIZATION_ROR: &str = "05gq02987";
+pub const ORGANIZATION_NAME: &str = "Brown University";
+
+/// `path` is the identity, i.e. `orcid/0000-0002-1825-0097`.
+pub fn handle(store: &mut Store, request: &Request, path: &str) -> Response {
+    match request.method.as_str() {
+        "GET" => match name(store, path) {
+            Some(name) => Response::json(200, &json!({ "name": name })),
+            None => Response::error(404, "Unknown identifier."),
+        },
+        "PUT" => {
+            let body = match request.json() {
+                Ok(body) => body,
+                Err(response) => return response,
+            };
+            match body["name"].as_str() {
+                Some(name) => {
+                    store.identities.insert(path.to_owned(), name.to_owned());
+                    Response::empty(204)
+                }
+                None => Response::error(400, "The body needs a name."),
+            }
+        }
+        _ => Response::error(405, "Method not allowed for this resource."),
+    }
+}
+
+fn name(store: &Store, path: &str) -> Option<String> {
+    if let Some(name) = store.identities.get(path) {
+        return Some(name.clone());
+    }
+    match path.split_once('/') {
+        Some(("orcid", oidc::SUBJECT)) => Some(oidc::NAME.into()),
+        Some(("ror", ORGANIZATION_ROR)) => Some(ORGANIZATION_NAME.into()),
+        _ => None,
+    }
+}
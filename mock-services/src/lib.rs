@@ -0,0 +1,145 @@
+//! In-memory stand-ins for the PIT service and the RDA collection API.
+//!
+//! They implement the endpoints the client uses, including ETags, and keep
+//! everything in memory. Both APIs are served on the same port:
+//!
+//! - `{base}/api/v1/pit/pid` (PIT service)
+//! - `{base}/api/v1/collections` (collection API)
+//! - `{base}/api/handles` (Handle System REST API, on the records of the PIT service)
+//! - `{base}/orcid/{id}` and `{base}/ror/{id}` (names of people and organizations)
+//! - `{base}/.well-known/openid-configuration` and `{base}/oidc` (OpenID Connect login)
+//!
+//! Integration tests start a server on a free port with `MockServer::start("127.0.0.1:0")`.
+//! `MockServer::start_secured` answers the services only with a token (see `oidc::authorized`).
+//! For development, run the binary and point the client to it (see `config.rs`).
+
+pub mod collections;
+pub mod handles;
+pub mod http;
+pub mod identities;
+pub mod oidc;
+pub mod pit;
+pub mod store;
+
+use std::{
+    io::{self, BufReader},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use http::{Request, Response};
+use identities::KINDS;
+use store::Store;
+
+/// A running mock server. It runs in background threads until the process ends.
+pub struct MockServer {
+    address: SocketAddr,
+}
+
+impl MockServer {
+    /// Binds to the address and serves in a background thread.
+    /// Use port 0 to get a free port.
+    pub fn start(address: &str) -> io::Result<Self> {
+        Self::serve_store(address, Store::default())
+    }
+
+    /// Like `start`, but the services require a token.
+    pub fn start_secured(address: &str) -> io::Result<Self> {
+        Self::serve_store(address, Store::secured())
+    }
+
+    fn serve_store(address: &str, store: Store) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let store = Arc::new(Mutex::new(store));
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let store = store.clone();
+                        thread::spawn(move || serve(&store, stream));
+                    }
+                    Err(e) => eprintln!("Could not accept connection: {}", e),
+                }
+            }
+        });
+        Ok(Self { address })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Base URI for the PIT service, as in `config::pit_base_uri`.
+    pub fn pit_base_uri(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Base URI for the collection API, as in `config::collection_base_uri`.
+    pub fn collection_base_uri(&self) -> String {
+        format!("http://{}/api/v1", self.address)
+    }
+
+    /// Base URI for the Handle REST API, as in `config::handle_resolver_uri`.
+    pub fn handle_base_uri(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Base URI for the identity lookup, as in `config::identity_lookup_uri`.
+    pub fn identity_lookup_uri(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// The issuer of the OpenID Connect provider, as in `config::oidc_issuer`.
+    pub fn oidc_issuer(&self) -> String {
+        format!("http://{}", self.address)
+    }
+}
+
+fn serve(store: &Mutex<Store>, stream: TcpStream) {
+    let mut reader = BufReader::new(&stream);
+    let response = match Request::read(&mut reader) {
+        Ok(request) => {
+            // a panicking request must not take down the other ones.
+            let mut store = store.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            handle(&mut store, &request)
+        }
+        Err(e) => Response::error(400, &format!("Could not read request: {}", e)),
+    };
+    let mut writer = &stream;
+    if let Err(e) = response.write(&mut writer) {
+        eprintln!("Could not write response: {}", e);
+    }
+}
+
+pub fn handle(store: &mut Store, request: &Request) -> Response {
+    if request.method == "OPTIONS" {
+        // CORS preflight, the allowed methods and headers are always sent.
+        return Response::empty(204);
+    }
+    if request.path == oidc::DISCOVERY {
+        return oidc::discovery(request);
+    }
+    if let Some(path) = request.path.strip_prefix(oidc::BASE) {
+        return oidc::handle(store, request, path);
+    }
+    if let Some(handle) = request.path.strip_prefix(handles::BASE) {
+        // resolving handles is public.
+        return handles::handle(store, request, &http::path_decode(handle.trim_matches('/')));
+    }
+    if KINDS.iter().any(|kind| request.path.starts_with(kind)) {
+        // a public service, which does not know our tokens.
+        return identities::handle(store, request, request.path.trim_matches('/'));
+    }
+    if store.secured && !oidc::authorized(store, request) {
+        return Response::error(401, "A valid token is required.");
+    }
+    if let Some(pid) = request.path.strip_prefix(pit::BASE) {
+        return pit::handle(store, request, pid.trim_matches('/'));
+    }
+    if let Some(path) = request.path.strip_prefix(collections::BASE) {
+        return collections::handle(store, request, path);
+    }
+    Response::error(404, "Unknown endpoint.")
+}
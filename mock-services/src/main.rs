@@ -0,0 +1,35 @@
+//! Serves the mock services for local development.
+//!
+//! `cargo run -p fairris-mock-services [--secured] [PIT_PORT] [COLLECTION_PORT] [IDENTITY_PORT]`
+//!
+//! The default ports match the defaults of the client (`config.rs`).
+//! Each port serves all APIs, but the stores are separate.
+//! With `--secured`, the services require a token, i.e. from a login at the PIT port.
+
+use std::{env, thread};
+
+use fairris_mock_services::MockServer;
+
+fn main() {
+    let (flags, ports): (Vec<String>, Vec<String>) = env::args().skip(1).partition(|arg| arg.starts_with("--"));
+    let secured = flags.iter().any(|flag| flag == "--secured");
+    let start = if secured { MockServer::start_secured } else { MockServer::start };
+    let mut ports = ports.into_iter();
+    let pit_port = ports.next().unwrap_or_else(|| "8090".into());
+    let collection_port = ports.next().unwrap_or_else(|| "8091".into());
+    let identity_port = ports.next().unwrap_or_else(|| "8092".into());
+
+    let pit = start(&format!("127.0.0.1:{}", pit_port)).expect("Could not start PIT mock.");
+    let collections = start(&format!("127.0.0.1:{}", collection_port)).expect("Could not start collection mock.");
+    let identities = start(&format!("127.0.0.1:{}", identity_port)).expect("Could not start identity mock.");
+    println!("FAIRRIS_PIT_URI={}", pit.pit_base_uri());
+    println!("FAIRRIS_COLLECTION_URI={}", collections.collection_base_uri());
+    println!("FAIRRIS_IDENTITY_LOOKUP_URI={}", identities.identity_lookup_uri());
+    if secured {
+        println!("FAIRRIS_OIDC_ISSUER={}", pit.oidc_issuer());
+    }
+
+    loop {
+        thread::park();
+    }
+}
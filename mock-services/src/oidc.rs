@@ -0,0 +1,151 @@
+//! A minimal OpenID Connect provider: discovery, the authorization code flow with PKCE and refresh tokens.
+//!
+//! Every login is approved immediately for the same user, without asking.
+//! The provider checks that a PKCE challenge (`S256`) was sent and a verifier is given,
+//! but does not hash the verifier, so it can not catch wrong challenges.
+
+use serde_json::{json, Value};
+
+use crate::{
+    http::{Request, Response},
+    store::Store,
+};
+
+pub const BASE: &str = "/oidc";
+pub const DISCOVERY: &str = "/.well-known/openid-configuration";
+
+/// The user of every login. The subject is an (example) ORCID.
+pub const SUBJECT: &str = "0000-0002-1825-0097";
+pub const NAME: &str = "Josiah Carberry";
+
+/// Accepted as bearer token or as `X-API-Key` in addition to the tokens of logins.
+pub const STATIC_TOKEN: &str = "mock-static-token";
+
+/// Seconds until access tokens expire.
+pub const EXPIRES_IN: u64 = 300;
+
+pub fn discovery(request: &Request) -> Response {
+    let issuer = format!("http://{}", request.header("Host").unwrap_or("localhost"));
+    Response::json(
+        200,
+        &json!({
+            "issuer": issuer,
+            "authorization_endpoint": format!("{}{}/authorize", issuer, BASE),
+            "token_endpoint": format!("{}{}/token", issuer, BASE),
+            "response_types_supported": ["code"],
+            "grant_types_supported": ["authorization_code", "refresh_token"],
+            "code_challenge_methods_supported": ["S256"],
+        }),
+    )
+}
+
+pub fn handle(store: &mut Store, request: &Request, path: &str) -> Response {
+    match (request.method.as_str(), path.trim_matches('/')) {
+        ("GET", "authorize") => authorize(store, request),
+        ("POST", "token") => token(store, request),
+        _ => Response::error(405, "Method not allowed for this resource."),
+    }
+}
+
+/// Whether the request carries a token of this provider, or the static one.
+pub fn authorized(store: &Store, request: &Request) -> bool {
+    let bearer = request
+        .header("Authorization")
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let token = bearer.or_else(|| request.header("X-API-Key"));
+    match token {
+        Some(token) => token == STATIC_TOKEN || store.access_tokens.contains(token),
+        None => false,
+    }
+}
+
+fn authorize(store: &mut Store, request: &Request) -> Response {
+    let param = |name| request.query_param(name).unwrap_or_default();
+    if param("response_type") != "code" || param("client_id").is_empty() {
+        return Response::error(400, "Expected response_type=code and a client_id.");
+    }
+    let redirect_uri = param("redirect_uri");
+    if redirect_uri.is_empty() {
+        return Response::error(400, "A redirect_uri is required.");
+    }
+    if param("code_challenge_method") != "S256" || param("code_challenge").is_empty() {
+        return Response::error(400, "A PKCE challenge with method S256 is required.");
+    }
+    let code = format!("mock-code-{}", store.next_id());
+    store.codes.insert(code.clone(), param("code_challenge"));
+    let separator = if redirect_uri.contains('?') { '&' } else { '?' };
+    Response::redirect(&format!(
+        "{}{}code={}&state={}",
+        redirect_uri,
+        separator,
+        code,
+        param("state")
+    ))
+}
+
+fn token(store: &mut Store, request: &Request) -> Response {
+    let form = request.form();
+    let param = |name: &str| form.get(name).cloned().unwrap_or_default();
+    match param("grant_type").as_str() {
+        "authorization_code" => {
+            if store.codes.remove(&param("code")).is_none() {
+                return Response::json(400, &json!({ "error": "invalid_grant" }));
+            }
+            if param("code_verifier").is_empty() {
+                return Response::json(400, &json!({ "error": "invalid_request" }));
+            }
+            issue(store, true)
+        }
+        "refresh_token" => {
+            if !store.refresh_tokens.remove(&param("refresh_token")) {
+                return Response::json(400, &json!({ "error": "invalid_grant" }));
+            }
+            issue(store, false)
+        }
+        _ => Response::json(400, &json!({ "error": "unsupported_grant_type" })),
+    }
+}
+
+/// New tokens. Refreshing does not issue a new ID token, like many providers.
+fn issue(store: &mut Store, with_id_token: bool) -> Response {
+    let id = store.next_id();
+    let access_token = format!("mock-access-{}", id);
+    let refresh_token = format!("mock-refresh-{}", id);
+    store.access_tokens.insert(access_token.clone());
+    store.refresh_tokens.insert(refresh_token.clone());
+    let mut tokens = json!({
+        "access_token": access_token,
+        "token_type": "Bearer",
+        "expires_in": EXPIRES_IN,
+        "refresh_token": refresh_token,
+    });
+    if with_id_token {
+        tokens["id_token"] = Value::String(id_token());
+    }
+    Response::json(200, &tokens)
+}
+
+/// An unsigned JWT (`alg: none`) with the claims of the user.
+fn id_token() -> String {
+    let header = json!({ "alg": "none", "typ": "JWT" });
+    let claims = json!({ "sub": SUBJECT, "name": NAME });
+    format!(
+        "{}.{}.",
+        base64url(header.to_string().as_bytes()),
+        base64url(claims.to_string().as_bytes())
+    )
+}
+
+fn base64url(input: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut output = String::new();
+    for chunk in input.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let bits = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        // without padding: 2, 3 or 4 characters for 1, 2 or 3 bytes.
+        for i in 0..=chunk.len() {
+            output.push(ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    output
+}
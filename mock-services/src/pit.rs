@@ -0,0 +1,74 @@
+//! The `api/v1/pit/pid` endpoints of the PIT service (Typed PID Maker).
+
+use serde_json::Value;
+
+use crate::{
+    http::{Request, Response},
+    store::{etag, precondition, Store},
+};
+
+pub const BASE: &str = "/api/v1/pit/pid";
+
+/// Prefix of the PIDs handed out by the mock.
+pub const PREFIX: &str = "sandboxed";
+
+pub fn handle(store: &mut Store, request: &Request, pid: &str) -> Response {
+    match (request.method.as_str(), pid) {
+        ("POST", "") => register(store, request),
+        ("GET", pid) if !pid.is_empty() => resolve(store, pid),
+        ("PUT", pid) if !pid.is_empty() => update(store, request, pid),
+        _ => Response::error(405, "Method not allowed for this resource."),
+    }
+}
+
+fn register(store: &mut Store, request: &Request) -> Response {
+    let mut record = match read_record(request) {
+        Ok(record) => record,
+        Err(response) => return response,
+    };
+    let pid = format!("{}/{}", PREFIX, store.next_id());
+    record["pid"] = Value::String(pid.clone());
+    let response = Response::json(201, &record).with_etag(etag(&record));
+    store.records.insert(pid, record);
+    response
+}
+
+fn resolve(store: &Store, pid: &str) -> Response {
+    match store.records.get(pid) {
+        Some(record) => Response::json(200, record).with_etag(etag(record)),
+        None => Response::error(404, &format!("PID {} does not exist.", pid)),
+    }
+}
+
+fn update(store: &mut Store, request: &Request, pid: &str) -> Response {
+    let current = match store.records.get(pid) {
+        Some(record) => record,
+        None => return Response::error(404, &format!("PID {} does not exist.", pid)),
+    };
+    if let Err(status) = precondition(request.header("If-Match"), current, false) {
+        return Response::error(status, "The record was changed in the meantime.");
+    }
+    let mut record = match read_record(request) {
+        Ok(record) => record,
+        Err(response) => return response,
+    };
+    match record.get("pid").and_then(Value::as_str) {
+        Some(given) if !given.is_empty() && given != pid => {
+            return Response::error(400, "The PID of the record does not match the URL.")
+        }
+        _ => record["pid"] = Value::String(pid.to_owned()),
+    }
+    let response = Response::json(200, &record).with_etag(etag(&record));
+    store.records.insert(pid.to_owned(), record);
+    response
+}
+
+/// A record is an object with an `entries` object.
+fn read_record(request: &Request) -> Result<Value, Response> {
+    let record = request.json()?;
+    if matches!(record.get("entries"), Some(Value::Object(_))) {
+        Ok(record)
+    } else {
+        Err(Response::error(400, "A record needs an object of entries."))
+    }
+}
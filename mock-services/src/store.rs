@@ -0,0 +1,57 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use serde_json::Value;
+
+/// Everything the mock services know. It is lost when the server stops.
+#[derive(Debug, Default)]
+pub struct Store {
+    pub records: HashMap<String, Value>,
+    /// Sorted, so listings are stable.
+    pub collections: BTreeMap<String, Value>,
+    pub members: HashMap<String, Vec<Value>>,
+    /// Names of people and organizations set with `PUT`, by `orcid/{id}` or `ror/{id}`.
+    pub identities: HashMap<String, String>,
+    /// If set, the services answer only requests with a valid token, see `oidc::authorized`.
+    pub secured: bool,
+    /// Authorization codes and the PKCE challenge of the login they belong to.
+    pub codes: HashMap<String, String>,
+    pub access_tokens: HashSet<String>,
+    pub refresh_tokens: HashSet<String>,
+    last_id: u64,
+}
+
+impl Store {
+    pub fn secured() -> Self {
+        Self {
+            secured: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn next_id(&mut self) -> u64 {
+        self.last_id += 1;
+        self.last_id
+    }
+}
+
+/// A strong ETag, derived from the content.
+pub fn etag(value: &Value) -> String {
+    let mut hasher = DefaultHasher::new();
+    value.to_string().hash(&mut hasher);
+    format!("\"{:x}\"", hasher.finish())
+}
+
+/// Checks the `If-Match` header against the current ETag.
+/// Without header, `required` decides if the request may pass.
+pub fn precondition(if_match: Option<&str>, current: &Value, required: bool) -> Result<(), u16> {
+    match if_match {
+        Some("*") => Ok(()),
+        Some(given) if given == etag(current) => Ok(()),
+        Some(_) => Err(412),
+        None if required => Err(428),
+        None => Ok(()),
+    }
+}
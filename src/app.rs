@@ -0,0 +1,245 @@
+//! The single page application: the main component, its routes and the entry point.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    app_state::{self, data_manager::DataManager, pid_manager::PidManager},
+    auth::{self, oidc::Identity, AuthManager},
+    bulk_editor::BulkEditor,
+    data_type_registry::Pid,
+    details_page::DetailsPage,
+    graph_page::RelationGraphPage,
+    login_button::LoginButton,
+    pidinfo::PidInfo,
+    search_component::SearchComponent,
+    template_menu::TemplateMenu,
+    templates_page::TemplatesPage,
+    workspace_io::WorkspaceIo,
+    workspace_list::WorkspaceList,
+};
+
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+use yew_router::{prelude::*, router::Router, Switch};
+
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+#[derive(Switch, Debug, Clone)]
+pub enum AppRoute {
+    #[to = "/fdo/{*:path}"]
+    Details { path: String },
+    #[to = "/search"]
+    Search,
+    #[to = "/graph"]
+    Graph,
+    #[to = "/bulk"]
+    BulkEdit,
+    #[to = "/templates"]
+    Templates,
+    #[to = "/"]
+    Index,
+}
+
+pub struct Model {
+    link: ComponentLink<Self>,
+
+    pid_manager: Box<dyn Bridge<PidManager>>,
+    // keeping a bridge to the data state here keeps it alive.
+    _data_manager: Box<dyn Bridge<DataManager>>,
+    _auth_manager: Box<dyn Bridge<AuthManager>>,
+    /// New objects list the logged in user as contributor.
+    identity: Option<Identity>,
+
+    known_pids: HashMap<Pid, PidInfo>,
+    /// Objects selected for bulk editing or export.
+    selection: HashSet<Pid>,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    AddDefaultItem,
+    PidRemove(Pid),  // object will be removed
+    ToggleSelection(Pid),
+    ClearSelection,
+
+    UpdatePidInfoList(HashMap<Pid, PidInfo>),
+    IdentityChanged(Option<Identity>),
+    Error(String),
+    Noop,
+}
+
+impl Component for Model {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+        use crate::app_state::pid_manager::Incoming;
+        let mut pid_manager = PidManager::bridge(link.callback(|msg| {
+            match msg {
+                app_state::pid_manager::Outgoing::AllPidInformation(infos) => Msg::UpdatePidInfoList(infos),
+                app_state::pid_manager::Outgoing::Created(_) => Msg::Noop,
+            }
+        }));
+        pid_manager.send(Incoming::GetAllPidInformation);
+        let _data_manager = DataManager::bridge(link.callback(|_msg| Msg::Noop));
+        let mut auth_manager = AuthManager::bridge(link.callback(|msg| match msg {
+            auth::Outgoing::Identity(identity) => Msg::IdentityChanged(identity),
+        }));
+        auth_manager.send(auth::Incoming::GetIdentity);
+        Self {
+            link,
+            pid_manager,
+            _data_manager,
+            _auth_manager: auth_manager,
+            identity: None,
+            known_pids: Default::default(),
+            selection: Default::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        use crate::app_state::pid_manager::Incoming;
+
+        log::debug!("Model received update {:?}", msg);
+        match msg {
+            Msg::Error(issue) => {
+                log::error!("Something went wrong: {}", issue);
+                false
+            },
+            Msg::UpdatePidInfoList(list) => {
+                self.known_pids = list;
+                // removed objects can not be edited anymore.
+                let known_pids = &self.known_pids;
+                self.selection.retain(|pid| known_pids.contains_key(pid));
+                true
+            },
+
+            Msg::IdentityChanged(identity) => {
+                self.identity = identity;
+                false
+            }
+            Msg::AddDefaultItem => {
+                match &self.identity {
+                    Some(identity) => {
+                        let mut info = PidInfo::default();
+                        identity.prefill(&mut info);
+                        self.pid_manager.send(Incoming::AddUnregisteredPidInfo(info));
+                    }
+                    None => self.pid_manager.send(Incoming::AddUnregisteredItem),
+                }
+                true
+            }
+            Msg::PidRemove(pid) => {
+                self.pid_manager.send( Incoming::RemovePidInfo(pid) );
+                true
+            }
+            Msg::ToggleSelection(pid) => {
+                if !self.selection.remove(&pid) {
+                    self.selection.insert(pid);
+                }
+                true
+            }
+            Msg::ClearSelection => {
+                self.selection.clear();
+                true
+            }
+            Msg::Noop => false,
+        }
+    }
+
+    fn change(&mut self, _: Self::Properties) -> ShouldRender {
+        // Should only return "true" if new properties are different to
+        // previously received properties.
+        true
+    }
+
+    fn view(&self) -> Html {
+        let known_pids = self.known_pids.clone();
+        let mut selected: Vec<PidInfo> = self
+            .selection
+            .iter()
+            .filter_map(|pid| self.known_pids.get(pid).cloned())
+            .collect();
+        selected.sort_by(|a, b| a.pid().cmp(b.pid()));
+        let router_function = move |switch: AppRoute| match switch {
+            AppRoute::Details { ref path } => {
+                let pid = Pid(path.to_string());
+                known_pids.get(&pid).map_or_else(
+                    || Self::view_record_not_found_page(path),
+                    |item| {
+                        html! {<DetailsPage record=item.clone() />}
+                    }
+                )
+            },
+            AppRoute::Search => html! {<SearchComponent/>},
+            AppRoute::Graph => html! {<RelationGraphPage/>},
+            AppRoute::BulkEdit => html! {<BulkEditor records=selected.clone() />},
+            AppRoute::Templates => html! {<TemplatesPage/>},
+            AppRoute::Index => Self::view_welcome_page(),
+        };
+        html! {
+            <div id="everything">
+                <div id="sidebar" class="maincolumns">
+                    <div id="pidbuttons">
+                        <button onclick=self.link.callback(|_| Msg::AddDefaultItem)>{ "Add" }</button>
+                        <RouterButton<AppRoute> route=AppRoute::Search>{ "Search" }</RouterButton<AppRoute>>
+                        <RouterButton<AppRoute> route=AppRoute::Graph>{ "Graph" }</RouterButton<AppRoute>>
+                    </div>
+                    <LoginButton />
+                    <TemplateMenu />
+                    <WorkspaceIo />
+                    { self.view_selection() }
+                    <WorkspaceList model_link=self.link.clone() known_pids=self.known_pids.clone() selection=self.selection.clone() />
+                </div>
+                <Router<AppRoute, ()> render = Router::render(router_function)
+                />
+            </div>
+        }
+    }
+}
+
+impl Model {
+    fn view_selection(&self) -> Html {
+        if self.selection.is_empty() {
+            return html! {};
+        }
+        let mut selection: Vec<Pid> = self.selection.iter().cloned().collect();
+        selection.sort_by(|a, b| a.0.cmp(&b.0));
+        html! {
+            <div class="selection-bar">
+                <span>{ format!("{} selected", self.selection.len()) }</span>
+                <RouterButton<AppRoute> route=AppRoute::BulkEdit>{ "Bulk edit" }</RouterButton<AppRoute>>
+                <WorkspaceIo pids=selection allow_import=false />
+                <button onclick=self.link.callback(|_| Msg::ClearSelection)>{ "Clear" }</button>
+            </div>
+        }
+    }
+
+    fn view_record_not_found_page(pid: &String) -> Html {
+        // TODO extend and style
+        html! {<p>{format!("Object {} not locally available.", pid)}</p>}
+    }
+
+    fn view_welcome_page() -> Html {
+        // TODO extend and style
+        // TODO Add understandable introduction, tell the user that he may play around.
+        html! {
+            <div>
+            <h1>{"Welcome to Fairris"}</h1>
+            <p>{"
+                Fairris show the FAIR digital object ecosystem (the testbed) from a user perspective. 
+                Imagine this user interface to be a digital lab notebook, workflow system or IDE, depending on your needs. 
+                It will help you to manage and register your research data and may automate a lot of your work.
+            "}</p>
+            </div>
+        }
+    }
+}
+
+#[wasm_bindgen(start)]
+pub fn run_app() {
+    console_error_panic_hook::set_once();
+    wasm_logger::init(wasm_logger::Config::default());
+    App::<Model>::new().mount_to_body();
+}
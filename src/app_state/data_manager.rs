@@ -1,9 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::PidInfo;
+use crate::pidinfo::PidInfo;
 
 use rand::prelude::*;
-use strum::IntoEnumIterator;
 use yew::worker::{Agent, AgentLink, Context, HandlerId};
 
 use super::data::{Data, DataID};
@@ -73,7 +72,7 @@ impl Agent for DataManager {
                     self.select(id);
                     (true, true)
                 } else {
-                    let selection_changed: bool = self.selected == None;
+                    let selection_changed: bool = self.selected.is_none();
                     self.unselect();
                     (false, selection_changed)
                 }
@@ -84,7 +83,7 @@ impl Agent for DataManager {
                     self.select(id);
                     (false, changed)
                 } else {
-                    let changed = self.selected != None;
+                    let changed = self.selected.is_some();
                     self.unselect();
                     (false, changed)
                 }
@@ -174,8 +173,8 @@ impl DataManager {
         if let Some(id) = &item.data {
             log::debug!("Found some data in record: {:?}", id);
             self.known_data
-                .get(&id)
-                .map(|data| (id.clone(), data.clone()))
+                .get(id)
+                .map(|data| (*id, data.clone()))
         } else {
             // TODO here you may handle retrieving data out of the raw record inside the pidinfo
             //      (map data info to data objects, send GET request to collection service, etc)
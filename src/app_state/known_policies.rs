@@ -0,0 +1,54 @@
+use yew::{
+    format::Json,
+    services::storage::{Area, StorageService},
+};
+
+use crate::data_type_registry::{Pid, PolicyDefinition};
+
+const STORAGE_KEY: &str = "fairris.policies";
+
+/// Remembers policy objects which were created or resolved by this client,
+/// so equal policies are reused instead of being registered again.
+/// The list is persisted in the local storage of the browser.
+pub struct KnownPolicies {
+    storage: Option<StorageService>,
+    policies: Vec<(PolicyDefinition, String)>,
+}
+
+impl KnownPolicies {
+    pub fn load() -> Self {
+        let storage = StorageService::new(Area::Local)
+            .map_err(|e| log::error!("Local storage not available: {}", e))
+            .ok();
+        let policies = storage
+            .as_ref()
+            .and_then(|storage| {
+                let Json(policies) = storage.restore(STORAGE_KEY);
+                policies.ok()
+            })
+            .unwrap_or_default();
+        Self { storage, policies }
+    }
+
+    pub fn find_pid(&self, definition: &PolicyDefinition) -> Option<Pid> {
+        self.policies
+            .iter()
+            .find(|(known, _)| known == definition)
+            .map(|(_, pid)| Pid(pid.clone()))
+    }
+
+    pub fn find_definition(&self, pid: &Pid) -> Option<PolicyDefinition> {
+        self.policies
+            .iter()
+            .find(|(_, known)| *known == **pid)
+            .map(|(definition, _)| definition.clone())
+    }
+
+    pub fn insert(&mut self, definition: PolicyDefinition, pid: Pid) {
+        self.policies.retain(|(_, known)| *known != *pid);
+        self.policies.push((definition, pid.0));
+        if let Some(storage) = self.storage.as_mut() {
+            storage.store(STORAGE_KEY, Json(&self.policies));
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! How the objects of the workspace are listed in the sidebar.
+
+use std::{collections::HashMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use yew::{
+    format::Json,
+    services::storage::{Area, StorageService},
+};
+
+use crate::{
+    data_type_registry::Pid,
+    pidinfo::{PidInfo, State},
+};
+
+const STORAGE_KEY: &str = "fairris.workspace-list";
+
+#[derive(Clone, Copy, Debug, EnumIter, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortKey {
+    Pid,
+    State,
+    Type,
+    DateCreated,
+    DateModified,
+}
+
+/// Associates sort keys with their Display name (for the user interface).
+impl Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortKey::Pid => write!(f, "PID"),
+            SortKey::State => write!(f, "State"),
+            SortKey::Type => write!(f, "Type"),
+            SortKey::DateCreated => write!(f, "Date created"),
+            SortKey::DateModified => write!(f, "Date modified"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, EnumIter, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Grouping {
+    None,
+    State,
+    Type,
+}
+
+/// Associates groupings with their Display name (for the user interface).
+impl Display for Grouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Grouping::None => write!(f, "No grouping"),
+            Grouping::State => write!(f, "Group by state"),
+            Grouping::Type => write!(f, "Group by type"),
+        }
+    }
+}
+
+macro_rules! impl_parse_display {
+    ( $given_type:tt ) => {
+        impl $given_type {
+            pub fn parse(s: &str) -> Option<Self> {
+                Self::iter().find(|x| x.to_string() == s)
+            }
+        }
+    };
+}
+
+impl_parse_display!(SortKey);
+impl_parse_display!(Grouping);
+
+/// A group of listed objects. Pinned objects are listed in their own group first.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Group<'a> {
+    pub name: String,
+    pub items: Vec<&'a PidInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ListPreferences {
+    pub sort: SortKey,
+    pub descending: bool,
+    pub grouping: Grouping,
+    pub filter: String,
+    pub pinned: Vec<String>,
+}
+
+impl Default for ListPreferences {
+    fn default() -> Self {
+        Self {
+            sort: SortKey::Pid,
+            descending: false,
+            grouping: Grouping::None,
+            filter: String::new(),
+            pinned: Vec::new(),
+        }
+    }
+}
+
+impl ListPreferences {
+    /// Restores the preferences from the local storage of the browser.
+    pub fn load() -> Self {
+        StorageService::new(Area::Local)
+            .ok()
+            .and_then(|storage| {
+                let Json(preferences) = storage.restore(STORAGE_KEY);
+                preferences.ok()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn store(&self) {
+        match StorageService::new(Area::Local) {
+            Ok(mut storage) => storage.store(STORAGE_KEY, Json(self)),
+            Err(e) => log::error!("Local storage not available: {}", e),
+        }
+    }
+
+    pub fn is_pinned(&self, pid: &Pid) -> bool {
+        self.pinned.contains(&pid.0)
+    }
+
+    pub fn toggle_pin(&mut self, pid: &Pid) {
+        if self.is_pinned(pid) {
+            self.pinned.retain(|p| *p != pid.0);
+        } else {
+            self.pinned.push(pid.0.clone());
+        }
+    }
+
+    fn matches(&self, info: &PidInfo) -> bool {
+        let filter = self.filter.trim().to_lowercase();
+        filter.is_empty()
+            || info.pid().to_lowercase().contains(&filter)
+            || info.describe().to_lowercase().contains(&filter)
+    }
+
+    fn type_name(info: &PidInfo) -> String {
+        match &info.digital_object_type {
+            Ok(t) => t.to_string(),
+            Err(Some(pid)) => pid.0.clone(),
+            Err(None) => "No type".into(),
+        }
+    }
+
+    fn state_rank(state: State) -> u8 {
+        match state {
+            // needs attention first.
+            State::RegistrationUnknown => 0,
+            State::Unregistered => 1,
+            State::Modified => 2,
+            State::Clean => 3,
+        }
+    }
+
+    /// Filters, sorts and groups the objects. Ties are broken by PID,
+    /// so the order does not depend on the order of the map.
+    pub fn arrange<'a>(&self, infos: &'a HashMap<Pid, PidInfo>) -> Vec<Group<'a>> {
+        let mut items: Vec<&PidInfo> = infos.values().filter(|info| self.matches(info)).collect();
+        items.sort_by(|a, b| {
+            let order = match self.sort {
+                SortKey::Pid => a.pid().cmp(b.pid()),
+                SortKey::State => Self::state_rank(a.state()).cmp(&Self::state_rank(b.state())),
+                SortKey::Type => Self::type_name(a).cmp(&Self::type_name(b)),
+                SortKey::DateCreated => a.date_created.to_rfc3339().cmp(&b.date_created.to_rfc3339()),
+                SortKey::DateModified => a.date_modified.to_rfc3339().cmp(&b.date_modified.to_rfc3339()),
+            };
+            let order = if self.descending { order.reverse() } else { order };
+            order.then_with(|| a.pid().cmp(b.pid()))
+        });
+        let (pinned, items): (Vec<&PidInfo>, Vec<&PidInfo>) = items
+            .into_iter()
+            .partition(|info| self.pinned.contains(info.pid()));
+
+        let mut groups: Vec<Group> = Vec::new();
+        if !pinned.is_empty() {
+            groups.push(Group { name: "Pinned".into(), items: pinned });
+        }
+        let group_of = |info: &PidInfo| match self.grouping {
+            Grouping::None => String::new(),
+            Grouping::State => format!("{:?}", info.state()),
+            Grouping::Type => Self::type_name(info),
+        };
+        let mut grouped: Vec<Group> = Vec::new();
+        for info in items {
+            let name = group_of(info);
+            match grouped.iter_mut().find(|g| g.name == name) {
+                Some(group) => group.items.push(info),
+                None => grouped.push(Group { name, items: vec![info] }),
+            }
+        }
+        grouped.sort_by(|a, b| match self.grouping {
+            Grouping::State => {
+                let rank = |g: &Group| g.items.first().map(|i| Self::state_rank(i.state()));
+                rank(a).cmp(&rank(b))
+            }
+            _ => a.name.cmp(&b.name),
+        });
+        groups.extend(grouped);
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace() -> HashMap<Pid, PidInfo> {
+        let mut infos = HashMap::new();
+        for (pid, registered) in [("21.T11148/b", true), ("unregistered-1", false), ("21.T11148/a", true)].iter() {
+            let mut info = PidInfo::default();
+            *info.pid_mut() = (*pid).into();
+            if *registered {
+                info = PidInfo::from_registered(info.as_record());
+            }
+            infos.insert(Pid((*pid).into()), info);
+        }
+        infos
+    }
+
+    fn pids(groups: &[Group]) -> Vec<Vec<String>> {
+        groups
+            .iter()
+            .map(|g| g.items.iter().map(|i| i.pid().clone()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn sorts_groups_filters_and_pins() {
+        let infos = workspace();
+        let mut preferences = ListPreferences::default();
+        assert_eq!(
+            pids(&preferences.arrange(&infos)),
+            vec![vec!["21.T11148/a", "21.T11148/b", "unregistered-1"]]
+        );
+
+        preferences.grouping = Grouping::State;
+        let groups = preferences.arrange(&infos);
+        assert_eq!(groups[0].name, "Unregistered");
+        assert_eq!(pids(&groups), vec![vec!["unregistered-1"], vec!["21.T11148/a", "21.T11148/b"]]);
+
+        preferences.grouping = Grouping::None;
+        preferences.descending = true;
+        preferences.toggle_pin(&Pid("21.T11148/a".into()));
+        let groups = preferences.arrange(&infos);
+        assert_eq!(groups[0].name, "Pinned");
+        assert_eq!(pids(&groups), vec![vec!["21.T11148/a"], vec!["unregistered-1", "21.T11148/b"]]);
+
+        preferences.filter = "UNREG".into();
+        assert_eq!(pids(&preferences.arrange(&infos)), vec![vec!["unregistered-1"]]);
+    }
+}
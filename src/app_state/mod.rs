@@ -1,3 +1,9 @@
+pub use fairris_core::app_state::*;
+
+// The agents holding the state of the web application.
 pub mod data_manager;
 pub mod pid_manager;
-pub mod data;
\ No newline at end of file
+pub mod known_policies;
+pub mod list_preferences;
+pub mod template_manager;
+pub mod outbox_manager;
@@ -0,0 +1,143 @@
+use std::{collections::HashSet, time::Duration};
+
+use yew::{
+    agent::{Dispatched, Dispatcher},
+    format::Json,
+    services::{
+        interval::IntervalTask,
+        storage::{Area, StorageService},
+        IntervalService,
+    },
+    worker::{Agent, AgentLink, Context, HandlerId},
+};
+
+use super::outbox::{Operation, Outbox};
+use crate::{
+    collection_service::{self, CollectionService, Etag},
+    pidinfo::PidInfo,
+    pit_service::{self, PitService},
+};
+
+const STORAGE_KEY: &str = "fairris.outbox";
+/// How often the pending operations are published again.
+const REPLAY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Keeps the changes which could not be published because a service was unreachable,
+/// and publishes them again periodically until it works.
+/// The outbox is persisted in the local storage of the browser, so changes survive a reload.
+pub struct OutboxManager {
+    link: AgentLink<OutboxManager>,
+    subscribers: HashSet<HandlerId>,
+
+    storage: Option<StorageService>,
+    outbox: Outbox,
+    pit_service: Dispatcher<PitService>,
+    collection_service: Dispatcher<CollectionService>,
+    _replay: IntervalTask,
+}
+
+#[derive(Debug)]
+pub enum Incoming {
+    GetOutbox,
+    /// Queues the operation, with the reason why publishing failed.
+    Queue(Operation, String),
+    /// Publishes all pending operations now.
+    Replay,
+}
+
+#[derive(Debug, Clone)]
+pub enum Outgoing {
+    Outbox(Outbox),
+}
+
+impl Agent for OutboxManager {
+    type Reach = Context<Self>;
+    type Message = ();
+    type Input = Incoming;
+    type Output = Outgoing;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        let storage = StorageService::new(Area::Local)
+            .map_err(|e| log::error!("Local storage not available: {}", e))
+            .ok();
+        let outbox = storage
+            .as_ref()
+            .and_then(|storage| {
+                let Json(outbox) = storage.restore(STORAGE_KEY);
+                outbox.ok()
+            })
+            .unwrap_or_default();
+        let replay = IntervalService::spawn(REPLAY_INTERVAL, link.callback(|_| ()));
+        Self {
+            link,
+            subscribers: Default::default(),
+            storage,
+            outbox,
+            pit_service: PitService::dispatcher(),
+            collection_service: CollectionService::dispatcher(),
+            _replay: replay,
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) {
+        if !self.outbox.is_empty() {
+            self.replay();
+        }
+    }
+
+    fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
+        log::debug!("OutboxManager message: {:?}", msg);
+        match msg {
+            Incoming::GetOutbox => {
+                self.link.respond(id, Outgoing::Outbox(self.outbox.clone()));
+                return;
+            }
+            Incoming::Queue(operation, reason) => {
+                log::warn!("Queued for later publishing: {}", reason);
+                self.outbox.queue(operation, reason);
+            }
+            Incoming::Replay => self.replay(),
+        }
+        self.changed();
+    }
+
+    fn connected(&mut self, id: HandlerId) {
+        self.subscribers.insert(id);
+    }
+
+    fn disconnected(&mut self, id: HandlerId) {
+        self.subscribers.remove(&id);
+    }
+}
+
+impl OutboxManager {
+    /// Sends all operations to the services again. Those still failing come back with `Queue`.
+    fn replay(&mut self) {
+        for pending in self.outbox.take_all() {
+            match pending.operation {
+                Operation::Register(record) => self
+                    .pit_service
+                    .send(pit_service::Request::Register(PidInfo::from_unregistered(record))),
+                Operation::Update(record) => self
+                    .pit_service
+                    .send(pit_service::Request::Update(PidInfo::from_registered(record))),
+                Operation::Push(id, collection, etag) => match Etag::from_str(&etag) {
+                    Ok(etag) => self
+                        .collection_service
+                        .send(collection_service::Request::Push(id, *collection, etag)),
+                    Err(e) => log::error!("Dropped queued push with invalid ETag {}: {}", etag, e),
+                },
+            }
+        }
+        self.changed();
+    }
+
+    fn changed(&mut self) {
+        if let Some(storage) = self.storage.as_mut() {
+            storage.store(STORAGE_KEY, Json(&self.outbox));
+        }
+        for sub in self.subscribers.iter() {
+            self.link.respond(*sub, Outgoing::Outbox(self.outbox.clone()));
+        }
+    }
+}
@@ -1,6 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::{data_type_registry::Pid, pidinfo::PidInfo, service_communication::PidRecord};
+use crate::{
+    app_state::versioning,
+    data_type_registry::Pid,
+    pidinfo::{PidInfo, State},
+    service_communication::PidRecord,
+};
 
 use rand::prelude::*;
 use yew::{worker::{Agent, AgentLink, Context, HandlerId}};
@@ -17,14 +22,22 @@ pub struct PidManager {
 pub enum Incoming {
     GetAllPidInformation,
     AddUnregisteredItem,
+    /// Adds the object with a new unregistered PID. Answers with `Outgoing::Created`.
+    AddUnregisteredPidInfo(PidInfo),
     AddPidInfo(PidInfo),  // TODO rename: This one updates record AND local changes into the state
+    /// Like `AddPidInfo`, but notifies subscribers only once.
+    AddPidInfos(Vec<PidInfo>),
     UpdateRecord(Pid, PidRecord),  // TODO rename: This one updates only the internal record. The equivalent for the local changed might be a good idea.
+    /// The registration of the draft may or may not have succeeded.
+    MarkRegistrationUnknown(Pid),
     RemovePidInfo(Pid),
 }
 
 #[derive(Debug, Clone)]
 pub enum Outgoing {
     AllPidInformation(HashMap<Pid, PidInfo>),
+    /// The PID assigned to an object given with `Incoming::AddUnregisteredPidInfo`.
+    Created(Pid),
 }
 
 impl Agent for PidManager {
@@ -44,23 +57,40 @@ impl Agent for PidManager {
 
     fn update(&mut self, _msg: Self::Message) {}
 
-    fn handle_input(&mut self, msg: Self::Input, _id: HandlerId) {
+    fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
         log::debug!("PidManager message: {:?}", msg);
-        let (pids_changed, selection_changed): (bool, bool) = match msg {
+        let mut created: Option<Pid> = None;
+        let (pids_changed, _selection_changed): (bool, bool) = match msg {
             Incoming::GetAllPidInformation => (true, false),
             Incoming::AddUnregisteredItem => {
-                self.add_unregistered();
+                self.add_unregistered(PidInfo::default());
+                (true, false)
+            }
+            Incoming::AddUnregisteredPidInfo(pidinfo) => {
+                created = Some(self.add_unregistered(pidinfo));
                 (true, false)
             }
             Incoming::AddPidInfo(pidinfo) => {
                 self.add(pidinfo);
                 (true, false)
             }
+            Incoming::AddPidInfos(pidinfos) => {
+                for pidinfo in pidinfos {
+                    self.add(pidinfo);
+                }
+                (true, false)
+            }
             Incoming::RemovePidInfo(pid) => {
                 self.remove(&pid);
                 // TODO it would be possible to select something else (or nothing) in case the deleted is selected.
                 (true, false)
             }
+            Incoming::MarkRegistrationUnknown(pid) => {
+                if let Some(info) = self.known_pids.get_mut(&pid) {
+                    *info.state_mut() = State::RegistrationUnknown;
+                }
+                (true, false)
+            }
             Incoming::UpdateRecord(pid, record) => {
                 self.update_record(&pid, record);
                 let selection_was_changed = self.selected == Some(pid);
@@ -70,6 +100,10 @@ impl Agent for PidManager {
         if pids_changed {
             self.notify_all(Outgoing::AllPidInformation(self.known_pids.clone()));
         }
+        // answer after the notification, so receivers already know the new object.
+        if let Some(pid) = created {
+            self.link.respond(id, Outgoing::Created(pid));
+        }
     }
 
     fn connected(&mut self, id: HandlerId) {
@@ -88,19 +122,30 @@ impl PidManager {
     }
 
     fn remove(&mut self, pid: &Pid) {
-        self.known_pids.remove(&pid);
+        self.known_pids.remove(pid);
     }
 
     fn update_record(&mut self, pid: &Pid, record: PidRecord) {
-        let data_id = self.known_pids.get(&pid).map(|old_pidinfo| old_pidinfo.data).flatten();
+        let old_object = self.known_pids.get(pid);
+        let data_id = old_object.and_then(|old_pidinfo| old_pidinfo.data);
+        let mut new_object = match old_object {
+            Some(local) => PidInfo::from_published(record, local),
+            None => PidInfo::from_registered(record),
+        };
         self.remove(pid);
-        let mut new_object = PidInfo::from_registered(record);
         new_object.data = data_id;
+        let new_pid = Pid(new_object.pid().clone());
         self.add(new_object);
+        // objects may link to the unregistered PID, i.e. to a new version.
+        if *pid != new_pid {
+            for info in self.known_pids.values_mut() {
+                versioning::replace_links(info, pid, &new_pid);
+            }
+            versioning::link_predecessor(&mut self.known_pids, &new_pid);
+        }
     }
 
-    pub fn add_unregistered(&mut self) -> Pid {
-        let mut object = PidInfo::default();
+    pub fn add_unregistered(&mut self, mut object: PidInfo) -> Pid {
         let pid: Pid;
         loop {
             let random_number = rand::thread_rng().gen::<u16>();
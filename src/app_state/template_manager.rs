@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use yew::{
+    format::Json,
+    services::storage::{Area, StorageService},
+    worker::{Agent, AgentLink, Context, HandlerId},
+};
+
+use super::templates::Template;
+
+const STORAGE_KEY: &str = "fairris.templates";
+
+/// Keeps the templates for new drafts.
+/// The templates are persisted in the local storage of the browser.
+pub struct TemplateManager {
+    link: AgentLink<TemplateManager>,
+    subscribers: HashSet<HandlerId>,
+
+    storage: Option<StorageService>,
+    templates: Vec<Template>,
+}
+
+#[derive(Debug)]
+pub enum Incoming {
+    GetAllTemplates,
+    /// Adds the template, replacing a template with the same name.
+    Save(Template),
+    /// Like `Save`, but for several templates.
+    Import(Vec<Template>),
+    Remove(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum Outgoing {
+    AllTemplates(Vec<Template>),
+}
+
+impl Agent for TemplateManager {
+    type Reach = Context<Self>;
+    type Message = ();
+    type Input = Incoming;
+    type Output = Outgoing;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        let storage = StorageService::new(Area::Local)
+            .map_err(|e| log::error!("Local storage not available: {}", e))
+            .ok();
+        let templates = storage
+            .as_ref()
+            .and_then(|storage| {
+                let Json(templates) = storage.restore(STORAGE_KEY);
+                templates.ok()
+            })
+            .unwrap_or_default();
+        Self {
+            link,
+            subscribers: Default::default(),
+            storage,
+            templates,
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) {}
+
+    fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
+        log::debug!("TemplateManager message: {:?}", msg);
+        match msg {
+            Incoming::GetAllTemplates => {
+                self.link.respond(id, Outgoing::AllTemplates(self.templates.clone()));
+                return;
+            }
+            Incoming::Save(template) => self.save(template),
+            Incoming::Import(templates) => templates.into_iter().for_each(|t| self.save(t)),
+            Incoming::Remove(name) => self.templates.retain(|t| t.name != name),
+        }
+        if let Some(storage) = self.storage.as_mut() {
+            storage.store(STORAGE_KEY, Json(&self.templates));
+        }
+        for sub in self.subscribers.iter() {
+            self.link.respond(*sub, Outgoing::AllTemplates(self.templates.clone()));
+        }
+    }
+
+    fn connected(&mut self, id: HandlerId) {
+        self.subscribers.insert(id);
+    }
+
+    fn disconnected(&mut self, id: HandlerId) {
+        self.subscribers.remove(&id);
+    }
+}
+
+impl TemplateManager {
+    fn save(&mut self, template: Template) {
+        match self.templates.iter_mut().find(|t| t.name == template.name) {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+    }
+}
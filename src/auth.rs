@@ -0,0 +1,270 @@
+//! Authentication: the credentials of all requests, and the login with OpenID Connect.
+
+pub use fairris_core::auth::*;
+
+use std::collections::HashSet;
+
+use yew::{
+    format::Json,
+    services::{
+        storage::{Area, StorageService},
+        timeout::TimeoutTask,
+        TimeoutService,
+    },
+    worker::{Agent, AgentLink, Context, HandlerId},
+};
+
+use crate::service_communication::{
+    send_to_agent,
+    transport::{Logging, Pipeline},
+    FetchTransport,
+};
+use oidc::{Client, Identity, PendingLogin, Provider, Session};
+
+thread_local! {
+    /// Shared by the pipelines of all service agents, so a login applies to all of them.
+    static AUTHORIZATION: Authorization = Authorization::new(Credentials::configured());
+}
+
+/// The credentials middleware of the service agents, see `service_communication::pipeline`.
+pub fn authorization() -> Authorization {
+    AUTHORIZATION.with(Clone::clone)
+}
+
+/// The session survives reloads. The pending login only has to survive the redirect to the provider.
+const SESSION_KEY: &str = "fairris.session";
+const PENDING_KEY: &str = "fairris.login";
+
+/// Keeps the login session. Its access token is used for all requests,
+/// and refreshed before it expires. Without login, the configured credentials are used.
+pub struct AuthManager {
+    link: AgentLink<AuthManager>,
+    subscribers: HashSet<HandlerId>,
+
+    /// `None` if no login provider is configured.
+    client: Option<Client>,
+    transport: Pipeline,
+    local_storage: Option<StorageService>,
+    session_storage: Option<StorageService>,
+    session: Option<Session>,
+    refresh: Option<TimeoutTask>,
+}
+
+#[derive(Debug)]
+pub enum Incoming {
+    GetIdentity,
+    Login,
+    Logout,
+}
+
+#[derive(Debug, Clone)]
+pub enum Outgoing {
+    /// The logged in user. `None` without login.
+    Identity(Option<Identity>),
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    Discovered(Result<Provider, String>),
+    LoggedIn(Result<Session, String>),
+    Refresh,
+    Refreshed(Result<Session, String>),
+}
+
+impl Agent for AuthManager {
+    type Reach = Context<Self>;
+    type Message = Msg;
+    type Input = Incoming;
+    type Output = Outgoing;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        let local_storage = StorageService::new(Area::Local)
+            .map_err(|e| log::error!("Local storage not available: {}", e))
+            .ok();
+        let session_storage = StorageService::new(Area::Session)
+            .map_err(|e| log::error!("Session storage not available: {}", e))
+            .ok();
+        let client = crate::config::oidc_issuer().map(|issuer| Client {
+            issuer: issuer.into(),
+            client_id: crate::config::oidc_client_id().into(),
+            redirect_uri: redirect_uri(),
+            scope: "openid profile".into(),
+        });
+        let mut manager = Self {
+            link,
+            subscribers: Default::default(),
+            client,
+            transport: Pipeline::new(FetchTransport::default()).with(Logging::new("Login")),
+            local_storage,
+            session_storage,
+            session: None,
+            refresh: None,
+        };
+        manager.restore_session();
+        manager.finish_login();
+        manager
+    }
+
+    fn update(&mut self, msg: Self::Message) {
+        match msg {
+            Msg::Discovered(Ok(provider)) => self.redirect_to(&provider),
+            Msg::Discovered(Err(e)) => log::error!("Could not reach the login provider: {}", e),
+            Msg::LoggedIn(Ok(session)) | Msg::Refreshed(Ok(session)) => self.set_session(Some(session)),
+            Msg::LoggedIn(Err(e)) => log::error!("Login failed: {}", e),
+            Msg::Refresh => self.refresh_session(),
+            Msg::Refreshed(Err(e)) => {
+                log::error!("The login expired: {}", e);
+                self.set_session(None);
+            }
+        }
+    }
+
+    fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
+        log::debug!("AuthManager message: {:?}", msg);
+        match msg {
+            Incoming::GetIdentity => self.link.respond(id, Outgoing::Identity(self.identity())),
+            Incoming::Login => match &self.client {
+                Some(client) => {
+                    let request = oidc::discovery_request(&client.issuer);
+                    send_to_agent(&self.link, &self.transport, request, |outcome| {
+                        Msg::Discovered(outcome.and_then(|response| oidc::parse_provider(&response)))
+                    });
+                }
+                None => log::error!("No login provider is configured."),
+            },
+            Incoming::Logout => self.set_session(None),
+        }
+    }
+
+    fn connected(&mut self, id: HandlerId) {
+        self.subscribers.insert(id);
+    }
+
+    fn disconnected(&mut self, id: HandlerId) {
+        self.subscribers.remove(&id);
+    }
+}
+
+impl AuthManager {
+    fn identity(&self) -> Option<Identity> {
+        self.session.as_ref().map(|session| session.identity.clone())
+    }
+
+    fn restore_session(&mut self) {
+        let stored = self.local_storage.as_ref().and_then(|storage| {
+            let Json(session) = storage.restore(SESSION_KEY);
+            session.ok()
+        });
+        if let Some(session) = stored {
+            self.set_session(Some(session));
+            let expired = self.session.as_ref().is_some_and(|s| s.is_expired(chrono::Utc::now()));
+            if expired {
+                self.refresh_session();
+            }
+        }
+    }
+
+    /// Goes to the provider. It comes back to the redirect URI, which loads the application again.
+    fn redirect_to(&mut self, provider: &Provider) {
+        let client = match &self.client {
+            Some(client) => client,
+            None => return,
+        };
+        let (uri, pending) = oidc::start_login(client, provider);
+        if let Some(storage) = self.session_storage.as_mut() {
+            storage.store(PENDING_KEY, Json(&pending));
+        }
+        let location = web_sys::window().map(|window| window.location());
+        if let Err(e) = location.map(|location| location.set_href(&uri)).unwrap_or(Ok(())) {
+            log::error!("Could not go to the login provider: {:?}", e);
+        }
+    }
+
+    /// If the provider redirected back to the application, exchanges the code for tokens.
+    fn finish_login(&mut self) {
+        let (client, storage) = match (&self.client, self.session_storage.as_mut()) {
+            (Some(client), Some(storage)) => (client, storage),
+            _ => return,
+        };
+        let Json(pending) = storage.restore(PENDING_KEY);
+        let pending: PendingLogin = match pending {
+            Ok(pending) => pending,
+            Err(_) => return,
+        };
+        let query = web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .unwrap_or_default();
+        match oidc::parse_redirect(&query, &pending) {
+            Some(Ok(code)) => {
+                let token_endpoint = pending.token_endpoint.clone();
+                let request = oidc::token_request(client, &pending, &code);
+                send_to_agent(&self.link, &self.transport, request, move |outcome| {
+                    Msg::LoggedIn(outcome.and_then(|response| {
+                        oidc::parse_session(&response, &token_endpoint, None, chrono::Utc::now())
+                    }))
+                });
+            }
+            Some(Err(e)) => log::error!("{}", e),
+            None => return,
+        }
+        storage.remove(PENDING_KEY);
+        // the code can not be used twice, so it should not stay in the address bar.
+        if let Some(history) = web_sys::window().and_then(|window| window.history().ok()) {
+            history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some("/")).ok();
+        }
+    }
+
+    fn refresh_session(&mut self) {
+        let request = match (&self.client, &self.session) {
+            (Some(client), Some(session)) => oidc::refresh_request(client, session),
+            _ => None,
+        };
+        let request = match request {
+            Some(request) => request,
+            None => {
+                log::warn!("The login can not be refreshed and has to be repeated.");
+                self.set_session(None);
+                return;
+            }
+        };
+        let previous = self.session.clone();
+        send_to_agent(&self.link, &self.transport, request, move |outcome| {
+            Msg::Refreshed(outcome.and_then(|response| {
+                let previous = previous.as_ref();
+                let endpoint = previous.map(|p| p.token_endpoint.as_str()).unwrap_or_default();
+                oidc::parse_session(&response, endpoint, previous, chrono::Utc::now())
+            }))
+        });
+    }
+
+    /// Uses the session for all requests, or the configured credentials without session.
+    fn set_session(&mut self, session: Option<Session>) {
+        let credentials = session
+            .as_ref()
+            .map_or_else(Credentials::configured, |s| Credentials::Bearer(s.access_token.clone()));
+        authorization().set(credentials);
+        self.refresh = session.as_ref().and_then(Session::refresh_at).map(|at| {
+            let delay = (at - chrono::Utc::now()).to_std().unwrap_or_default();
+            TimeoutService::spawn(delay, self.link.callback(|_| Msg::Refresh))
+        });
+        if let Some(storage) = self.local_storage.as_mut() {
+            match &session {
+                Some(session) => storage.store(SESSION_KEY, Json(session)),
+                None => storage.remove(SESSION_KEY),
+            }
+        }
+        self.session = session;
+        for sub in self.subscribers.iter() {
+            self.link.respond(*sub, Outgoing::Identity(self.identity()));
+        }
+    }
+}
+
+/// The provider sends the user back to the start page of the application.
+/// The URI has to be registered for the client at the provider.
+fn redirect_uri() -> String {
+    web_sys::window()
+        .and_then(|window| window.location().origin().ok())
+        .map(|origin| format!("{}/", origin))
+        .unwrap_or_default()
+}
@@ -0,0 +1,247 @@
+use std::{convert::TryFrom, fmt::Display};
+
+use strum::IntoEnumIterator;
+use yew::{agent::Dispatcher, prelude::*};
+
+use crate::{
+    app_state::{
+        bulk_edit::{BulkEdit, LocationsChange, Shared},
+        pid_manager::{self, PidManager},
+    },
+    data_type_registry::{DigitalObjectType, HasProfileKey, Locations, Pid, Policy, Profile, Version},
+    pid_label::PidLabel,
+    pidinfo::PidInfo,
+};
+
+const KEEP: &str = "";
+/// Edits profile, type, policy, locations and version of several objects at once.
+/// Changed objects are marked as modified, so they can be published later.
+pub struct BulkEditor {
+    link: ComponentLink<Self>,
+    props: Props,
+    pid_manager: Dispatcher<PidManager>,
+
+    edit: BulkEdit,
+    /// The selected kind of change. Its locations stay empty, they are in `locations`.
+    locations_mode: LocationsChange,
+    locations: Vec<String>,
+    /// Number of objects changed by the last application.
+    changed: Option<usize>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    /// The selected objects.
+    pub records: Vec<PidInfo>,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    ProfileSelected(String),
+    TypeSelected(String),
+    PolicyChanged(String),
+    VersionChanged(String),
+    LocationsModeSelected(String),
+    LocationsChanged(String),
+    Apply,
+    Error(String),
+}
+
+impl Component for BulkEditor {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            props,
+            pid_manager: PidManager::dispatcher(),
+            edit: BulkEdit::default(),
+            locations_mode: LocationsChange::Keep,
+            locations: Vec::new(),
+            changed: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::ProfileSelected(value) => {
+                self.edit.profile = Self::non_empty(value).map(|pid| Profile::try_from(&Pid(pid)).map_err(Some));
+            }
+            Msg::TypeSelected(value) => {
+                self.edit.digital_object_type =
+                    Self::non_empty(value).map(|pid| DigitalObjectType::try_from(&Pid(pid)).map_err(Some));
+            }
+            Msg::PolicyChanged(value) => self.edit.policy = Self::non_empty(value).map(Policy),
+            Msg::VersionChanged(value) => self.edit.version = Self::non_empty(value).map(Version),
+            Msg::LocationsModeSelected(mode) => {
+                self.locations_mode = LocationsChange::iter().find(|m| m.to_string() == mode).unwrap_or_default();
+                self.update_locations_change();
+            }
+            Msg::LocationsChanged(text) => {
+                self.locations = text
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(String::from)
+                    .collect();
+                self.update_locations_change();
+            }
+            Msg::Apply => {
+                let changed: Vec<PidInfo> = self
+                    .props
+                    .records
+                    .iter()
+                    .cloned()
+                    .filter_map(|mut info| if self.edit.apply(&mut info) { Some(info) } else { None })
+                    .collect();
+                self.changed = Some(changed.len());
+                if !changed.is_empty() {
+                    self.pid_manager.send(pid_manager::Incoming::AddPidInfos(changed));
+                }
+            }
+            Msg::Error(e) => {
+                log::error!("Bulk editor error: {}", e);
+                return false;
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let records = &self.props.records;
+        if records.is_empty() {
+            return html! {
+                <div id="content" class="maincolumns scroll-vertical">
+                    <h2>{ "Bulk editing" }</h2>
+                    <p class="hint-text">{ "Select objects in the workspace to edit them together." }</p>
+                </div>
+            };
+        }
+        let profile = Shared::of(records.iter().map(|r| r.profile.clone()));
+        let digital_object_type = Shared::of(records.iter().map(|r| r.digital_object_type.clone()));
+        let policy = Shared::of(records.iter().map(|r| r.policy.0.clone()));
+        let version = Shared::of(records.iter().map(|r| r.version.0.clone()));
+        let locations = Shared::of(records.iter().map(|r| r.locations.urls().join("\n")));
+        let keep_locations = self.locations_mode == LocationsChange::Keep;
+        let on_locations_mode = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::LocationsModeSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        html! {
+            <div id="content" class="maincolumns scroll-vertical">
+                <h2>{ format!("Bulk editing {} objects", records.len()) }</h2>
+                <ul class="bulk-selection">
+                    { for records.iter().map(|r| html! { <li>{ r.pid() }{ " – " }{ r.describe() }</li> }) }
+                </ul>
+                <div class="two-column-lefty">
+                    <label class="form-description" for="bulk-profile"><PidLabel pid=Profile::get_key() fallback=Profile::get_key_name() /></label>
+                    <select class="form-input" id="bulk-profile"
+                            onchange=self.link.callback(|e: ChangeData| match e {
+                                ChangeData::Select(element) => Msg::ProfileSelected(element.value()),
+                                other => Msg::Error(format!("Got unexpected: {:?}", other))
+                            })>
+                        <option value=KEEP selected=self.edit.profile.is_none()>{ Self::keep_label(&profile.map(Self::name_of)) }</option>
+                        { for Profile::iter().map(|p| Self::view_option(p, Pid::from(&p), &self.edit.profile)) }
+                    </select>
+
+                    <label class="form-description" for="bulk-type"><PidLabel pid=DigitalObjectType::get_key() fallback=DigitalObjectType::get_key_name() /></label>
+                    <select class="form-input" id="bulk-type"
+                            onchange=self.link.callback(|e: ChangeData| match e {
+                                ChangeData::Select(element) => Msg::TypeSelected(element.value()),
+                                other => Msg::Error(format!("Got unexpected: {:?}", other))
+                            })>
+                        <option value=KEEP selected=self.edit.digital_object_type.is_none()>{ Self::keep_label(&digital_object_type.map(Self::name_of)) }</option>
+                        { for DigitalObjectType::iter().map(|t| Self::view_option(t, Pid::from(&t), &self.edit.digital_object_type)) }
+                    </select>
+
+                    <label class="form-description" for="bulk-policy"><PidLabel pid=Policy::get_key() fallback=Policy::get_key_name() /></label>
+                    <input class="form-input" id="bulk-policy" type="text"
+                        placeholder=Self::placeholder(&policy)
+                        value=self.edit.policy.as_ref().map(|p| p.0.clone()).unwrap_or_default()
+                        oninput=self.link.callback(|e: InputData| Msg::PolicyChanged(e.value)) />
+
+                    <label class="form-description" for="bulk-version"><PidLabel pid=Version::get_key() fallback=Version::get_key_name() /></label>
+                    <input class="form-input" id="bulk-version" type="text"
+                        placeholder=Self::placeholder(&version)
+                        value=self.edit.version.as_ref().map(|v| v.0.clone()).unwrap_or_default()
+                        oninput=self.link.callback(|e: InputData| Msg::VersionChanged(e.value)) />
+
+                    <label class="form-description" for="bulk-locations"><PidLabel pid=Locations::get_key() fallback=Locations::get_key_name() /></label>
+                    <div class="form-input bulk-locations">
+                        <select onchange=on_locations_mode>
+                            {
+                                for LocationsChange::iter().map(|mode| {
+                                    let selected = mode == self.locations_mode;
+                                    html! { <option selected=selected>{ mode }</option> }
+                                })
+                            }
+                        </select>
+                        <textarea id="bulk-locations" disabled=keep_locations
+                            placeholder=Self::placeholder(&locations)
+                            value=self.locations.join("\n")
+                            oninput=self.link.callback(|e: InputData| Msg::LocationsChanged(e.value)) />
+                    </div>
+                </div>
+                <div class="column-form">
+                    <button class="publish-button" disabled=self.edit.is_empty() onclick=self.link.callback(|_| Msg::Apply)>
+                        { format!("Apply to {} objects", records.len()) }
+                    </button>
+                    {
+                        for self.changed.iter().map(|n| html! {
+                            <p class="hint-text">{ format!("{} objects changed. They are marked as modified and can be published from their details page.", n) }</p>
+                        })
+                    }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl BulkEditor {
+    fn non_empty(value: String) -> Option<String> {
+        Some(value.trim().to_string()).filter(|v| !v.is_empty())
+    }
+
+    fn update_locations_change(&mut self) {
+        let urls = self.locations.clone();
+        self.edit.locations = match self.locations_mode {
+            LocationsChange::Keep => LocationsChange::Keep,
+            LocationsChange::Append(_) => LocationsChange::Append(urls),
+            LocationsChange::Replace(_) => LocationsChange::Replace(urls),
+        };
+    }
+
+    fn name_of<T: ToString>(value: Result<T, Option<Pid>>) -> String {
+        match value {
+            Ok(known) => known.to_string(),
+            Err(Some(pid)) => pid.0,
+            Err(None) => "none".into(),
+        }
+    }
+
+    fn keep_label(shared: &Shared<String>) -> String {
+        match shared {
+            Shared::Same(value) => format!("Keep ({})", value),
+            Shared::Mixed => "Keep (mixed)".into(),
+        }
+    }
+
+    fn placeholder(shared: &Shared<String>) -> String {
+        match shared {
+            Shared::Same(value) if value.is_empty() => "Keep (empty)".into(),
+            Shared::Same(value) => format!("Keep ({})", value.replace('\n', ", ")),
+            Shared::Mixed => "Keep (mixed)".into(),
+        }
+    }
+
+    fn view_option<T: PartialEq + Display>(value: T, pid: Pid, chosen: &Option<Result<T, Option<Pid>>>) -> Html {
+        let selected = matches!(chosen, Some(Ok(c)) if *c == value);
+        html! { <option value=pid.0 selected=selected>{ value }</option> }
+    }
+}
@@ -1,26 +1,33 @@
-pub mod collection;
-pub mod collection_capabilities;
-pub mod collection_properties;
-pub mod member_item;
-
-use std::collections::HashSet;
-
-use anyhow::Error;
-use serde_json::Value;
-use yew::{prelude::*, Callback, agent::Dispatcher, format::Json, services::FetchService, services::fetch, services::fetch::FetchTask, worker::*};
-
-use crate::app_state::{data::{Data, DataID}, data_manager::DataManager};
+pub use fairris_core::collection_service::*;
+
+use std::collections::{HashMap, HashSet};
+
+use yew::{prelude::*, agent::Dispatcher, worker::*};
+
+use crate::{
+    app_state::{
+        data::{Data, DataID},
+        data_manager::DataManager,
+        outbox::Operation,
+        outbox_manager::{self, OutboxManager},
+    },
+    service_communication::{
+        collection_api, secured_pipeline, send_to_agent,
+        transport::{transient_failure, Pipeline},
+    },
+};
 use collection::*;
-
-pub type Etag = http::header::HeaderValue;
-pub type MaybeEtag = Option<Etag>;
+use member_item::Member;
 
 pub struct CollectionService {
     link: AgentLink<CollectionService>,
     subscribers: HashSet<HandlerId>,
 
-    task: Option<FetchTask>,
+    transport: Pipeline,
     data_manager: Dispatcher<DataManager>,
+    outbox: Dispatcher<OutboxManager>,
+    /// Copies which are being registered, and the id of the collection they copy.
+    duplicates: HashMap<DataID, String>,
 }
 
 #[derive(Debug)]
@@ -28,6 +35,10 @@ pub enum Request {
     Register(Vec<(DataID, Collection)>),
     Push(DataID, Collection, Etag),
     Pull(DataID, Collection),
+    /// Registers a copy of the collection, including its members, with the given data id.
+    Duplicate(DataID, Collection),
+    /// Reads the members of the registered collection with the given id.
+    GetMembers(String),
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +46,14 @@ pub enum Response {
     Registered(Vec<(DataID, Collection)>),
     Pushed(DataID, Collection, MaybeEtag),
     Pulled(DataID, Collection, MaybeEtag),
+    /// Intermediate step of a duplication. Subscribers will not be notified.
+    MembersFetched(DataID, String, Vec<Member>),
+    /// The members of the registered collection with the given id.
+    Members(String, Vec<Member>),
+    /// The number of members added to the registered copy.
+    MembersCopied(DataID, usize),
+    /// The service was unreachable. The push is kept in the outbox and will be published later.
+    Queued(Operation, String),
     Error(String),
 }
 
@@ -48,8 +67,10 @@ impl Agent for CollectionService {
         Self {
             link,
             subscribers: HashSet::new(),
-            task: None,
+            transport: secured_pipeline("Collection service"),
             data_manager: DataManager::dispatcher(),
+            outbox: OutboxManager::dispatcher(),
+            duplicates: HashMap::new(),
         }
     }
 
@@ -58,18 +79,32 @@ impl Agent for CollectionService {
         let msg = match msg.clone() {
             Response::Registered(collections) => {
                 for (id, coll) in collections.iter() {
-                    self.data_manager.send(DataMsg::UpdateData(*id, Data::Collection(coll.clone())));
+                    self.data_manager.send(DataMsg::UpdateData(*id, Data::Collection(Box::new(coll.clone()))));
+                    let source = self.duplicates.remove(id);
+                    if let (Some(source), Some(target)) = (source, coll.get_id()) {
+                        self.fetch_members(*id, source, target.to_owned());
+                    }
                 }
                 Some(Response::Registered(collections))
             }
+            Response::MembersFetched(id, target, members) => {
+                self.add_members(id, target, members);
+                None
+            }
+            Response::MembersCopied(id, count) => Some(Response::MembersCopied(id, count)),
+            Response::Members(id, members) => Some(Response::Members(id, members)),
             Response::Pushed(id, coll, etag) => {
-                self.data_manager.send(DataMsg::UpdateData(id, Data::Collection(coll.clone())));
+                self.data_manager.send(DataMsg::UpdateData(id, Data::Collection(Box::new(coll.clone()))));
                 Some(Response::Pushed(id, coll, etag))
             }
             Response::Pulled(id, coll, etag) => {
-                self.data_manager.send(DataMsg::UpdateData(id, Data::Collection(coll.clone())));
+                self.data_manager.send(DataMsg::UpdateData(id, Data::Collection(Box::new(coll.clone()))));
                 Some(Response::Pulled(id, coll, etag))
             }
+            Response::Queued(operation, reason) => {
+                self.outbox.send(outbox_manager::Incoming::Queue(operation.clone(), reason.clone()));
+                Some(Response::Queued(operation, reason))
+            }
             Response::Error(msg) => {
                 log::error!("Collection Service Error: {}", msg);
                 None
@@ -103,6 +138,13 @@ impl Agent for CollectionService {
             Request::Pull(id, coll) => {
                 self.pull_collection(id, coll);
             }
+            Request::Duplicate(id, coll) => {
+                if let Some(source) = coll.get_id() {
+                    self.duplicates.insert(id, source.to_owned());
+                }
+                self.register_collections(vec![id], vec![coll.as_new()].into());
+            }
+            Request::GetMembers(id) => self.get_members(id),
         }
     }
 
@@ -117,190 +159,87 @@ impl Agent for CollectionService {
 
 impl CollectionService {
     pub fn register_collections(&mut self, ids: Vec<DataID>, collections: Collections) {
-        let callback = self.link.callback(
-            move |http_response: fetch::Response<Result<String, Error>>| {
-                if http_response.status().is_success() {
-                    let thing: serde_json::Result<Vec<Collection>> = serde_json::from_str(
-                        http_response
-                            .body()
-                            .as_ref()
-                            .expect("Get reference from body")
-                            .as_str(),
-                    );
-                    if let Ok(collections) = thing {
-                        let packed = ids
-                            .clone()
-                            .into_iter()
-                            .zip(collections.into_iter())
-                            .collect();
-                            Response::Registered(packed)
-                    } else {
-                        Response::Error(format!(
-                            "Could not parse collections from collection service: {:?}",
-                            thing
-                        ))
-                    }
-                } else {
-                    Response::Error(
-                        "Response of collection service was not successful.".to_string(),
-                    )
+        let request = collection_api::create_collections_request(Self::get_base_uri(), &collections);
+        send_to_agent(&self.link, &self.transport, request, move |outcome| {
+            match outcome.and_then(|response| collection_api::parse_collections(&response)) {
+                Ok(collections) => {
+                    let packed = ids
+                        .clone()
+                        .into_iter()
+                        .zip(collections)
+                        .collect();
+                    Response::Registered(packed)
                 }
-            },
-        );
-        let collections = &collections.into();
-        let request = Self::create_collections_request(&collections);
-        self.send_json_request(request, callback);
+                Err(e) => Response::Error(e),
+            }
+        });
     }
 
     pub fn push_collection(&mut self, id: DataID, collection: Collection, etag: Etag) {
-        let callback = self.link.callback(
-            move |http_response: fetch::Response<Result<String, Error>>| {
-                if http_response.status().is_success() {
-                    let thing: serde_json::Result<Collection> = serde_json::from_str(
-                        http_response
-                            .body()
-                            .as_ref()
-                            .expect("Get reference from body")
-                            .as_str(),
-                    );
-                    let etag = http_response.headers().get("ETag").cloned();
-                    if let Ok(collection) = thing {
-                        Response::Pushed(id, collection, etag)
-                    } else {
-                        Response::Error(format!(
-                            "Could not parse collections from collection service: {:?}",
-                            thing
-                        ))
-                    }
-                } else {
-                    Response::Error(
-                        "Response of collection service was not successful.".to_string(),
-                    )
+        match collection_api::push_collection_request(Self::get_base_uri(), &collection, &etag) {
+            Ok(request) => send_to_agent(&self.link, &self.transport, request, move |outcome| {
+                if let Some(reason) = transient_failure(&outcome) {
+                    let etag = etag.to_str().unwrap_or_default().to_owned();
+                    return Response::Queued(Operation::Push(id, Box::new(collection.clone()), etag), reason);
                 }
-            },
-        );
-        let collection_id = collection.get_id().map(|id| id.to_owned());
-        if let Some(id) = collection_id {
-            let collection = serde_json::to_value(collection).expect("Could not serialize collection.");
-            let request = Self::push_collection_request(id.as_str(), &collection, etag);
-            self.send_json_request(request, callback);
-        } else {
-            log::error!("Tried to push (update) a collection without id (not registered).")
+                outcome
+                    .and_then(|response| collection_api::parse_collection(&response))
+                    .map(|(collection, etag)| Response::Pushed(id, collection, etag))
+                    .unwrap_or_else(Response::Error)
+            }),
+            Err(e) => log::error!("{}", e),
         }
     }
 
     pub fn pull_collection(&mut self, id: DataID, collection: Collection) {
-        let callback = self.link.callback(
-            move |http_response: fetch::Response<Result<String, Error>>| {
-                if http_response.status().is_success() {
-                    let thing: serde_json::Result<Collection> = serde_json::from_str(
-                        http_response
-                            .body()
-                            .as_ref()
-                            .expect("Get reference from body")
-                            .as_str(),
-                    );
-                    let etag = http_response.headers().get("ETag").cloned();
-                    if let Ok(collection) = thing {
-                        Response::Pulled(id, collection, etag)
-                    } else {
-                        Response::Error(format!(
-                            "Could not parse collections from collection service: {:?}",
-                            thing
-                        ))
-                    }
-                } else {
-                    Response::Error(
-                        "Response of collection service was not successful.".to_string(),
-                    )
-                }
-            },
-        );
-        let collection_id = collection.get_id().map(|id| id.to_owned());
-        if let Some(id) = collection_id {
-            let request = Self::pull_collection_request(id.as_str());
-            self.send_empty_request(request, callback);
+        if let Some(collection_id) = collection.get_id() {
+            let request = collection_api::pull_collection_request(Self::get_base_uri(), collection_id);
+            send_to_agent(&self.link, &self.transport, request, move |outcome| {
+                outcome
+                    .and_then(|response| collection_api::parse_collection(&response))
+                    .map(|(collection, etag)| Response::Pulled(id, collection, etag))
+                    .unwrap_or_else(Response::Error)
+            });
         } else {
             log::error!("Tried to pull (download) a collection without id (not registered).")
         }
     }
 
-    fn send_json_request(
-        &mut self,
-        request: fetch::Request<Json<&Value>>,
-        callback: Callback<fetch::Response<Result<String, Error>>>,
-    ) {
-        self.task = FetchService::fetch(request, callback)
-            .map_err(|e| log::error!("Error creating task to register metadata: {}", e))
-            .ok();
-    }
-
-    fn send_empty_request(
-        &mut self,
-        request: fetch::Request<yew::format::Nothing>,
-        callback: Callback<fetch::Response<Result<String, Error>>>,
-    ) {
-        self.task = FetchService::fetch(request, callback)
-            .map_err(|e| log::error!("Error creating task to register metadata: {}", e))
-            .ok();
-    }
-
-    fn create_collections_request(collections: &serde_json::Value) -> fetch::Request<Json<&Value>> {
-        fetch::Request::post(Self::get_create_collections_uri())
-            .header("Content-Type", "application/json")
-            .body(Json(collections))
-            .expect("Failed to build this request.")
+    /// Reads all members of the source collection, so they can be added to the copy.
+    fn fetch_members(&mut self, id: DataID, source: String, target: String) {
+        let request = collection_api::members_request(Self::get_base_uri(), &source);
+        send_to_agent(&self.link, &self.transport, request, move |outcome| {
+            match outcome.and_then(|response| collection_api::parse_members(&response)) {
+                Ok(members) => Response::MembersFetched(id, target.clone(), members),
+                Err(e) => Response::Error(format!("Could not read members of collection {}: {}", source, e)),
+            }
+        });
     }
 
-    fn push_collection_request<'a>(id: &str, collection: &'a serde_json::Value, etag: Etag) -> fetch::Request<Json<&'a Value>> {
-        fetch::Request::put(Self::get_collection_uri(id))
-            .header("Content-Type", "application/json")
-            .header("IF-Match", etag)
-            .body(Json(collection))
-            .expect("Failed to build this request.")
+    pub fn get_members(&mut self, id: String) {
+        let request = collection_api::members_request(Self::get_base_uri(), &id);
+        send_to_agent(&self.link, &self.transport, request, move |outcome| {
+            match outcome.and_then(|response| collection_api::parse_members(&response)) {
+                Ok(members) => Response::Members(id.clone(), members),
+                Err(e) => Response::Error(format!("Could not read members of collection {}: {}", id, e)),
+            }
+        });
     }
 
-    fn pull_collection_request(id: &str) -> fetch::Request<yew::format::Nothing> {
-        fetch::Request::get(Self::get_collection_uri(id))
-            //.header("Content-Type", "application/json")
-            .body(yew::format::nothing::Nothing)
-            .expect("Failed to build this request.")
+    fn add_members(&mut self, id: DataID, target: String, members: Vec<Member>) {
+        let count = members.len();
+        if count == 0 {
+            self.link.send_message(Response::MembersCopied(id, 0));
+            return;
+        }
+        let request = collection_api::add_members_request(Self::get_base_uri(), &target, &members);
+        send_to_agent(&self.link, &self.transport, request, move |outcome| match outcome {
+            Ok(response) if response.status().is_success() => Response::MembersCopied(id, count),
+            _ => Response::Error("Members could not be added to the copied collection.".to_string()),
+        });
     }
 
-    // TODO make configurable
     fn get_base_uri() -> &'static str {
-        "http://localhost:8091/api/v1"
-    }
-
-    /// POST: create one or more collections.
-    /// GET: get collections within this API.
-    fn get_create_collections_uri() -> String {
-        format!("{}/{}/", Self::get_base_uri(), "collections")
-    }
-
-    /// GET: get the collection
-    /// PUT: update the collections properties
-    /// DELETE: delete the collection
-    fn get_collection_uri(id: &str) -> String {
-        format!("{}/{}/{}", Self::get_base_uri(), "collections", id)
-    }
-}
-
-impl From<Collections> for serde_json::Value {
-    fn from(cs: Collections) -> Self {
-        serde_json::Value::Array(
-            cs.iter()
-                .map(|c| serde_json::to_value(c))
-                .filter(|result| {
-                    if let Ok(_) = result {
-                        true
-                    } else {
-                        log::error!("Own collection could not be serialized: {:?}", result);
-                        false
-                    }
-                })
-                .map(|ok| ok.unwrap())
-                .collect(),
-        )
+        crate::config::collection_base_uri()
     }
 }
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use yew::prelude::*;
+
+use crate::{
+    data_type_registry::{Contributor, Contributors, HasProfileKey, Identifier, CONTRIBUTOR_ROLES},
+    identity_lookup::{self, IdentityLookup},
+};
+
+use super::DetailsPage;
+
+pub struct ContributorsEditor {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    lookup: Box<dyn Bridge<IdentityLookup>>,
+    names: HashMap<Identifier, Option<String>>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub active: bool,
+    pub form_link: ComponentLink<DetailsPage>,
+    pub contributors: Contributors,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    Add,
+    Remove(usize),
+    MoveUp(usize),
+    MoveDown(usize),
+    IdentifierChanged(usize, String),
+    RoleChanged(usize, String),
+    Name(Identifier, Option<String>),
+    Error(String),
+}
+
+impl Component for ContributorsEditor {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let lookup = IdentityLookup::bridge(link.callback(|response| match response {
+            identity_lookup::Response::Name(identifier, name) => Msg::Name(identifier, name),
+        }));
+        let mut editor = Self {
+            link,
+            props,
+            lookup,
+            names: Default::default(),
+        };
+        editor.lookup_names();
+        editor
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let list = &mut self.props.contributors.0;
+        match msg {
+            Msg::Add => list.push(Contributor::default()),
+            Msg::Remove(i) if i < list.len() => {
+                list.remove(i);
+            }
+            Msg::MoveUp(i) if i > 0 && i < list.len() => list.swap(i - 1, i),
+            Msg::MoveDown(i) if i + 1 < list.len() => list.swap(i, i + 1),
+            Msg::IdentifierChanged(i, identifier) if i < list.len() => {
+                // store the canonical form, if the identifier is valid
+                list[i].identifier = Identifier::parse(&identifier)
+                    .map(|id| id.to_uri())
+                    .unwrap_or(identifier);
+            }
+            Msg::RoleChanged(i, role) if i < list.len() => list[i].role = role,
+            Msg::Name(identifier, name) => {
+                self.names.insert(identifier, name);
+                return true;
+            }
+            Msg::Error(e) => {
+                log::error!("Contributors editor error: {}", e);
+                return false;
+            }
+            other => {
+                log::error!("Message not handled: {:?}", other);
+                return false;
+            }
+        }
+        self.lookup_names();
+        self.props
+            .form_link
+            .send_message(super::Msg::ContributorsChanged(self.props.contributors.clone()));
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        self.lookup_names();
+        true
+    }
+
+    fn view(&self) -> Html {
+        let name = Contributors::get_key_name();
+        html! {
+            <>
+                <label class="form-description">{ name }</label>
+                <div class="form-input contributors">
+                    { for self.props.contributors.0.iter().enumerate().map(|(i, c)| self.view_contributor(i, c)) }
+                    <button disabled=!self.props.active onclick=self.link.callback(|_| Msg::Add)>{ "Add contributor" }</button>
+                </div>
+            </>
+        }
+    }
+}
+
+impl ContributorsEditor {
+    fn lookup_names(&mut self) {
+        for contributor in self.props.contributors.0.iter() {
+            if let Ok(identifier) = contributor.validate() {
+                if !self.names.contains_key(&identifier) {
+                    self.names.insert(identifier.clone(), None);
+                    self.lookup
+                        .send(identity_lookup::Request::Lookup(identifier));
+                }
+            }
+        }
+    }
+
+    fn view_contributor(&self, i: usize, contributor: &Contributor) -> Html {
+        let disabled = !self.props.active;
+        let on_identifier = self.link.callback(move |e: ChangeData| match e {
+            ChangeData::Value(identifier) => Msg::IdentifierChanged(i, identifier),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let on_role = self.link.callback(move |e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::RoleChanged(i, element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let validation = match contributor.validate() {
+            Ok(identifier) => self
+                .names
+                .get(&identifier)
+                .cloned()
+                .flatten()
+                .map_or_else(|| html! {}, |name| html! { <span class="hint-text">{ name }</span> }),
+            Err(e) => html! { <span class="error-text">{ e }</span> },
+        };
+        let mut roles: Vec<&str> = CONTRIBUTOR_ROLES.to_vec();
+        if !roles.contains(&contributor.role.as_str()) {
+            roles.push(contributor.role.as_str());
+        }
+        html! {
+            <div class="contributor-row">
+                <input value=contributor.identifier.clone() disabled=disabled onchange=on_identifier
+                    placeholder="ORCID, ROR or PID" />
+                <select disabled=disabled onchange=on_role>
+                    {
+                        for roles.into_iter().map(|role| {
+                            let selected = role == contributor.role;
+                            html! { <option value=role selected=selected>{ role }</option> }
+                        })
+                    }
+                </select>
+                <button disabled=disabled onclick=self.link.callback(move |_| Msg::MoveUp(i))>{ "↑" }</button>
+                <button disabled=disabled onclick=self.link.callback(move |_| Msg::MoveDown(i))>{ "↓" }</button>
+                <button disabled=disabled onclick=self.link.callback(move |_| Msg::Remove(i))>{ "✗" }</button>
+                { validation }
+            </div>
+        }
+    }
+}
@@ -63,7 +63,7 @@ impl Component for AnnotatedImageForm {
         let anno_urls = self.props.image.annotation_urls.join("\n");
         let on_anno_url_changed = self.link.callback(|change: ChangeData| match change {
             ChangeData::Value(urls) => {
-                let list: Vec<String> = urls.split("\n").map(|s| String::from(s)).collect();
+                let list: Vec<String> = urls.split("\n").map(String::from).collect();
                 Msg::AnnotationUrlsChanged(list)
             },
             other => Msg::Error(format!("Got unexpected value: {:?}", other))
@@ -29,7 +29,12 @@ pub enum Msg {
     // PropertiesChanged as sub-component?
     // CapabilitiesChanged as sub-component?
     PublishClicked,
-    Update(DataID, Collection, MaybeEtag),
+    Update(DataID, Box<Collection>, MaybeEtag),
+    /// Members were added to a copied collection, which changes its etag.
+    MembersChanged(DataID),
+    /// The push will be published later.
+    Queued(String),
+    Noop,
     Error(String),
 }
 
@@ -40,11 +45,15 @@ impl Component for CollectionForm {
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         let collection_service =
             CollectionService::bridge(link.callback(|response| match response {
-                collection_service::Response::Registered(collections) => {
+                collection_service::Response::Registered(_) => {
                     Msg::Error("Unimplemented".into())
                 }
-                collection_service::Response::Pushed(id, coll, etag) => Msg::Update(id, coll, etag),
-                collection_service::Response::Pulled(id, coll, etag) => Msg::Update(id, coll, etag),
+                collection_service::Response::Pushed(id, coll, etag) => Msg::Update(id, Box::new(coll), etag),
+                collection_service::Response::Pulled(id, coll, etag) => Msg::Update(id, Box::new(coll), etag),
+                collection_service::Response::MembersCopied(id, _) => Msg::MembersChanged(id),
+                collection_service::Response::MembersFetched(..) => Msg::Error("Unexpected intermediate response.".into()),
+                collection_service::Response::Members(..) => Msg::Noop,
+                collection_service::Response::Queued(_, reason) => Msg::Queued(reason),
                 collection_service::Response::Error(e) => Msg::Error(e),
             }));
 
@@ -70,7 +79,7 @@ impl Component for CollectionForm {
                 self.update_data();
             }
             Msg::PublishClicked => {
-                let id = self.props.id.clone();
+                let id = self.props.id;
                 if self.props.collection.get_id().is_none() {
                     // register collection
                     self.collection_service
@@ -99,11 +108,18 @@ impl Component for CollectionForm {
                 self.update_data();
             }
             Msg::Error(e) => log::error!("Error: {}", e),
+            Msg::Queued(reason) => log::warn!("Collection will be pushed later: {}", reason),
+            Msg::Noop => return false,
+            Msg::MembersChanged(id) => {
+                if self.props.id == id {
+                    self.pull_collection();
+                }
+            }
             Msg::Update(id, collection, etag) => {
                 if self.props.id == id {
                     if let Some(etag) = etag {
                         self.etag = Some((id, etag));
-                        self.props.collection = collection
+                        self.props.collection = *collection
                     } else {
                         log::error!("Did not receive an etag! Current is: {:?}", self.etag);
                     }
@@ -126,7 +142,7 @@ impl Component for CollectionForm {
         let etag_exists = self
             .etag
             .clone()
-            .map_or(false, |(id, _)| id == self.props.id);
+            .is_some_and(|(id, _)| id == self.props.id);
         let changed = self.props.neq_assign(props);
         if changed_id || !etag_exists {
             self.pull_collection();
@@ -144,8 +160,7 @@ impl Component for CollectionForm {
             .props
             .collection
             .description
-            .as_ref()
-            .map(|desc| desc.as_str())
+            .as_deref()
             .unwrap_or("");
         let on_description_changed = self.link.callback(|c: ChangeData| match c {
             ChangeData::Value(description) => Msg::DescriptionChanged(description),
@@ -198,7 +213,7 @@ const DESCRIPTION_FIELD: &str = "description_field";
 impl CollectionForm {
     fn update_data(&mut self) {
         use crate::app_state::data::Data;
-        let data = Data::Collection(self.props.collection.clone());
+        let data = Data::Collection(Box::new(self.props.collection.clone()));
         let id = self.props.id;
         //self.props
         //    .detail_page
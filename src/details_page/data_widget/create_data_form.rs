@@ -43,7 +43,7 @@ impl Component for CreateData {
             Msg::ButtonClick => {
                 let maybe_data = Data::try_from(&self.datatype).ok();
                 self.data_manager.send( Incoming::AddAndSelectData(maybe_data.clone()));
-                if let None = maybe_data {
+                if maybe_data.is_none() {
                     log::error!("Could not parse a data entry from {}", &self.datatype);
                 };
             }
@@ -1,6 +1,6 @@
-mod data_widget;
+mod widget;
 mod create_data_form;
 mod annotated_image_form;
 mod collection_form;
 
-pub use data_widget::*;
\ No newline at end of file
+pub use widget::*;
\ No newline at end of file
@@ -5,7 +5,7 @@ use yew::prelude::*;
 use super::annotated_image_form::*;
 use super::collection_form::*;
 use super::create_data_form::*;
-use crate::{DetailsPage, app_state::{data::{Data, DataID}, data_manager::DataManager}, details_page};
+use crate::{details_page::DetailsPage, app_state::{data::{Data, DataID}, data_manager::DataManager}, details_page};
 
 pub struct DataWidget {
     link: ComponentLink<Self>,
@@ -86,7 +86,7 @@ impl Component for DataWidget {
     }
 
     fn view(&self) -> Html {
-        let nothing_is_selected = self.data == None;
+        let nothing_is_selected = self.data.is_none();
         let on_selection = self.link.callback(|value: ChangeData| match value {
             // e will be a data id or "new" as you can see in the code below.
             ChangeData::Select(element) if element.value() == "new" => Msg::DataEmpty,
@@ -95,14 +95,14 @@ impl Component for DataWidget {
         });
         let data_list = self.data_list.iter()
             .map(|(dataid, data)| {
-                let selected = self.data.clone().and_then(|(id, _data)| Some(*dataid == id)).unwrap_or(false);
+                let selected = self.data.clone().is_some_and(|(id, _data)| *dataid == id);
                 // TODO type name is not a good description.
                 html! { <option value=dataid selected=selected>{ format!("{} - {}", **dataid, data.type_name()) }</option> }
             });
         let content_form = match &self.data {
             None => html! {<CreateData/>},
             Some((id, Data::AnnotatedImage(image))) => html! {<AnnotatedImageForm id=id image=image />},
-            Some((id, Data::Collection(collection))) => html! {<CollectionForm id=id collection=collection />},
+            Some((id, Data::Collection(collection))) => html! {<CollectionForm id=id collection=(**collection).clone() />},
         };
         html! {
             <details>
@@ -135,8 +135,8 @@ const DATA_CHOOSER_NAME: &str = "data_chooser";
 
 impl DataWidget {
     fn update_dropdown(&mut self) {
-        use crate::details_page::helpers::DOM;
-        let dropdown = DOM::get_element::<web_sys::HtmlSelectElement, _>(DATA_CHOOSER_NAME);
+        use crate::details_page::helpers::Dom;
+        let dropdown = Dom::get_element::<web_sys::HtmlSelectElement, _>(DATA_CHOOSER_NAME);
         // TODO the unused result warning should remember you to also display a missing or unknown type.
         self.data.clone().map_or_else(
             || dropdown.set_value("new"),
@@ -1,10 +1,8 @@
-use std::convert::TryFrom;
-
 use yew::prelude::*;
 
-use crate::data_type_registry::{DateCreated, DateTimeHandle, HasProfileKey};
+use crate::{data_type_registry::{DateCreated, DateTimeHandle, HasProfileKey}, pid_label::PidLabel};
 
-use super::DetailsPage;
+use super::{DetailsPage, date_time_picker::DateTimePicker};
 
 pub struct DateCreatedInput {
     link: ComponentLink<Self>,
@@ -16,12 +14,14 @@ pub struct Props {
     pub active: bool,
     pub form_link: ComponentLink<DetailsPage>,
     pub date_created: DateCreated,
+    /// Set if the published record contains an invalid date.
+    #[prop_or_default]
+    pub stored_error: Option<String>,
 }
 
 #[derive(Debug)]
 pub enum Msg {
-    Value(String),
-    Error(String),
+    Value(DateTimeHandle),
 }
 
 impl Component for DateCreatedInput {
@@ -33,21 +33,11 @@ impl Component for DateCreatedInput {
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
-        match msg {
-            Msg::Value(string) => {
-                let handle = DateTimeHandle::try_from(&string);
-                if let Ok(date) = handle {
-                    self.props
-                        .form_link
-                        .send_message(super::Msg::DateCreatedChanged(DateCreated(date)));
-                } else {
-                    // TODO handle wrong input
-                    log::error!("Could not parse date.");
-                }
-            }
-            other => log::error!("Message not handled: {:?}", other),
-        }
-        true
+        let Msg::Value(date) = msg;
+        self.props
+            .form_link
+            .send_message(super::Msg::DateCreatedChanged(DateCreated(date)));
+        false
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
@@ -57,18 +47,12 @@ impl Component for DateCreatedInput {
 
     fn view(&self) -> Html {
         let name = DateCreated::get_key_name();
-        let content = self.props.date_created.0.to_string();
         html! {
             <>
-                <label class="form-description" for=name>{ name }</label>
-                <input class="form-input" id=name disabled=!self.props.active value=content
-                    onchange=self.link.callback(|e: ChangeData| match e {
-                        ChangeData::Value(datestring) => {
-                            Msg::Value(datestring)
-                        },
-                        other => Msg::Error(format!("Got unexpected: {:?}", other))
-                    })
-                />
+                <label class="form-description" for=name><PidLabel pid=DateCreated::get_key() fallback=name /></label>
+                <DateTimePicker id=name active=self.props.active value=self.props.date_created.0.clone()
+                    stored_error=self.props.stored_error.clone()
+                    on_change=self.link.callback(Msg::Value) />
             </>
         }
     }
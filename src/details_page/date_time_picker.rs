@@ -0,0 +1,141 @@
+use chrono::{FixedOffset, Local, Offset};
+use yew::prelude::*;
+
+use crate::data_type_registry::{utc_offset, DateError, DateTimeHandle};
+
+/// Edits a date with a calendar or as (partial) ISO 8601 text.
+/// Both are shown in the selected timezone. Invalid text is shown with an error
+/// and not handed to the parent.
+pub struct DateTimePicker {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    timezone: FixedOffset,
+    text: String,
+    error: Option<DateError>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub id: String,
+    pub active: bool,
+    pub value: DateTimeHandle,
+    pub on_change: Callback<DateTimeHandle>,
+    /// Shown if the published record contains a date which could not be read.
+    #[prop_or_default]
+    pub stored_error: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    Picked(String),
+    Typed(String),
+    TimezoneSelected(String),
+    Error(String),
+}
+
+impl Component for DateTimePicker {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let timezone = Local::now().offset().fix();
+        let text = props.value.format_in(timezone);
+        Self {
+            link,
+            props,
+            timezone,
+            text,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Picked(text) | Msg::Typed(text) => {
+                match DateTimeHandle::parse(&text, self.timezone) {
+                    Ok(date) => {
+                        self.error = None;
+                        self.text = date.format_in(self.timezone);
+                        self.props.on_change.emit(date);
+                    }
+                    Err(e) => {
+                        self.error = Some(e);
+                        self.text = text;
+                    }
+                }
+            }
+            Msg::TimezoneSelected(seconds) => {
+                self.timezone = seconds
+                    .parse()
+                    .ok()
+                    .and_then(FixedOffset::east_opt)
+                    .unwrap_or_else(utc_offset);
+                if self.error.is_none() {
+                    self.text = self.props.value.format_in(self.timezone);
+                }
+            }
+            Msg::Error(e) => log::error!("Date picker error: {}", e),
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props.value != self.props.value || props.active != self.props.active {
+            self.error = None;
+            self.text = props.value.format_in(self.timezone);
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let on_timezone = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::TimezoneSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let error = self
+            .error
+            .as_ref()
+            .map(|e| e.to_string())
+            .or_else(|| self.props.stored_error.clone());
+        html! {
+            <div class="form-input date-time-picker">
+                <input type="datetime-local" disabled=!self.props.active
+                    value=self.props.value.format_local_input(self.timezone)
+                    onchange=self.link.callback(|e: ChangeData| match e {
+                        ChangeData::Value(value) => Msg::Picked(value),
+                        other => Msg::Error(format!("Got unexpected: {:?}", other)),
+                    }) />
+                <select disabled=!self.props.active onchange=on_timezone title="Timezone">
+                    { for Self::timezones().into_iter().map(|(label, offset)| {
+                        let selected = offset == self.timezone;
+                        html! { <option value=offset.local_minus_utc().to_string() selected=selected>{ label }</option> }
+                    }) }
+                </select>
+                <input type="text" id=self.props.id.clone() disabled=!self.props.active value=self.text.clone()
+                    placeholder="ISO 8601, i.e. 2021-03-04 or 2021-03"
+                    onchange=self.link.callback(|e: ChangeData| match e {
+                        ChangeData::Value(value) => Msg::Typed(value),
+                        other => Msg::Error(format!("Got unexpected: {:?}", other)),
+                    }) />
+                { for error.iter().map(|e| html! { <p class="error-text">{ e }</p> }) }
+            </div>
+        }
+    }
+}
+
+impl DateTimePicker {
+    /// The local timezone of the browser, followed by UTC-12:00 to UTC+14:00.
+    fn timezones() -> Vec<(String, FixedOffset)> {
+        let local = Local::now().offset().fix();
+        let mut timezones = vec![(format!("Local (UTC{})", local), local)];
+        timezones.extend(
+            (-12..=14)
+                .filter_map(|hours| FixedOffset::east_opt(hours * 3600))
+                .filter(|offset| *offset != local)
+                .map(|offset| (format!("UTC{}", offset), offset)),
+        );
+        timezones
+    }
+}
@@ -1,6 +1,6 @@
 use yew::prelude::*;
 
-use crate::data_type_registry::{Etag, HasProfileKey};
+use crate::{data_type_registry::{Etag, HasProfileKey}, pid_label::PidLabel};
 
 use super::DetailsPage;
 
@@ -37,7 +37,7 @@ impl Component for EtagInput {
                     .form_link
                     .send_message(super::Msg::EtagChanged(Etag(etag)));
             }
-            other => log::error!("Message not handled: {:?}", other),
+            Msg::Error(e) => log::error!("Error: {}", e),
         }
         true
     }
@@ -52,7 +52,7 @@ impl Component for EtagInput {
         let content = self.props.etag.0.as_str();
         html! {
             <>
-                <label class="form-description" for=name>{ name }</label>
+                <label class="form-description" for=name><PidLabel pid=Etag::get_key() fallback=name /></label>
                 <input class="form-input" id=name disabled=!self.props.active value=content
                     onchange=self.link.callback(|e: ChangeData| match e {
                         ChangeData::Value(etag) => {
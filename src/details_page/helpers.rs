@@ -1,6 +1,6 @@
-pub struct DOM;
+pub struct Dom;
 
-impl DOM {
+impl Dom {
     pub fn get_element<T, I>(id: I) -> T
     where
         I: AsRef<str>,
@@ -14,4 +14,29 @@ impl DOM {
             x.unchecked_into::<T>()
         }
     }
+
+    /// Offers the given content as a file download to the user.
+    pub fn download<N>(filename: N, mime: &str, content: &[u8]) -> Result<(), wasm_bindgen::JsValue>
+    where
+        N: AsRef<str>,
+    {
+        use wasm_bindgen::JsCast;
+        let window = web_sys::window().expect("no global `window` exists");
+        let document = window.document().expect("should have a document on window");
+
+        let bytes = js_sys::Uint8Array::from(content);
+        let parts = js_sys::Array::of1(&bytes);
+        let mut options = web_sys::BlobPropertyBag::new();
+        options.type_(mime);
+        let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+        let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+        let anchor = document
+            .create_element("a")?
+            .unchecked_into::<web_sys::HtmlAnchorElement>();
+        anchor.set_href(&url);
+        anchor.set_download(filename.as_ref());
+        anchor.click();
+        web_sys::Url::revoke_object_url(&url)
+    }
 }
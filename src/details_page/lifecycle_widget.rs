@@ -0,0 +1,189 @@
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+use crate::{
+    app_state::lifecycle::{LocationHandling, Retirement},
+    data_type_registry::{ObjectStatus, Pid},
+    pidinfo::PidInfo,
+};
+
+use super::DetailsPage;
+
+/// Deprecates or withdraws the object, or reactivates it.
+pub struct LifecycleWidget {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    retirement: Retirement,
+    error: Option<String>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub active: bool,
+    pub form_link: ComponentLink<DetailsPage>,
+    pub record: PidInfo,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    StatusSelected(String),
+    TombstoneChanged(String),
+    SuccessorChanged(String),
+    LocationHandlingSelected(String),
+    RedirectChanged(String),
+    Apply,
+    Error(String),
+}
+
+impl Component for LifecycleWidget {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let retirement = Retirement::of(&props.record);
+        Self {
+            link,
+            props,
+            retirement,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::StatusSelected(status) => {
+                self.retirement.status = ObjectStatus::parse(&status).unwrap_or_default();
+            }
+            Msg::TombstoneChanged(text) => {
+                self.retirement.tombstone = Some(text).filter(|t| !t.trim().is_empty());
+            }
+            Msg::SuccessorChanged(pid) => {
+                let pid = pid.trim();
+                self.retirement.superseded_by = Some(Pid(pid.into())).filter(|_| !pid.is_empty());
+            }
+            Msg::LocationHandlingSelected(name) => {
+                self.retirement.locations = if name == LocationHandling::Clear.to_string() {
+                    LocationHandling::Clear
+                } else if name == LocationHandling::Redirect(String::new()).to_string() {
+                    let target = self
+                        .retirement
+                        .superseded_by
+                        .as_ref()
+                        .map(Pid::to_url)
+                        .unwrap_or_default();
+                    LocationHandling::Redirect(target)
+                } else {
+                    LocationHandling::Keep
+                };
+            }
+            Msg::RedirectChanged(url) => self.retirement.locations = LocationHandling::Redirect(url),
+            Msg::Apply => match self.retirement.validate() {
+                Ok(()) => {
+                    self.error = None;
+                    self.props
+                        .form_link
+                        .send_message(super::Msg::LifecycleChanged(self.retirement.clone()));
+                }
+                Err(e) => self.error = Some(e),
+            },
+            Msg::Error(e) => log::error!("Lifecycle widget error: {}", e),
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let lifecycle_changed = Retirement::of(&self.props.record) != Retirement::of(&props.record);
+        if lifecycle_changed || self.props.active != props.active {
+            self.retirement = Retirement::of(&props.record);
+            self.error = None;
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let record = &self.props.record;
+        html! {
+            <details open=record.status.is_tombstoned()>
+                <summary>{ "Lifecycle" }</summary>
+                <div class="two-column-lefty">
+                    <p class="align-right">{ "Status:" }</p>
+                    <p>{ record.status.to_string() }</p>
+                    <p class="align-right">{ "Tombstone:" }</p>
+                    <p>{ record.tombstone.0.clone().unwrap_or_else(|| "-".into()) }</p>
+                    <p class="align-right">{ "Superseded by:" }</p>
+                    <p>{ record.superseded_by.0.as_ref().map(|pid| pid.0.clone()).unwrap_or_else(|| "-".into()) }</p>
+                </div>
+                { if self.props.active { self.view_editor() } else { html! {} } }
+            </details>
+        }
+    }
+}
+
+impl LifecycleWidget {
+    fn view_editor(&self) -> Html {
+        let on_status = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::StatusSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let on_handling = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::LocationHandlingSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let handlings = [
+            LocationHandling::Keep,
+            LocationHandling::Clear,
+            LocationHandling::Redirect(String::new()),
+        ];
+        let tombstone = self.retirement.tombstone.clone().unwrap_or_default();
+        let successor = self
+            .retirement
+            .superseded_by
+            .as_ref()
+            .map(|pid| pid.0.clone())
+            .unwrap_or_default();
+        html! {
+            <div class="lifecycle-editor">
+                <select onchange=on_status title="Status">
+                    {
+                        for ObjectStatus::iter().map(|status| {
+                            let selected = status == self.retirement.status;
+                            html! { <option selected=selected>{ status }</option> }
+                        })
+                    }
+                </select>
+                <input placeholder="Superseded by (PID)" value=successor
+                    oninput=self.link.callback(|e: InputData| Msg::SuccessorChanged(e.value)) />
+                <select onchange=on_handling title="Locations">
+                    {
+                        for handlings.iter().map(|handling| {
+                            let selected = handling.to_string() == self.retirement.locations.to_string();
+                            html! { <option selected=selected>{ handling }</option> }
+                        })
+                    }
+                </select>
+                {
+                    if let LocationHandling::Redirect(url) = &self.retirement.locations {
+                        html! {
+                            <input placeholder="Redirect to (URL)" value=url
+                                oninput=self.link.callback(|e: InputData| Msg::RedirectChanged(e.value)) />
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                <textarea placeholder="Tombstone text" value=tombstone
+                    oninput=self.link.callback(|e: InputData| Msg::TombstoneChanged(e.value)) />
+                <button onclick=self.link.callback(|_| Msg::Apply)>{ "Apply status" }</button>
+                {
+                    if let Some(e) = &self.error {
+                        html! { <p class="error-text">{ e }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+}
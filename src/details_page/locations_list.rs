@@ -1,12 +1,27 @@
-use yew::prelude::*;
+use anyhow::Error;
+use strum::IntoEnumIterator;
+use yew::{
+    format::Nothing,
+    prelude::*,
+    services::fetch::{self, FetchService, FetchTask},
+    services::Task,
+};
 
-use crate::data_type_registry::{HasProfileKey, Locations};
+use crate::{
+    data_type_registry::{HasProfileKey, Location, LocationCheck, Locations, Protocol},
+    pid_label::PidLabel,
+    service_communication::from_fetch,
+};
 
 use super::DetailsPage;
 
+/// Edits the locations of an object in priority order.
+/// Each location has a protocol and can be checked for reachability with a HEAD request.
 pub struct LocationsList {
     link: ComponentLink<Self>,
     props: Props,
+
+    tasks: Vec<FetchTask>,
 }
 
 #[derive(Properties, Clone)]
@@ -18,7 +33,15 @@ pub struct Props {
 
 #[derive(Debug)]
 pub enum Msg {
-    Value(Vec<String>),
+    Add,
+    Remove(usize),
+    MoveUp(usize),
+    MoveDown(usize),
+    UrlChanged(usize, String),
+    ProtocolChanged(usize, String),
+    Check(usize),
+    CheckAll,
+    Checked(String, LocationCheck),
     Error(String),
 }
 
@@ -27,18 +50,71 @@ impl Component for LocationsList {
     type Properties = Props;
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        Self { link, props }
+        Self {
+            link,
+            props,
+            tasks: Vec::new(),
+        }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let list = &mut self.props.locations.0;
         match msg {
-            Msg::Value(urls) => {
-                self.props
-                    .form_link
-                    .send_message(super::Msg::LocationsChanged(Locations(urls)));
+            Msg::Add => list.push(Location::new(String::new())),
+            Msg::Remove(i) if i < list.len() => {
+                list.remove(i);
+            }
+            Msg::MoveUp(i) if i > 0 && i < list.len() => list.swap(i - 1, i),
+            Msg::MoveDown(i) if i + 1 < list.len() => list.swap(i, i + 1),
+            Msg::UrlChanged(i, url) if i < list.len() => {
+                // a new URL invalidates the check and may imply another protocol.
+                list[i] = Location::new(url.trim().to_owned());
+            }
+            Msg::ProtocolChanged(i, protocol) if i < list.len() => match Protocol::parse(&protocol) {
+                Some(protocol) => {
+                    list[i].protocol = protocol;
+                    list[i].check = None;
+                }
+                None => {
+                    log::error!("Unknown protocol: {}", protocol);
+                    return false;
+                }
+            },
+            Msg::Check(i) if i < list.len() => {
+                let location = list[i].clone();
+                self.check(&location);
+                return true;
+            }
+            Msg::CheckAll => {
+                for location in self.props.locations.0.clone() {
+                    self.check(&location);
+                }
+                return true;
+            }
+            Msg::Checked(url, check) => {
+                self.tasks.retain(|task| task.is_active());
+                let mut found = false;
+                for location in list.iter_mut().filter(|l| l.url == url) {
+                    location.check = Some(check.clone());
+                    found = true;
+                }
+                if !found {
+                    // the location was changed or removed in the meantime.
+                    return true;
+                }
+            }
+            Msg::Error(e) => {
+                log::error!("Locations editor error: {}", e);
+                return false;
+            }
+            other => {
+                log::error!("Message not handled: {:?}", other);
+                return false;
             }
-            other => log::error!("Message not handled: {:?}", other),
         }
+        self.props
+            .form_link
+            .send_message(super::Msg::LocationsChanged(self.props.locations.clone()));
         true
     }
 
@@ -49,20 +125,133 @@ impl Component for LocationsList {
 
     fn view(&self) -> Html {
         let name = Locations::get_key_name();
-        let content = self.props.locations.0.join("\n");
+        let checking = self.tasks.iter().any(|task| task.is_active());
+        let can_check = self.props.active && !self.props.locations.0.is_empty() && !checking;
+        let progress = if checking {
+            html! { <span class="hint-text">{ "Checking…" }</span> }
+        } else {
+            html! {}
+        };
         html! {
             <>
-                <label class="form-description" for=name>{ name }</label>
-                <textarea class="form-input" id=name disabled=!self.props.active value=content
-                    onchange=self.link.callback(|e: ChangeData| match e {
-                        ChangeData::Value(element) => {
-                            let urls: Vec<String> = element.split("\n").map(|str| str.to_owned()).collect();
-                            Msg::Value(urls)
-                        },
-                        other => Msg::Error(format!("Got unexpected: {:?}", other))
-                    })
-                />
+                <label class="form-description" for=name><PidLabel pid=Locations::get_key() fallback=name /></label>
+                <div class="form-input locations" id=name>
+                    { for self.props.locations.0.iter().enumerate().map(|(i, l)| self.view_location(i, l)) }
+                    <div class="location-buttons">
+                        <button disabled=!self.props.active onclick=self.link.callback(|_| Msg::Add)>{ "Add location" }</button>
+                        <button disabled=!can_check onclick=self.link.callback(|_| Msg::CheckAll)>{ "Check all" }</button>
+                        { progress }
+                    </div>
+                    <p class="hint-text">{ "The first location is the primary one. Servers which do not allow cross-origin requests can not be checked." }</p>
+                </div>
             </>
         }
     }
 }
+
+impl LocationsList {
+    /// Sends a HEAD request to the location. The result is stored with the location.
+    fn check(&mut self, location: &Location) {
+        let check_url = match location.check_url() {
+            Some(url) => url,
+            None => return,
+        };
+        let request = match fetch::Request::head(check_url).body(Nothing) {
+            Ok(request) => request,
+            Err(e) => {
+                log::error!("Could not build request to check {}: {}", location.url, e);
+                return;
+            }
+        };
+        let url = location.url.clone();
+        let callback = self.link.callback(move |response: fetch::Response<Result<String, Error>>| {
+            // unreachable servers and failed CORS requests have no status.
+            let (status, content_type) = match from_fetch(response) {
+                Ok(response) => (
+                    Some(response.status().as_u16()),
+                    response
+                        .headers()
+                        .get("content-type")
+                        .and_then(|value| value.to_str().ok())
+                        .map(String::from),
+                ),
+                Err(e) => {
+                    log::info!("Could not check {}: {}", url, e);
+                    (None, None)
+                }
+            };
+            Msg::Checked(
+                url.clone(),
+                LocationCheck {
+                    status,
+                    content_type,
+                    checked: chrono::Utc::now().to_rfc3339(),
+                },
+            )
+        });
+        match FetchService::fetch(request, callback) {
+            Ok(task) => self.tasks.push(task),
+            Err(e) => {
+                log::error!("Error creating task to check {}: {}", location.url, e);
+                self.link.send_message(Msg::Checked(
+                    location.url.clone(),
+                    LocationCheck {
+                        status: None,
+                        content_type: None,
+                        checked: chrono::Utc::now().to_rfc3339(),
+                    },
+                ));
+            }
+        }
+    }
+
+    fn view_location(&self, i: usize, location: &Location) -> Html {
+        let disabled = !self.props.active;
+        let on_url = self.link.callback(move |e: ChangeData| match e {
+            ChangeData::Value(url) => Msg::UrlChanged(i, url),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let on_protocol = self.link.callback(move |e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::ProtocolChanged(i, element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let validation = match location.validate() {
+            Ok(()) => Self::view_check(location.check.as_ref()),
+            Err(e) => html! { <span class="error-text">{ e }</span> },
+        };
+        let can_check = !disabled && location.check_url().is_some();
+        html! {
+            <div class="location-row">
+                <input value=location.url.clone() disabled=disabled onchange=on_url placeholder="URL, S3 URI or handle" />
+                <select disabled=disabled onchange=on_protocol title="Protocol">
+                    {
+                        for Protocol::iter().map(|protocol| {
+                            let selected = protocol == location.protocol;
+                            html! { <option value=protocol.to_string() selected=selected>{ protocol }</option> }
+                        })
+                    }
+                </select>
+                <button disabled=disabled onclick=self.link.callback(move |_| Msg::MoveUp(i))>{ "↑" }</button>
+                <button disabled=disabled onclick=self.link.callback(move |_| Msg::MoveDown(i))>{ "↓" }</button>
+                <button disabled=disabled onclick=self.link.callback(move |_| Msg::Remove(i))>{ "✗" }</button>
+                <button disabled=!can_check onclick=self.link.callback(move |_| Msg::Check(i))>{ "Check" }</button>
+                { validation }
+            </div>
+        }
+    }
+
+    fn view_check(check: Option<&LocationCheck>) -> Html {
+        let check = match check {
+            Some(check) => check,
+            None => return html! {},
+        };
+        let status = check
+            .status
+            .map_or_else(|| "unreachable".to_string(), |status| format!("HTTP {}", status));
+        let content_type = check.content_type.clone().unwrap_or_default();
+        let class = if check.is_reachable() { "hint-text" } else { "error-text" };
+        html! {
+            <span class=class title=format!("Checked {}", check.checked)>{ format!("{} {}", status, content_type) }</span>
+        }
+    }
+}
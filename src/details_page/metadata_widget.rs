@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+use crate::{
+    app_state::{
+        data::{Data, DataID},
+        data_manager::{DataManager, Incoming, Outgoing},
+    },
+    data_type_registry::{HasProfileKey, MetadataObject},
+    metadata_mapping::MetadataFormat,
+    pidinfo::PidInfo,
+    service_communication::{MetadataContext, MetadataObjectReference, ResourceReference},
+};
+
+use super::{helpers::Dom, DetailsPage};
+
+/// Shows the record as a metadata document and allows to attach or import such documents.
+pub struct MetadataWidget {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    _data_manager: Box<dyn Bridge<DataManager>>,
+    known_data: HashMap<DataID, Data>,
+
+    format: MetadataFormat,
+    import_document: String,
+    import_error: Option<String>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub active: bool,
+    pub form_link: ComponentLink<DetailsPage>,
+    pub record: PidInfo,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    SetData(HashMap<DataID, Data>),
+    FormatSelected(String),
+    Download,
+    ReferenceChanged(String),
+    ImportDocumentChanged(String),
+    Import,
+    Error(String),
+    Noop,
+}
+
+impl Component for MetadataWidget {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut data_manager = DataManager::bridge(link.callback(|msg| match msg {
+            Outgoing::AllData(data) => Msg::SetData(data),
+            Outgoing::SelectedData(_) => Msg::Noop,
+        }));
+        data_manager.send(Incoming::GetAllData);
+        Self {
+            link,
+            props,
+            _data_manager: data_manager,
+            known_data: Default::default(),
+            format: MetadataFormat::DataCiteXml,
+            import_document: String::new(),
+            import_error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetData(data) => self.known_data = data,
+            Msg::FormatSelected(name) => {
+                if let Some(format) = MetadataFormat::iter().find(|f| f.to_string() == name) {
+                    self.format = format;
+                }
+            }
+            Msg::Download => {
+                let filename = format!(
+                    "{}.{}",
+                    self.props.record.pid().replace('/', "_"),
+                    self.format.file_extension()
+                );
+                let document = self.document();
+                if let Err(e) = Dom::download(filename, self.format.mime_type(), document.as_bytes()) {
+                    log::error!("Could not download metadata document: {:?}", e);
+                }
+                return false;
+            }
+            Msg::ReferenceChanged(reference) => {
+                let reference = reference.trim();
+                let object = if reference.is_empty() {
+                    MetadataObject(None)
+                } else {
+                    MetadataObject(Some(MetadataObjectReference {
+                        context: MetadataContext::Describing,
+                        resource: ResourceReference::from(reference),
+                    }))
+                };
+                self.props
+                    .form_link
+                    .send_message(super::Msg::MetadataObjectChanged(object));
+            }
+            Msg::ImportDocumentChanged(document) => self.import_document = document,
+            Msg::Import => match self.format.parse(&self.import_document) {
+                Ok(attributes) => {
+                    self.import_error = None;
+                    self.props
+                        .form_link
+                        .send_message(super::Msg::MetadataImported(attributes));
+                }
+                Err(e) => self.import_error = Some(e),
+            },
+            Msg::Error(e) => log::error!("Metadata widget error: {}", e),
+            Msg::Noop => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let on_format = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::FormatSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let on_reference = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Value(reference) => Msg::ReferenceChanged(reference),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let on_import_document = self.link.callback(|e: InputData| Msg::ImportDocumentChanged(e.value));
+        let reference = self
+            .props
+            .record
+            .metadata_object
+            .0
+            .as_ref()
+            .map(|r| r.resource.as_str().to_owned())
+            .unwrap_or_default();
+        let name = MetadataObject::get_key_name();
+        html! {
+            <details>
+                <summary>{ "Metadata document" }</summary>
+                <div class="two-column-lefty">
+                    <label class="form-description" for=FORMAT_FIELD>{ "Format" }</label>
+                    <select class="form-input" id=FORMAT_FIELD onchange=on_format>
+                        {
+                            for MetadataFormat::iter().map(|f: MetadataFormat| {
+                                let selected = f == self.format;
+                                html! { <option selected=selected>{ f }</option> }
+                            })
+                        }
+                    </select>
+                    <label class="form-description" for=name>{ "Attached document (URL or PID)" }</label>
+                    <input class="form-input" id=name disabled=!self.props.active value=reference onchange=on_reference />
+                </div>
+                <pre class="metadata-document">{ self.document() }</pre>
+                <div class="column-form">
+                    <button onclick=self.link.callback(|_| Msg::Download)>{ "Download document" }</button>
+                </div>
+                <div class="two-column-lefty">
+                    <label class="form-description" for=IMPORT_FIELD>{ "Import attributes from a document of the selected format" }</label>
+                    <textarea class="form-input" id=IMPORT_FIELD disabled=!self.props.active
+                        value=self.import_document.clone() oninput=on_import_document />
+                </div>
+                {
+                    self.import_error.as_ref().map_or_else(
+                        || html! {},
+                        |e| html! { <p class="error-text">{ e }</p> },
+                    )
+                }
+                <div class="column-form">
+                    <button disabled=!self.props.active onclick=self.link.callback(|_| Msg::Import)>{ "Apply to record" }</button>
+                </div>
+            </details>
+        }
+    }
+}
+
+const FORMAT_FIELD: &str = "metadata_format";
+const IMPORT_FIELD: &str = "metadata_import";
+
+impl MetadataWidget {
+    fn document(&self) -> String {
+        let data = self
+            .props
+            .record
+            .data
+            .and_then(|id| self.known_data.get(&id));
+        self.format.generate(&self.props.record, data)
+    }
+}
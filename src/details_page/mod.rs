@@ -1,34 +1,50 @@
+mod contributors_editor;
 mod data_widget;
 mod date_created_input;
 mod date_modified_input;
+mod date_time_picker;
 mod edit_button;
 mod etag_input;
-mod helpers;
+pub mod helpers;
+mod lifecycle_widget;
 mod locations_list;
+mod metadata_widget;
 mod policy_input;
 mod profile_selector;
 mod publish_button;
+mod record_view;
+mod ro_crate_export;
+mod template_saver;
 mod type_selector;
+mod version_chain;
 mod version_input;
 
+use contributors_editor::*;
 use data_widget::*;
 use date_created_input::*;
 use date_modified_input::*;
 use edit_button::*;
 use etag_input::*;
+use lifecycle_widget::*;
 use locations_list::*;
+use metadata_widget::*;
 use policy_input::*;
 use profile_selector::*;
 use publish_button::*;
+use record_view::view_record;
+use ro_crate_export::*;
+use template_saver::*;
 use type_selector::*;
+use version_chain::*;
 use version_input::*;
 
 use yew::{agent::Dispatcher, prelude::*};
+use yew_router::{agent::RouteRequest, prelude::*};
 
-use crate::{pit_service::PitService, app_state::{data::DataID, data_manager, data_manager::DataManager, pid_manager, pid_manager::PidManager}, data_type_registry::{
-        DateCreated, DateModified, DigitalObjectType, Etag, Locations, Pid, Policy, Profile,
-        Version,
-    }, pidinfo::{PidInfo, State}};
+use crate::{AppRoute, pit_service::PitService, app_state::{data::DataID, data_manager, lifecycle::Retirement, data_manager::DataManager, pid_manager, pid_manager::PidManager}, data_type_registry::{
+        Contributors, DateCreated, DateModified, DateTimeHandle, DigitalObjectType, Etag, Locations, MetadataObject,
+        Pid, Policy, Profile, SupersededBy, Tombstone, Version,
+    }, metadata_mapping::MappedAttributes, pidinfo::{PidInfo, State}, workspace_io::WorkspaceIo, duplicate_button::DuplicateButton};
 
 pub struct DetailsPage {
     link: ComponentLink<Self>,
@@ -37,6 +53,7 @@ pub struct DetailsPage {
     pit_service: Dispatcher<PitService>,
     pid_manager: Dispatcher<PidManager>,
     data_manager: Dispatcher<DataManager>,
+    router: RouteAgentDispatcher,
 
     edit_mode: bool,
 }
@@ -60,6 +77,12 @@ pub enum Msg {
     VersionChanged(Version),
     PolicyChanged(Policy),
     EtagChanged(Etag),
+    MetadataObjectChanged(MetadataObject),
+    ContributorsChanged(Contributors),
+    MetadataImported(MappedAttributes),
+    LifecycleChanged(Retirement),
+    /// A draft for the next version of this object was added to the workspace.
+    NewVersionCreated(Pid),
 
     DataChanged(Option<DataID>),
 }
@@ -75,6 +98,7 @@ impl Component for DetailsPage {
             pit_service: PitService::dispatcher(),
             pid_manager: PidManager::dispatcher(),
             data_manager: DataManager::dispatcher(),
+            router: RouteAgentDispatcher::new(),
             edit_mode: false,
         };
         new_self.sync_page_to_record_state();
@@ -97,16 +121,23 @@ impl Component for DetailsPage {
                 use crate::pit_service::Request as PitReq;
                 match self.props.record.state() {
                     State::Clean => log::error!("Status is clean. This should not happen."),
-                    State::Modified => self.pit_service.send(PitReq::Update(self.props.record.clone())),
-                    State::Unregistered => self.pit_service.send(PitReq::Register(self.props.record.clone())),
+                    State::Modified => {
+                        // the published record changes now.
+                        self.props.record.date_modified = DateModified(DateTimeHandle::now());
+                        self.pit_service.send(PitReq::Update(self.props.record.clone()));
+                    }
+                    // the user checked that the earlier registration did not succeed.
+                    State::Unregistered | State::RegistrationUnknown => {
+                        self.pit_service.send(PitReq::Register(self.props.record.clone()))
+                    }
                 }
             }
 
             Msg::ProfileChanged(p) => {
-                self.props.record.profile = p.map_err(|e| Some(e));
+                self.props.record.profile = p.map_err(Some);
             }
             Msg::DigitalObjectTypeChanged(t) => {
-                self.props.record.digital_object_type = t.map_err(|e| Some(e));
+                self.props.record.digital_object_type = t.map_err(Some);
             }
             Msg::LocationsChanged(l) => self.props.record.locations = l,
             Msg::VersionChanged(v) => self.props.record.version = v,
@@ -114,6 +145,19 @@ impl Component for DetailsPage {
             Msg::EtagChanged(etag) => self.props.record.etag = etag,
             Msg::DateCreatedChanged(date) => self.props.record.date_created = date,
             Msg::DateModifiedChanged(date) => self.props.record.date_modified = date,
+            Msg::MetadataObjectChanged(object) => self.props.record.metadata_object = object,
+            Msg::ContributorsChanged(contributors) => self.props.record.contributors = contributors,
+            Msg::MetadataImported(attributes) => attributes.apply(&mut self.props.record),
+            Msg::LifecycleChanged(retirement) => {
+                if let Err(e) = retirement.apply(&mut self.props.record) {
+                    log::error!("Could not change lifecycle status: {}", e);
+                }
+            }
+            // this object links to the draft once the draft is registered.
+            Msg::NewVersionCreated(pid) => {
+                self.router
+                    .send(RouteRequest::ChangeRoute(Route::from(AppRoute::Details { path: pid.0 })));
+            }
             Msg::DataChanged(id) => {
                 // update own state
                 self.props.record.data = id;
@@ -144,11 +188,16 @@ impl Component for DetailsPage {
         let locations = self.props.record.locations.clone();
         let date_created = self.props.record.date_created.clone();
         let date_modified = self.props.record.date_modified.clone();
+        let invalid_created = DateCreated::invalid_value(&data.record).map(|e| format!("Published date is invalid: {}", e));
+        let invalid_modified = DateModified::invalid_value(&data.record).map(|e| format!("Published date is invalid: {}", e));
         let policy = self.props.record.policy.clone();
         let etag = self.props.record.etag.clone();
         let version = self.props.record.version.clone();
+        let contributors = self.props.record.contributors.clone();
+        let status = data.status;
         html! {
             <div id="content" class="maincolumns scroll-vertical">
+                { self.view_tombstone() }
                 <div class="two-column-lefty">
                     <div class="image-placeholder"><p>{ "Preview" }</p></div>
                     <div class="two-column-lefty">
@@ -157,15 +206,29 @@ impl Component for DetailsPage {
                         <p class="align-right">{ "Description:" }</p>
                         <p>{ data.describe() }</p>
                         <p class="align-right">{ "Status:" }</p>
-                        <p>{ format!("{:?}", data.state()) }</p>
+                        <p class=status.css_class()>{ format!("{:?}", data.state()) }</p>
+                        <p class="align-right">{ "Share:" }</p>
+                        <WorkspaceIo pids=vec![Pid(data.pid().clone())] allow_import=false />
+                        <p class="align-right">{ "Duplicate:" }</p>
+                        <DuplicateButton record=data.clone() />
                     </div>
                 </div>
 
                 <DataWidget detail_page=self.link.clone() />
 
+                <RoCrateExport record=data.clone() />
+
+                <TemplateSaver record=data.clone() />
+
+                <VersionChain form_link=self.link.clone() record=data.clone() />
+
+                <LifecycleWidget form_link=self.link.clone() active=self.edit_mode record=data.clone() />
+
+                <MetadataWidget form_link=self.link.clone() active=self.edit_mode record=data.clone() />
+
                 <details open=true>
                     <summary>{ "Record Metadata (raw)" }</summary>
-                    <div class="two-column-lefty">{ data.view_record() }</div>
+                    <div class="two-column-lefty">{ view_record(&data) }</div>
                 </details>
 
                 <details open=true>
@@ -181,9 +244,10 @@ impl Component for DetailsPage {
                         <LocationsList form_link=self.link.clone() active=self.edit_mode locations=locations />
                         <PolicyInput form_link=self.link.clone() active=self.edit_mode policy=policy />
                         <EtagInput form_link=self.link.clone() active=self.edit_mode etag=etag />
-                        <DateCreatedInput form_link=self.link.clone() active=self.edit_mode date_created=date_created />
-                        <DateModifiedInput form_link=self.link.clone() active=self.edit_mode date_modified=date_modified />
+                        <DateCreatedInput form_link=self.link.clone() active=self.edit_mode date_created=date_created stored_error=invalid_created />
+                        <DateModifiedInput form_link=self.link.clone() active=self.edit_mode date_modified=date_modified stored_error=invalid_modified />
                         <VersionInput form_link=self.link.clone() active=self.edit_mode version=version />
+                        <ContributorsEditor form_link=self.link.clone() active=self.edit_mode contributors=contributors />
                     </div>
                 </details>
 
@@ -193,6 +257,37 @@ impl Component for DetailsPage {
 }
 
 impl DetailsPage {
+    /// Shows a banner if the object was deprecated or withdrawn, also if only locally.
+    fn view_tombstone(&self) -> Html {
+        let info = &self.props.record;
+        let status = info.status;
+        if !status.is_tombstoned() {
+            return html! {};
+        }
+        let Tombstone(tombstone) = &info.tombstone;
+        let SupersededBy(successor) = &info.superseded_by;
+        let unpublished = if info.status_unpublished() {
+            html! { <p class="hint-text">{ "This status is not published, it is only kept in this workspace." }</p> }
+        } else {
+            html! {}
+        };
+        html! {
+            <div class=("tombstone-banner", status.css_class())>
+                <p><strong>{ format!("This object is {}.", status.to_string().to_lowercase()) }</strong></p>
+                { unpublished }
+                { for tombstone.iter().map(|text| html! { <p>{ text }</p> }) }
+                {
+                    for successor.iter().map(|pid| html! {
+                        <p>
+                            { "Superseded by " }
+                            <RouterAnchor<AppRoute> route=AppRoute::Details{ path: pid.0.clone() }>{ pid.0.as_str() }</RouterAnchor<AppRoute>>
+                        </p>
+                    })
+                }
+            </div>
+        }
+    }
+
     fn sync_page_to_record_state(&mut self) {
         use data_manager::Incoming;
         self.edit_mode = false;
@@ -1,12 +1,30 @@
+use strum::IntoEnumIterator;
 use yew::prelude::*;
 
-use crate::data_type_registry::{HasProfileKey, Policy};
+use crate::{
+    app_state::known_policies::KnownPolicies,
+    data_type_registry::{
+        spdx_license_name, HasProfileKey, Lifecycle, Pid, Policy, PolicyDefinition, SPDX_LICENSES,
+    },
+    pid_label::PidLabel,
+    pit_service::{self, PitService},
+};
 
 use super::DetailsPage;
 
 pub struct PolicyInput {
     link: ComponentLink<Self>,
     props: Props,
+
+    pit_service: Box<dyn Bridge<PitService>>,
+    known_policies: KnownPolicies,
+
+    /// The content of the currently referenced policy object, if resolved.
+    resolved: Resolution,
+    /// The policy being composed in the form.
+    definition: PolicyDefinition,
+    /// A policy object being registered.
+    pending: Option<PolicyDefinition>,
 }
 
 #[derive(Properties, Clone)]
@@ -16,9 +34,20 @@ pub struct Props {
     pub policy: Policy,
 }
 
+enum Resolution {
+    Resolving(Pid),
+    Resolved(PolicyDefinition),
+    Failed(String),
+}
+
 #[derive(Debug)]
 pub enum Msg {
     Value(String),
+    LifecycleSelected(String),
+    LicenseSelected(String),
+    TombstoneChanged(String),
+    UseDefinition,
+    PitResponse(pit_service::Response),
     Error(String),
 }
 
@@ -27,7 +56,19 @@ impl Component for PolicyInput {
     type Properties = Props;
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        Self { link, props }
+        let pit_service = PitService::bridge(link.callback(Msg::PitResponse));
+        let pid = Pid(props.policy.0.clone());
+        let mut input = Self {
+            link,
+            props,
+            pit_service,
+            known_policies: KnownPolicies::load(),
+            resolved: Resolution::Resolving(pid.clone()),
+            definition: PolicyDefinition::default(),
+            pending: None,
+        };
+        input.resolve(pid);
+        input
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
@@ -37,31 +78,156 @@ impl Component for PolicyInput {
                     .form_link
                     .send_message(super::Msg::PolicyChanged(Policy(pid)));
             }
-            other => log::error!("Message not handled: {:?}", other),
+            Msg::LifecycleSelected(lifecycle) => {
+                self.definition.lifecycle = Lifecycle::parse(&lifecycle).unwrap_or_default();
+            }
+            Msg::LicenseSelected(license) => self.definition.license = license,
+            Msg::TombstoneChanged(tombstone) => {
+                self.definition.tombstone = Some(tombstone).filter(|t| !t.trim().is_empty());
+            }
+            Msg::UseDefinition => {
+                if let Some(pid) = self.known_policies.find_pid(&self.definition) {
+                    self.props
+                        .form_link
+                        .send_message(super::Msg::PolicyChanged(Policy(pid.0)));
+                } else {
+                    self.pending = Some(self.definition.clone());
+                    self.pit_service
+                        .send(pit_service::Request::RegisterRecord(self.definition.to_record()));
+                }
+            }
+            Msg::PitResponse(response) => return self.handle_response(response),
+            Msg::Error(e) => log::error!("Policy input error: {}", e),
         }
         true
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let policy_changed = self.props.policy != props.policy;
         self.props = props;
+        if policy_changed {
+            self.resolve(Pid(self.props.policy.0.clone()));
+        }
         true
     }
 
     fn view(&self) -> Html {
         let name = Policy::get_key_name();
         let content = self.props.policy.0.as_str();
+        let summary = match &self.resolved {
+            Resolution::Resolving(_) => "Resolving policy...".to_owned(),
+            Resolution::Resolved(definition) => definition.summary(),
+            Resolution::Failed(e) => format!("Policy could not be resolved: {}", e),
+        };
         html! {
             <>
-                <label class="form-description" for=name>{ name }</label>
-                <input class="form-input" id=name disabled=!self.props.active value=content
-                    onchange=self.link.callback(|e: ChangeData| match e {
-                        ChangeData::Value(pid) => {
-                            Msg::Value(pid)
-                        },
-                        other => Msg::Error(format!("Got unexpected: {:?}", other))
-                    })
-                />
+                <label class="form-description" for=name><PidLabel pid=Policy::get_key() fallback=name /></label>
+                <div class="form-input policy-editor">
+                    <input id=name disabled=!self.props.active value=content
+                        onchange=self.link.callback(|e: ChangeData| match e {
+                            ChangeData::Value(pid) => {
+                                Msg::Value(pid)
+                            },
+                            other => Msg::Error(format!("Got unexpected: {:?}", other))
+                        })
+                    />
+                    <p class="hint-text">{ summary }</p>
+                    { if self.props.active { self.view_composer() } else { html! {} } }
+                </div>
             </>
         }
     }
 }
+
+impl PolicyInput {
+    fn resolve(&mut self, pid: Pid) {
+        if let Some(definition) = self.known_policies.find_definition(&pid) {
+            self.resolved = Resolution::Resolved(definition);
+        } else {
+            self.resolved = Resolution::Resolving(pid.clone());
+            self.pit_service.send(pit_service::Request::Resolve(pid));
+        }
+    }
+
+    fn handle_response(&mut self, response: pit_service::Response) -> ShouldRender {
+        use pit_service::Response;
+        match response {
+            Response::Resolved(pid, record) => match &self.resolved {
+                Resolution::Resolving(current) if *current == pid => {
+                    self.resolved = match PolicyDefinition::from_record(&record) {
+                        Some(definition) => {
+                            self.known_policies.insert(definition.clone(), pid.clone());
+                            Resolution::Resolved(definition)
+                        }
+                        None => Resolution::Failed("The object is not a policy.".into()),
+                    };
+                    true
+                }
+                _ => false,
+            },
+            Response::RecordRegistered(record) => {
+                if let Some(definition) = self.pending.take() {
+                    let pid = Pid(record.pid.clone());
+                    self.known_policies.insert(definition, pid.clone());
+                    self.props
+                        .form_link
+                        .send_message(super::Msg::PolicyChanged(Policy(pid.0)));
+                }
+                false
+            }
+            Response::ResolveFailed(pid, e) => match &self.resolved {
+                Resolution::Resolving(current) if *current == pid => {
+                    self.resolved = Resolution::Failed(e);
+                    true
+                }
+                _ => false,
+            },
+            Response::RecordRegistrationFailed(e) => {
+                self.pending = None;
+                log::error!("Could not register policy: {}", e);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn view_composer(&self) -> Html {
+        let on_lifecycle = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::LifecycleSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let on_license = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::LicenseSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let on_tombstone = self.link.callback(|e: InputData| Msg::TombstoneChanged(e.value));
+        let mut licenses: Vec<&str> = SPDX_LICENSES.iter().map(|(id, _)| *id).collect();
+        if spdx_license_name(&self.definition.license).is_none() {
+            licenses.push(self.definition.license.as_str());
+        }
+        let tombstone = self.definition.tombstone.clone().unwrap_or_default();
+        html! {
+            <div class="policy-composer">
+                <select onchange=on_lifecycle title="Lifecycle">
+                    {
+                        for Lifecycle::iter().map(|l: Lifecycle| {
+                            let selected = l == self.definition.lifecycle;
+                            html! { <option selected=selected>{ l }</option> }
+                        })
+                    }
+                </select>
+                <select onchange=on_license title="License (SPDX)">
+                    {
+                        for licenses.into_iter().map(|id| {
+                            let selected = id == self.definition.license;
+                            let title = spdx_license_name(id).unwrap_or(id);
+                            html! { <option value=id selected=selected title=title>{ id }</option> }
+                        })
+                    }
+                </select>
+                <textarea placeholder="Tombstone text (optional)" value=tombstone oninput=on_tombstone />
+                <button onclick=self.link.callback(|_| Msg::UseDefinition)>{ "Use this policy" }</button>
+            </div>
+        }
+    }
+}
@@ -4,9 +4,9 @@ use strum::IntoEnumIterator;
 use web_sys::HtmlSelectElement;
 use yew::prelude::*;
 
-use crate::data_type_registry::{HasProfileKey, Pid, Profile};
+use crate::{data_type_registry::{HasProfileKey, Pid, Profile}, pid_label::PidLabel};
 
-use super::{DetailsPage, helpers::DOM};
+use super::{DetailsPage, helpers::Dom};
 
 pub struct ProfileSelector {
     link: ComponentLink<Self>,
@@ -42,16 +42,16 @@ impl Component for ProfileSelector {
                     .form_link
                     .send_message(super::Msg::ProfileChanged(changed));
             }
-            other => log::error!("Message not handled: {:?}", other),
+            Msg::Error(e) => log::error!("Error: {}", e),
         }
         true
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
         self.props = props.clone();
-        let dropdown = DOM::get_element::<HtmlSelectElement, _>(Profile::get_key_name());
+        let dropdown = Dom::get_element::<HtmlSelectElement, _>(Profile::get_key_name());
         if let Ok(profile) = &props.maybe_profile {
-            dropdown.set_value(&*Pid::from(profile))
+            dropdown.set_value(&Pid::from(profile))
         }
         true
     }
@@ -60,7 +60,7 @@ impl Component for ProfileSelector {
         let name = Profile::get_key_name();
         html! {
             <>
-                <label class="form-description" for=name>{ name }</label>
+                <label class="form-description" for=name><PidLabel pid=Profile::get_key() fallback=name /></label>
                 <select class="form-input" id=name required=true disabled=!self.props.active
                         onchange=self.link.callback(|e: ChangeData| match e {
                             ChangeData::Select(element) => Msg::Value(element.value()),
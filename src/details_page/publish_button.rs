@@ -46,6 +46,7 @@ impl Component for PublishButton {
         } else {
             let (label, classes) = match self.props.state {
                 State::Unregistered => ("Publish object", "publish-button"),
+                State::RegistrationUnknown => ("Registration unknown, check manually. Publish again", "publish-button"),
                 State::Modified => ("Publish object changes", "update-button"),
                 State::Clean => ("Object state is published", "inactive-button"),
             };
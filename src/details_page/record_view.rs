@@ -0,0 +1,29 @@
+use serde_json as json;
+use yew::prelude::*;
+
+use crate::{data_type_registry::Pid, pid_label::PidLabel, pidinfo::PidInfo};
+
+/// Shows the published record of the object, entry by entry, with labels for PIDs.
+pub fn view_record(info: &PidInfo) -> Html {
+    info.record
+        .entries
+        .values()
+        .flatten()
+        .map(|entry| {
+            let value_label = entry
+                .value
+                .as_str()
+                .and_then(Pid::parse_handle)
+                .map(|pid| html! { <><br/><PidLabel pid=pid fallback="" /></> })
+                .unwrap_or_else(|| html! {});
+            html! {
+                <>
+                    <p class="align-right">
+                        <PidLabel pid=Pid(entry.key.clone()) fallback=entry.name.clone() /> <br/> { &entry.key }
+                    </p>
+                    <p>{ json::to_string_pretty(&entry.value).unwrap() }{ value_label }</p>
+                </>
+            }
+        })
+        .collect()
+}
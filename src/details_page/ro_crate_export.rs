@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use yew::prelude::*;
+
+use crate::{
+    app_state::{
+        data::{Data, DataID},
+        data_manager::{DataManager, Incoming, Outgoing},
+    },
+    collection_service::{self, member_item::Member, CollectionService},
+    pidinfo::PidInfo,
+    ro_crate::{RoCrate, METADATA_FILE_NAME},
+};
+
+use super::helpers::Dom;
+
+pub struct RoCrateExport {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    _data_manager: Box<dyn Bridge<DataManager>>,
+    collection_service: Box<dyn Bridge<CollectionService>>,
+    known_data: HashMap<DataID, Data>,
+    /// The members of the collection of the object, if it is a registered one.
+    members: Vec<Member>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub record: PidInfo,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    SetData(HashMap<DataID, Data>),
+    SetMembers(String, Vec<Member>),
+    DownloadMetadata,
+    DownloadZip,
+    Noop,
+}
+
+impl Component for RoCrateExport {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut data_manager = DataManager::bridge(link.callback(|msg| match msg {
+            Outgoing::AllData(data) => Msg::SetData(data),
+            Outgoing::SelectedData(_) => Msg::Noop,
+        }));
+        data_manager.send(Incoming::GetAllData);
+        let collection_service = CollectionService::bridge(link.callback(|response| match response {
+            collection_service::Response::Members(id, members) => Msg::SetMembers(id, members),
+            _ => Msg::Noop,
+        }));
+        Self {
+            link,
+            props,
+            _data_manager: data_manager,
+            collection_service,
+            known_data: Default::default(),
+            members: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetData(data) => {
+                self.known_data = data;
+                self.get_members();
+            }
+            Msg::SetMembers(id, members) => {
+                if self.collection_id().as_deref() == Some(id.as_str()) {
+                    self.members = members;
+                }
+            }
+            Msg::DownloadMetadata => {
+                let metadata = self.ro_crate().metadata_string();
+                self.download(METADATA_FILE_NAME.into(), "application/ld+json", metadata.as_bytes());
+            }
+            Msg::DownloadZip => {
+                let zip = self.ro_crate().to_zip();
+                let filename = format!("{}.crate.zip", self.props.record.pid().replace('/', "_"));
+                self.download(filename, "application/zip", &zip);
+            }
+            Msg::Noop => return false,
+        }
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        self.members.clear();
+        self.get_members();
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <details>
+                <summary>{ "RO-Crate" }</summary>
+                <p>{ "Export this object and its data as an RO-Crate (version 1.1)." }</p>
+                <div class="column-form">
+                    <button onclick=self.link.callback(|_| Msg::DownloadMetadata)>{ METADATA_FILE_NAME }</button>
+                    <button onclick=self.link.callback(|_| Msg::DownloadZip)>{ "Crate as zip" }</button>
+                </div>
+            </details>
+        }
+    }
+}
+
+impl RoCrateExport {
+    fn data(&self) -> Option<&Data> {
+        self.props.record.data.and_then(|id| self.known_data.get(&id))
+    }
+
+    fn collection_id(&self) -> Option<String> {
+        match self.data() {
+            Some(Data::Collection(collection)) => collection.get_id().map(String::from),
+            _ => None,
+        }
+    }
+
+    /// Members of unregistered collections are not known yet.
+    fn get_members(&mut self) {
+        if let Some(id) = self.collection_id() {
+            self.collection_service
+                .send(collection_service::Request::GetMembers(id));
+        }
+    }
+
+    fn ro_crate(&self) -> RoCrate {
+        RoCrate::new(&self.props.record, self.data(), &self.members)
+    }
+
+    fn download(&self, filename: String, mime: &str, content: &[u8]) {
+        if let Err(e) = Dom::download(filename, mime, content) {
+            log::error!("Could not download RO-Crate: {:?}", e);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use yew::{agent::Dispatcher, prelude::*};
+
+use crate::{
+    app_state::{
+        data::{Data, DataID},
+        data_manager::{DataManager, Incoming, Outgoing},
+        template_manager::{self, TemplateManager},
+        templates::Template,
+    },
+    pidinfo::PidInfo,
+};
+
+/// Saves the local state of an object as template for new drafts.
+pub struct TemplateSaver {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    _data_manager: Box<dyn Bridge<DataManager>>,
+    template_manager: Dispatcher<TemplateManager>,
+    known_data: HashMap<DataID, Data>,
+
+    name: String,
+    include_data: bool,
+    status: Option<String>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub record: PidInfo,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    SetData(HashMap<DataID, Data>),
+    NameChanged(String),
+    ToggleIncludeData,
+    Save,
+    Noop,
+}
+
+impl Component for TemplateSaver {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut data_manager = DataManager::bridge(link.callback(|msg| match msg {
+            Outgoing::AllData(data) => Msg::SetData(data),
+            Outgoing::SelectedData(_) => Msg::Noop,
+        }));
+        data_manager.send(Incoming::GetAllData);
+        Self {
+            link,
+            props,
+            _data_manager: data_manager,
+            template_manager: TemplateManager::dispatcher(),
+            known_data: Default::default(),
+            name: String::new(),
+            include_data: true,
+            status: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetData(data) => self.known_data = data,
+            Msg::NameChanged(name) => self.name = name,
+            Msg::ToggleIncludeData => self.include_data = !self.include_data,
+            Msg::Save => {
+                let name = self.name.trim().to_string();
+                let data = self.data().filter(|_| self.include_data).cloned();
+                let template = Template::from_object(name.clone(), &self.props.record, data);
+                self.template_manager
+                    .send(template_manager::Incoming::Save(template));
+                self.status = Some(format!("Saved template \"{}\".", name));
+            }
+            Msg::Noop => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props.record.pid() != self.props.record.pid() {
+            self.status = None;
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let data = self.data().map(|data| {
+            html! {
+                <label>
+                    <input type="checkbox" checked=self.include_data onclick=self.link.callback(|_| Msg::ToggleIncludeData) />
+                    { format!("Include data ({})", data.type_name()) }
+                </label>
+            }
+        });
+        let no_name = self.name.trim().is_empty();
+        html! {
+            <details>
+                <summary>{ "Template" }</summary>
+                <p>{ "Save the current state of this object as template for new drafts. Dates will be set when a draft is created." }</p>
+                <div class="column-form">
+                    <input type="text" placeholder="Template name" value=self.name.clone()
+                        oninput=self.link.callback(|e: InputData| Msg::NameChanged(e.value)) />
+                    { data.unwrap_or_else(|| html! {}) }
+                    <button disabled=no_name onclick=self.link.callback(|_| Msg::Save)>{ "Save as template" }</button>
+                </div>
+                { for self.status.iter().map(|status| html! { <p class="status-text">{ status }</p> }) }
+            </details>
+        }
+    }
+}
+
+impl TemplateSaver {
+    fn data(&self) -> Option<&Data> {
+        self.props.record.data.and_then(|id| self.known_data.get(&id))
+    }
+}
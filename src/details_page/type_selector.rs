@@ -4,9 +4,9 @@ use strum::IntoEnumIterator;
 use web_sys::HtmlSelectElement;
 use yew::prelude::*;
 
-use crate::data_type_registry::*;
+use crate::{data_type_registry::*, pid_label::PidLabel};
 
-use super::{helpers::DOM, DetailsPage};
+use super::{helpers::Dom, DetailsPage};
 
 pub struct DigitalObjectTypeSelector {
     link: ComponentLink<Self>,
@@ -42,16 +42,16 @@ impl Component for DigitalObjectTypeSelector {
                     .form_link
                     .send_message(super::Msg::DigitalObjectTypeChanged(changed));
             }
-            other => log::error!("Message not handled: {:?}", other),
+            Msg::Error(e) => log::error!("Error: {}", e),
         }
         true
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
         self.props = props.clone();
-        let dropdown = DOM::get_element::<HtmlSelectElement, _>(DigitalObjectType::get_key_name());
+        let dropdown = Dom::get_element::<HtmlSelectElement, _>(DigitalObjectType::get_key_name());
         if let Ok(t) = &props.maybe_type {
-            dropdown.set_value(&*Pid::from(t))
+            dropdown.set_value(&Pid::from(t))
         }
         true
     }
@@ -60,7 +60,7 @@ impl Component for DigitalObjectTypeSelector {
         let name = DigitalObjectType::get_key_name();
         html! {
             <>
-                <label class="form-description" for=name>{ name }</label>
+                <label class="form-description" for=name><PidLabel pid=DigitalObjectType::get_key() fallback=name /></label>
                 <select class="form-input" id=name required=true disabled=!self.props.active
                         onchange=self.link.callback(|e: ChangeData| match e {
                             ChangeData::Select(element) => Msg::Value(element.value()),
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::{
+    app_state::{
+        pid_manager::{Incoming, Outgoing, PidManager},
+        versioning,
+    },
+    data_type_registry::{Bump, Pid},
+    pidinfo::PidInfo,
+    AppRoute,
+};
+
+use super::DetailsPage;
+
+/// Shows all known versions of the object and creates new versions.
+pub struct VersionChain {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    pid_manager: Box<dyn Bridge<PidManager>>,
+    known_pids: HashMap<Pid, PidInfo>,
+
+    bump: Bump,
+    error: Option<String>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub form_link: ComponentLink<DetailsPage>,
+    pub record: PidInfo,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    SetPidInfos(HashMap<Pid, PidInfo>),
+    BumpSelected(String),
+    CreateVersion,
+    Created(Pid),
+    Error(String),
+}
+
+impl Component for VersionChain {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut pid_manager = PidManager::bridge(link.callback(|msg| match msg {
+            Outgoing::AllPidInformation(infos) => Msg::SetPidInfos(infos),
+            Outgoing::Created(pid) => Msg::Created(pid),
+        }));
+        pid_manager.send(Incoming::GetAllPidInformation);
+        Self {
+            link,
+            props,
+            pid_manager,
+            known_pids: Default::default(),
+            bump: Bump::Minor,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetPidInfos(infos) => self.known_pids = infos,
+            Msg::BumpSelected(name) => self.bump = Bump::parse(&name).unwrap_or(Bump::Minor),
+            Msg::CreateVersion => match versioning::new_version(&self.known_pids, &self.props.record, self.bump) {
+                Ok(draft) => {
+                    self.error = None;
+                    self.pid_manager.send(Incoming::AddUnregisteredPidInfo(draft));
+                }
+                Err(e) => self.error = Some(e),
+            },
+            Msg::Created(pid) => {
+                self.props
+                    .form_link
+                    .send_message(super::Msg::NewVersionCreated(pid));
+            }
+            Msg::Error(e) => log::error!("Version chain error: {}", e),
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props.record.pid() != props.record.pid() {
+            self.error = None;
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let pid = Pid(self.props.record.pid().clone());
+        // the own entry may contain local changes which are not yet in the manager.
+        let mut known = self.known_pids.clone();
+        known.insert(pid.clone(), self.props.record.clone());
+        let chain = versioning::chain(&known, &pid);
+        let has_versions = chain.len() > 1;
+        let on_bump = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::BumpSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        html! {
+            <details open=has_versions>
+                <summary>{ "Versions" }</summary>
+                <ol class="version-chain">
+                    {
+                        for chain.into_iter().map(|(p, version)| {
+                            let label = match version {
+                                Some(v) => format!("{} ({})", v.0, p.0),
+                                None => p.0.clone(),
+                            };
+                            if p == pid {
+                                html! { <li class="current-version">{ label }</li> }
+                            } else {
+                                html! {
+                                    <li>
+                                        <RouterAnchor<AppRoute> route=AppRoute::Details{ path: p.0.clone() }>{ label }</RouterAnchor<AppRoute>>
+                                    </li>
+                                }
+                            }
+                        })
+                    }
+                </ol>
+                <div class="column-form">
+                    <select onchange=on_bump title="Version increment">
+                        {
+                            for Bump::iter().map(|b| {
+                                let selected = b == self.bump;
+                                html! { <option selected=selected>{ b }</option> }
+                            })
+                        }
+                    </select>
+                    <button onclick=self.link.callback(|_| Msg::CreateVersion)>{ "Create new version" }</button>
+                </div>
+                {
+                    if let Some(e) = &self.error {
+                        html! { <p class="error-text">{ e }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+            </details>
+        }
+    }
+}
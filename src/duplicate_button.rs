@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use yew::{agent::Dispatcher, prelude::*};
+use yew_router::{agent::RouteRequest, prelude::*};
+
+use crate::{
+    app_state::{
+        data::{Data, DataID},
+        data_manager::{self, DataManager},
+        duplicate::{duplicate, duplicate_data},
+        pid_manager::{self, PidManager},
+    },
+    collection_service::{self, CollectionService},
+    pidinfo::PidInfo,
+    AppRoute,
+};
+
+/// Creates a new draft from an object and opens it.
+/// Optionally, the data of the object is copied as well.
+pub struct DuplicateButton {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    pid_manager: Box<dyn Bridge<PidManager>>,
+    data_manager: Box<dyn Bridge<DataManager>>,
+    collection_service: Dispatcher<CollectionService>,
+    router: RouteAgentDispatcher,
+
+    copy_data: bool,
+    /// Waiting for the current data to copy it.
+    pending: bool,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub record: PidInfo,
+    /// Shows a small button only, which always copies the data.
+    #[prop_or_default]
+    pub compact: bool,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    Duplicate,
+    ToggleCopyData,
+    SetData(HashMap<DataID, Data>),
+    PidManager(pid_manager::Outgoing),
+    Noop,
+}
+
+impl Component for DuplicateButton {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let pid_manager = PidManager::bridge(link.callback(Msg::PidManager));
+        let data_manager = DataManager::bridge(link.callback(|msg| match msg {
+            data_manager::Outgoing::AllData(data) => Msg::SetData(data),
+            data_manager::Outgoing::SelectedData(_) => Msg::Noop,
+        }));
+        Self {
+            link,
+            props,
+            pid_manager,
+            data_manager,
+            collection_service: CollectionService::dispatcher(),
+            router: RouteAgentDispatcher::new(),
+            copy_data: true,
+            pending: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Duplicate => {
+                if self.copy_data && self.props.record.data.is_some() {
+                    self.pending = true;
+                    self.data_manager.send(data_manager::Incoming::GetAllData);
+                } else {
+                    self.add(duplicate(&self.props.record));
+                }
+            }
+            Msg::ToggleCopyData => {
+                self.copy_data = !self.copy_data;
+                return true;
+            }
+            Msg::SetData(known_data) => {
+                if self.pending {
+                    self.pending = false;
+                    let mut copy = duplicate(&self.props.record);
+                    copy.data = self.copy(&known_data);
+                    self.add(copy);
+                }
+            }
+            Msg::PidManager(pid_manager::Outgoing::Created(pid)) => {
+                let route = Route::from(AppRoute::Details { path: pid.0 });
+                self.router.send(RouteRequest::ChangeRoute(route));
+            }
+            Msg::PidManager(_) => {}
+            Msg::Noop => {}
+        }
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let onclick = self.link.callback(|_| Msg::Duplicate);
+        if self.props.compact {
+            return html! {
+                <button onclick=onclick class="fdo-duplicate-button" title="Duplicate as new draft">{ "⧉" }</button>
+            };
+        }
+        let copy_data = if self.props.record.data.is_some() {
+            html! {
+                <label>
+                    <input type="checkbox" checked=self.copy_data onclick=self.link.callback(|_| Msg::ToggleCopyData) />
+                    { "Copy data" }
+                </label>
+            }
+        } else {
+            html! {}
+        };
+        html! {
+            <div class="duplicate-button">
+                <button onclick=onclick>{ "Duplicate as new draft" }</button>
+                { copy_data }
+            </div>
+        }
+    }
+}
+
+impl DuplicateButton {
+    /// Copies the data of the object under a new id.
+    /// Registered collections are copied by the collection service, including their members.
+    fn copy(&mut self, known_data: &HashMap<DataID, Data>) -> Option<DataID> {
+        let original = self.props.record.data.and_then(|id| known_data.get(&id))?;
+        let id = loop {
+            let id = DataID(rand::thread_rng().gen::<u16>());
+            if !known_data.contains_key(&id) {
+                break id;
+            }
+        };
+        self.data_manager
+            .send(data_manager::Incoming::UpdateData(id, duplicate_data(original)));
+        if let Data::Collection(collection) = original {
+            if collection.get_id().is_some() {
+                self.collection_service
+                    .send(collection_service::Request::Duplicate(id, (**collection).clone()));
+            }
+        }
+        Some(id)
+    }
+
+    fn add(&mut self, copy: PidInfo) {
+        self.pid_manager
+            .send(pid_manager::Incoming::AddUnregisteredPidInfo(copy));
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::{HashMap, HashSet};
+
+use yew::prelude::*;
+use yew_router::{agent::RouteRequest, prelude::*};
+
+use crate::{
+    app_state::{
+        data::{Data, DataID},
+        data_manager::{self, DataManager},
+        pid_manager::{self, PidManager},
+    },
+    collection_service::{self, member_item::Member, CollectionService},
+    data_type_registry::Pid,
+    pidinfo::PidInfo,
+    pit_service::{self, PitService},
+    relation_graph::{layout, Graph, Origin, Position},
+    service_communication::PidRecord,
+    AppRoute,
+};
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 600.0;
+const NODE_RADIUS: f64 = 18.0;
+
+/// Shows the objects of the workspace and everything they reference as a graph.
+/// Referenced records are resolved when their node is clicked.
+pub struct RelationGraphPage {
+    link: ComponentLink<Self>,
+
+    pid_manager: Box<dyn Bridge<PidManager>>,
+    pit_service: Box<dyn Bridge<PitService>>,
+    _data_manager: Box<dyn Bridge<DataManager>>,
+    collection_service: Box<dyn Bridge<CollectionService>>,
+    router: RouteAgentDispatcher,
+
+    known_pids: HashMap<Pid, PidInfo>,
+    known_data: HashMap<DataID, Data>,
+    /// Members of registered collections, by collection id.
+    members: HashMap<String, Vec<Member>>,
+    resolved: HashMap<Pid, PidRecord>,
+    resolving: HashSet<Pid>,
+    failed: HashSet<Pid>,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    SetPidInfos(HashMap<Pid, PidInfo>),
+    SetData(HashMap<DataID, Data>),
+    SetMembers(String, Vec<Member>),
+    PitResponse(pit_service::Response),
+    NodeClicked(Pid),
+    Noop,
+}
+
+impl Component for RelationGraphPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut pid_manager = PidManager::bridge(link.callback(|msg| match msg {
+            pid_manager::Outgoing::AllPidInformation(infos) => Msg::SetPidInfos(infos),
+            pid_manager::Outgoing::Created(_) => Msg::Noop,
+        }));
+        pid_manager.send(pid_manager::Incoming::GetAllPidInformation);
+        let pit_service = PitService::bridge(link.callback(Msg::PitResponse));
+        let mut data_manager = DataManager::bridge(link.callback(|msg| match msg {
+            data_manager::Outgoing::AllData(data) => Msg::SetData(data),
+            data_manager::Outgoing::SelectedData(_) => Msg::Noop,
+        }));
+        data_manager.send(data_manager::Incoming::GetAllData);
+        let collection_service = CollectionService::bridge(link.callback(|response| match response {
+            collection_service::Response::Members(id, members) => Msg::SetMembers(id, members),
+            _ => Msg::Noop,
+        }));
+        Self {
+            link,
+            pid_manager,
+            pit_service,
+            _data_manager: data_manager,
+            collection_service,
+            router: RouteAgentDispatcher::new(),
+            known_pids: Default::default(),
+            known_data: Default::default(),
+            members: Default::default(),
+            resolved: Default::default(),
+            resolving: Default::default(),
+            failed: Default::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        use pit_service::Response;
+        match msg {
+            Msg::SetPidInfos(infos) => self.known_pids = infos,
+            Msg::SetData(data) => {
+                for collection in data.values().filter_map(|data| match data {
+                    Data::Collection(collection) => collection.get_id(),
+                    _ => None,
+                }) {
+                    if !self.members.contains_key(collection) {
+                        self.collection_service
+                            .send(collection_service::Request::GetMembers(collection.to_owned()));
+                    }
+                }
+                self.known_data = data;
+            }
+            Msg::SetMembers(id, members) => {
+                self.members.insert(id, members);
+            }
+            Msg::PitResponse(Response::Resolved(pid, record)) => {
+                if !self.resolving.remove(&pid) {
+                    return false;
+                }
+                self.resolved.insert(pid, record);
+            }
+            Msg::PitResponse(Response::ResolveFailed(pid, _)) => {
+                if !self.resolving.remove(&pid) {
+                    return false;
+                }
+                self.failed.insert(pid);
+            }
+            Msg::PitResponse(_) => return false,
+            Msg::NodeClicked(pid) => match self.origin_of(&pid) {
+                Origin::Workspace => self.open(pid),
+                Origin::Resolved => {
+                    if let Some(record) = self.resolved.get(&pid) {
+                        let info = PidInfo::from_registered(record.clone());
+                        self.known_pids.insert(pid.clone(), info.clone());
+                        self.pid_manager
+                            .send(pid_manager::Incoming::AddPidInfo(info));
+                    }
+                    self.open(pid);
+                }
+                Origin::Unresolved | Origin::Failed => {
+                    self.failed.remove(&pid);
+                    self.resolving.insert(pid.clone());
+                    self.pit_service.send(pit_service::Request::Resolve(pid));
+                }
+                Origin::Resolving => return false,
+            },
+            Msg::Noop => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        let workspace: Vec<(Pid, PidRecord)> = self
+            .known_pids
+            .iter()
+            .map(|(pid, info)| (pid.clone(), info.as_record()))
+            .collect();
+        let records = workspace
+            .iter()
+            .map(|(pid, record)| (pid.clone(), record, Origin::Workspace))
+            .chain(
+                self.resolved
+                    .iter()
+                    .filter(|(pid, _)| !self.known_pids.contains_key(pid))
+                    .map(|(pid, record)| (pid.clone(), record, Origin::Resolved)),
+            );
+        let graph = Graph::build(records, &self.members_by_object(), |pid| self.origin_of(pid));
+        let positions = layout(&graph, WIDTH, HEIGHT, 2.0 * NODE_RADIUS);
+        let view_box = format!("0 0 {} {}", WIDTH, HEIGHT);
+        html! {
+            <div id="content" class="maincolumns scroll-vertical">
+                <h2>{ "Relations" }</h2>
+                <p class="hint-text">
+                    { "Click an object of the workspace to open it. Click a referenced object to resolve it, and click it again to add it to the workspace." }
+                </p>
+                <svg class="relation-graph" viewBox=view_box>
+                    <defs>
+                        <marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="6" markerHeight="6" orient="auto">
+                            <path d="M 0 0 L 10 5 L 0 10 z" />
+                        </marker>
+                    </defs>
+                    { for graph.edges.iter().map(|edge| self.view_edge(positions[edge.from], positions[edge.to], &edge.name)) }
+                    { for graph.nodes.iter().zip(positions.iter()).map(|(node, position)| {
+                        let pid = node.pid.clone();
+                        let onclick = self.link.callback(move |_| Msg::NodeClicked(pid.clone()));
+                        let title = format!("{} ({:?})", node.pid, node.origin);
+                        html! {
+                            <g class=("graph-node", node.origin.css_class()) onclick=onclick>
+                                <title>{ title }</title>
+                                <circle cx=position.x.to_string() cy=position.y.to_string() r=NODE_RADIUS.to_string() />
+                                <text x=position.x.to_string() y=(position.y + NODE_RADIUS + 14.0).to_string() text-anchor="middle">
+                                    { &node.label }
+                                </text>
+                            </g>
+                        }
+                    }) }
+                </svg>
+            </div>
+        }
+    }
+}
+
+impl RelationGraphPage {
+    /// The members of the collections of the workspace objects, by object.
+    fn members_by_object(&self) -> HashMap<Pid, Vec<Member>> {
+        self.known_pids
+            .iter()
+            .filter_map(|(pid, info)| match info.data.and_then(|id| self.known_data.get(&id)) {
+                Some(Data::Collection(collection)) => {
+                    let members = self.members.get(collection.get_id()?)?;
+                    Some((pid.clone(), members.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn origin_of(&self, pid: &Pid) -> Origin {
+        if self.known_pids.contains_key(pid) {
+            Origin::Workspace
+        } else if self.resolved.contains_key(pid) {
+            Origin::Resolved
+        } else if self.resolving.contains(pid) {
+            Origin::Resolving
+        } else if self.failed.contains(pid) {
+            Origin::Failed
+        } else {
+            Origin::Unresolved
+        }
+    }
+
+    fn open(&mut self, pid: Pid) {
+        let route = Route::from(AppRoute::Details { path: pid.0 });
+        self.router.send(RouteRequest::ChangeRoute(route));
+    }
+
+    /// A line between the borders of both nodes, so the arrow head stays visible.
+    fn view_edge(&self, from: Position, to: Position, name: &str) -> Html {
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length <= 2.0 * NODE_RADIUS {
+            return html! {};
+        }
+        let (ux, uy) = (dx / length, dy / length);
+        let (x1, y1) = (from.x + ux * NODE_RADIUS, from.y + uy * NODE_RADIUS);
+        let (x2, y2) = (to.x - ux * NODE_RADIUS, to.y - uy * NODE_RADIUS);
+        html! {
+            <g class="graph-edge">
+                <title>{ name }</title>
+                <line x1=x1.to_string() y1=y1.to_string() x2=x2.to_string() y2=y2.to_string() marker-end="url(#arrow)" />
+                <text x=((x1 + x2) / 2.0).to_string() y=((y1 + y2) / 2.0).to_string() text-anchor="middle">{ name }</text>
+            </g>
+        }
+    }
+}
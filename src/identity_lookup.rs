@@ -0,0 +1,93 @@
+use std::collections::{HashMap, HashSet};
+
+use yew::worker::{Agent, AgentLink, Context, HandlerId};
+
+use crate::{
+    data_type_registry::Identifier,
+    service_communication::{identity_api, pipeline, send_to_agent, transport::Pipeline},
+};
+
+/// Resolves ORCID and ROR identifiers to human readable names.
+/// Results are cached for the lifetime of the application.
+pub struct IdentityLookup {
+    link: AgentLink<IdentityLookup>,
+    subscribers: HashSet<HandlerId>,
+
+    transport: Pipeline,
+    cache: HashMap<Identifier, Option<String>>,
+}
+
+#[derive(Debug)]
+pub enum Request {
+    Lookup(Identifier),
+}
+
+#[derive(Debug, Clone)]
+pub enum Response {
+    /// The name of the identified person or organization, if found.
+    Name(Identifier, Option<String>),
+}
+
+impl Agent for IdentityLookup {
+    type Reach = Context<Self>;
+    type Message = Response;
+    type Input = Request;
+    type Output = Response;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        Self {
+            link,
+            subscribers: Default::default(),
+            transport: pipeline("Identity lookup"),
+            cache: Default::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) {
+        let Response::Name(identifier, name) = &msg;
+        self.cache.insert(identifier.clone(), name.clone());
+        for sub in self.subscribers.iter() {
+            self.link.respond(*sub, msg.clone());
+        }
+    }
+
+    fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
+        match msg {
+            Request::Lookup(identifier) => {
+                if let Some(name) = self.cache.get(&identifier) {
+                    self.link
+                        .respond(id, Response::Name(identifier, name.clone()));
+                } else {
+                    self.lookup(identifier);
+                }
+            }
+        }
+    }
+
+    fn connected(&mut self, id: HandlerId) {
+        self.subscribers.insert(id);
+    }
+
+    fn disconnected(&mut self, id: HandlerId) {
+        self.subscribers.remove(&id);
+    }
+}
+
+impl IdentityLookup {
+    fn lookup(&mut self, identifier: Identifier) {
+        let request = match identity_api::lookup_request(crate::config::identity_lookup_uri(), &identifier) {
+            Some(request) => request,
+            None => {
+                self.link.send_message(Response::Name(identifier, None));
+                return;
+            }
+        };
+        send_to_agent(&self.link, &self.transport, request, move |outcome| {
+            let name = outcome
+                .and_then(|response| identity_api::parse_name(&response))
+                .map_err(|e| log::warn!("Could not look up {}: {}", identifier, e))
+                .ok();
+            Response::Name(identifier.clone(), name)
+        });
+    }
+}
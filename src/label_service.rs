@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json as json;
+use yew::worker::{Agent, AgentLink, Context, HandlerId};
+
+use crate::{
+    data_type_registry::Pid,
+    service_communication::{pipeline, send_to_agent, transport::Pipeline},
+};
+
+/// Human readable name and description of a PID, i.e. of an attribute type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Label {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl Label {
+    /// Reads a type definition as delivered by the data type registry.
+    /// The registry wraps the definition into `content`, but plain definitions are accepted as well.
+    pub fn from_type_definition(body: &str) -> Option<Self> {
+        let value: json::Value = json::from_str(body).ok()?;
+        let definition = value.get("content").unwrap_or(&value);
+        let string = |key: &str| {
+            definition
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+        Some(Self {
+            name: string("name")?,
+            description: string("description"),
+        })
+    }
+}
+
+/// Looks up names and descriptions of PIDs in the data type registry.
+/// Results are cached for the lifetime of the application,
+/// including PIDs the registry does not know.
+pub struct LabelService {
+    link: AgentLink<LabelService>,
+    subscribers: HashSet<HandlerId>,
+
+    transport: Pipeline,
+    cache: HashMap<Pid, Option<Label>>,
+    pending: HashSet<Pid>,
+}
+
+#[derive(Debug)]
+pub enum Request {
+    Lookup(Pid),
+}
+
+#[derive(Debug, Clone)]
+pub enum Response {
+    /// The label of the PID, if the registry knows it.
+    Label(Pid, Option<Label>),
+}
+
+impl Agent for LabelService {
+    type Reach = Context<Self>;
+    type Message = Response;
+    type Input = Request;
+    type Output = Response;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        Self {
+            link,
+            subscribers: Default::default(),
+            transport: pipeline("Data type registry"),
+            cache: Default::default(),
+            pending: Default::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) {
+        let Response::Label(pid, label) = &msg;
+        self.pending.remove(pid);
+        self.cache.insert(pid.clone(), label.clone());
+        for sub in self.subscribers.iter() {
+            self.link.respond(*sub, msg.clone());
+        }
+    }
+
+    fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
+        match msg {
+            Request::Lookup(pid) => {
+                if let Some(label) = self.cache.get(&pid) {
+                    self.link.respond(id, Response::Label(pid, label.clone()));
+                } else if self.pending.insert(pid.clone()) {
+                    self.lookup(pid);
+                }
+                // otherwise all subscribers will be notified when the pending lookup finishes.
+            }
+        }
+    }
+
+    fn connected(&mut self, id: HandlerId) {
+        self.subscribers.insert(id);
+    }
+
+    fn disconnected(&mut self, id: HandlerId) {
+        self.subscribers.remove(&id);
+    }
+}
+
+impl LabelService {
+    fn lookup(&mut self, pid: Pid) {
+        let request = http::Request::get(Self::get_lookup_uri(&pid))
+            .header("Accept", "application/json")
+            .body(None)
+            .expect("Failed to build this request.");
+        send_to_agent(&self.link, &self.transport, request, move |outcome| {
+            let label = match outcome {
+                Ok(response) if response.status().is_success() => Label::from_type_definition(response.body()),
+                Ok(response) => {
+                    log::debug!("No label for {}: {:?}", pid, response.status());
+                    None
+                }
+                Err(e) => {
+                    log::debug!("No label for {}: {}", pid, e);
+                    None
+                }
+            };
+            Response::Label(pid.clone(), label)
+        });
+    }
+
+    fn get_lookup_uri(pid: &Pid) -> String {
+        format!("{}/{}", crate::config::dtr_base_uri(), pid.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_registry_type_definitions() {
+        let wrapped = r#"{ "id": "21.T11148/b8457812905b83046284", "type": "PID-BasicInfoType",
+            "content": { "name": "digitalObjectLocation", "description": "Points to a location of the object." } }"#;
+        assert_eq!(
+            Label::from_type_definition(wrapped),
+            Some(Label {
+                name: "digitalObjectLocation".into(),
+                description: Some("Points to a location of the object.".into()),
+            })
+        );
+        let plain = r#"{ "name": "version", "description": "" }"#;
+        assert_eq!(
+            Label::from_type_definition(plain),
+            Some(Label { name: "version".into(), description: None })
+        );
+        assert_eq!(Label::from_type_definition("<html/>"), None);
+    }
+}
@@ -0,0 +1,77 @@
+use yew::prelude::*;
+
+use crate::{
+    auth::{self, oidc::Identity, AuthManager, Credentials},
+    config,
+};
+
+/// Shows who is logged in, with buttons to log in and out.
+/// Without login provider, it only tells if configured credentials are used.
+pub struct LoginButton {
+    link: ComponentLink<Self>,
+    auth_manager: Box<dyn Bridge<AuthManager>>,
+    identity: Option<Identity>,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    Identity(Option<Identity>),
+    Login,
+    Logout,
+}
+
+impl Component for LoginButton {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut auth_manager = AuthManager::bridge(link.callback(|response| match response {
+            auth::Outgoing::Identity(identity) => Msg::Identity(identity),
+        }));
+        auth_manager.send(auth::Incoming::GetIdentity);
+        Self {
+            link,
+            auth_manager,
+            identity: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Identity(identity) => {
+                self.identity = identity;
+                return true;
+            }
+            Msg::Login => self.auth_manager.send(auth::Incoming::Login),
+            Msg::Logout => self.auth_manager.send(auth::Incoming::Logout),
+        }
+        false
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        match &self.identity {
+            Some(identity) => html! {
+                <div class="account-bar">
+                    <span class="identity" title=identity.subject.clone()>{ identity.display_name() }</span>
+                    <button onclick=self.link.callback(|_| Msg::Logout)>{ "Logout" }</button>
+                </div>
+            },
+            None if config::oidc_issuer().is_some() => html! {
+                <div class="account-bar">
+                    <span class="identity">{ "Not logged in" }</span>
+                    <button onclick=self.link.callback(|_| Msg::Login)>{ "Login" }</button>
+                </div>
+            },
+            None if Credentials::configured() != Credentials::Anonymous => html! {
+                <div class="account-bar">
+                    <span class="identity">{ "Using the configured credentials" }</span>
+                </div>
+            },
+            None => html! {},
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use yew::prelude::*;
+
+use crate::{
+    data_type_registry::Pid,
+    label_service::{Label, LabelService, Request, Response},
+};
+
+/// Shows the name of a PID as registered in the data type registry,
+/// with its description as tooltip. Until the name is known,
+/// or if the registry does not know the PID, the fallback is shown.
+pub struct PidLabel {
+    props: Props,
+    label_service: Box<dyn Bridge<LabelService>>,
+    label: Option<Label>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub pid: Pid,
+    /// Shown if no name is known. Defaults to the PID.
+    #[prop_or_default]
+    pub fallback: String,
+}
+
+pub enum Msg {
+    Response(Response),
+}
+
+impl Component for PidLabel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut label_service = LabelService::bridge(link.callback(Msg::Response));
+        label_service.send(Request::Lookup(props.pid.clone()));
+        Self {
+            props,
+            label_service,
+            label: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let Msg::Response(Response::Label(pid, label)) = msg;
+        if pid != self.props.pid || label == self.label {
+            return false;
+        }
+        self.label = label;
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props.pid != self.props.pid {
+            self.label = None;
+            self.label_service.send(Request::Lookup(props.pid.clone()));
+        }
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let (name, description) = match &self.label {
+            Some(label) => (label.name.clone(), label.description.clone()),
+            None if self.props.fallback.is_empty() => (self.props.pid.0.clone(), None),
+            None => (self.props.fallback.clone(), None),
+        };
+        let title = description.unwrap_or_else(|| self.props.pid.0.clone());
+        html! { <span class="pid-label" title=title>{ name }</span> }
+    }
+}
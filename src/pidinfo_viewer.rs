@@ -1,7 +1,7 @@
 use yew::{Component, ComponentLink, prelude::*};
 use yew_router::prelude::*;
 
-use crate::{Model, AppRoute, Msg, data_type_registry::Pid, pidinfo::PidInfo};
+use crate::{Model, AppRoute, Msg, data_type_registry::Pid, duplicate_button::DuplicateButton, pidinfo::PidInfo};
 
 
 pub struct PidInfoView {
@@ -12,6 +12,15 @@ pub struct PidInfoView {
 pub struct Props {
     pub model_link: ComponentLink<Model>,
     pub record: PidInfo,
+    #[prop_or_default]
+    pub selected: bool,
+    #[prop_or_default]
+    pub pinned: bool,
+    /// Why changes of the object are waiting in the outbox, if they are.
+    #[prop_or_default]
+    pub pending: Option<String>,
+    #[prop_or_default]
+    pub on_pin: Callback<()>,
 }
 
 impl Component for PidInfoView {
@@ -34,13 +43,33 @@ impl Component for PidInfoView {
     fn view(&self) -> yew::Html {
         let pid = self.props.record.pid().clone();
         let pid2 = Pid(pid.clone());
+        let pid3 = pid2.clone();
+        let status = self.props.record.status;
+        let state = if self.props.record.status_unpublished() {
+            format!("{:?}, {} (not published)", self.props.record.state(), status)
+        } else if status.is_tombstoned() {
+            format!("{:?}, {}", self.props.record.state(), status)
+        } else {
+            format!("{:?}", self.props.record.state())
+        };
+        let (pin_class, pin_title) = if self.props.pinned { ("pinned", "Unpin") } else { ("", "Pin") };
         html! {
-                <div class="piditem">
+                <div class=("piditem", status.css_class())>
+                <input type="checkbox" class="fdo-select" title="Select for bulk editing" checked=self.props.selected
+                    onclick=self.props.model_link.callback( move |_| Msg::ToggleSelection(pid3.clone()) ) />
                 <RouterButton<AppRoute> route=AppRoute::Details{path: pid.clone()} classes="fdo-button">
                     <p>{ pid.as_str() }</p>
                     <p>{ self.props.record.describe() }</p>
-                    <p>{ format!("{:?}", self.props.record.state()) }</p>
+                    <p>{ state }</p>
+                    {
+                        match &self.props.pending {
+                            Some(reason) => html! { <p class="pending-sync" title=reason.clone()>{ "⟳ pending sync" }</p> },
+                            None => html! {},
+                        }
+                    }
                 </RouterButton<AppRoute>>
+                <DuplicateButton record=self.props.record.clone() compact=true />
+                <button onclick=self.props.on_pin.reform(|_| ()) class=("fdo-pin-button", pin_class) title=pin_title>{"📌"}</button>
                 <button onclick=self.props.model_link.callback( move |_| Msg::PidRemove(pid2.clone()) ) class="fdo-remove-button">{"✗"}</button>
                 </div>
         }
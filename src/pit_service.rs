@@ -1,50 +1,83 @@
 use std::collections::HashSet;
 
-use anyhow::Error;
-use serde_json as json;
 use yew::{
     prelude::*,
     agent::Dispatcher,
-    format::Json,
-    services::fetch,
-    services::fetch::FetchTask,
-    services::FetchService,
     worker::Agent,
     worker::AgentLink,
     worker::Context,
     worker::HandlerId,
-    Callback,
 };
 
 use crate::{
-    app_state::pid_manager::PidManager, data_type_registry::Pid, pidinfo::PidInfo,
-    service_communication::PidRecord,
+    app_state::{
+        outbox::Operation,
+        outbox_manager::{self, OutboxManager},
+        pid_manager::PidManager,
+    },
+    data_type_registry::Pid,
+    pidinfo::PidInfo,
+    service_communication::{
+        handle_api, pipeline, pit_api, secured_pipeline, send_to_agent,
+        transport::{transient_failure, unsent_failure, Outcome, Pipeline},
+        ApiRequest,
+        PidRecord,
+    },
 };
 
 pub struct PitService {
     link: AgentLink<PitService>,
     subscribers: HashSet<HandlerId>,
 
-    task: Option<FetchTask>,
+    transport: Pipeline,
+    /// For the Handle System, which is public.
+    handle_transport: Pipeline,
     pid_manager: Dispatcher<PidManager>,
+    outbox: Dispatcher<OutboxManager>,
 }
 
 #[derive(Debug)]
 pub enum Request {
     Register(PidInfo),
     Update(PidInfo),
+    /// Resolves a record without adding it to the workspace.
+    /// PIDs unknown to the PIT service are resolved with the Handle System.
+    Resolve(Pid),
+    /// Registers a record without adding it to the workspace, i.e. a policy object.
+    RegisterRecord(PidRecord),
 }
 
 #[derive(Debug, Clone)]
 pub enum Response {
     Registered(Pid, PidRecord),
-    Updated(PidInfo),
+    Updated(PidRecord),
+    Resolved(Pid, PidRecord),
+    /// Intermediate step of a resolution: the PIT service does not know the PID,
+    /// so it is resolved with the Handle System. Subscribers will not be notified.
+    UnknownToPit(Pid),
+    ResolveFailed(Pid, String),
+    /// Only sent to the component which requested the registration of the record.
+    RecordRegistered(PidRecord),
+    /// Only sent to the component which requested the registration of the record.
+    RecordRegistrationFailed(String),
+    /// The registration of the draft timed out or failed at a gateway. The service may have
+    /// registered it anyway, so it is not sent again automatically.
+    RegistrationUnknown(Pid, String),
+    /// The service was unreachable. The registration or update is kept in the outbox
+    /// and will be published later.
+    Queued(Operation, String),
     Error(String),
 }
 
+/// Responses are sent to all subscribers, or only to the component which sent the request.
+pub enum Msg {
+    Broadcast(Response),
+    Reply(HandlerId, Response),
+}
+
 impl Agent for PitService {
     type Reach = Context<Self>;
-    type Message = Response;
+    type Message = Msg;
     type Input = Request;
     type Output = Response;
 
@@ -53,27 +86,51 @@ impl Agent for PitService {
             link,
             subscribers: Default::default(),
 
-            task: None,
+            transport: secured_pipeline("PIT service"),
+            handle_transport: pipeline("Handle resolver"),
             pid_manager: PidManager::dispatcher(),
+            outbox: OutboxManager::dispatcher(),
         }
     }
 
     fn update(&mut self, msg: Self::Message) {
         use crate::app_state::pid_manager::Incoming as PidMsg;
+        let msg = match msg {
+            Msg::Broadcast(response) => response,
+            Msg::Reply(id, response) => {
+                self.link.respond(id, response);
+                return;
+            }
+        };
         match msg.clone() {
+            Response::UnknownToPit(pid) => {
+                self.resolve_handle(pid);
+                return;
+            }
             Response::Error(e) => log::error!("PIT SERVICE ERROR: {}", e),
             Response::Registered(pid, record) => self.pid_manager.send(PidMsg::UpdateRecord(pid, record)),
-            Response::Updated(info) => self.pid_manager.send(PidMsg::UpdateRecord(Pid(info.pid().clone()), info.record)),
+            Response::Updated(record) => self.pid_manager.send(PidMsg::UpdateRecord(Pid(record.pid.clone()), record)),
+            Response::ResolveFailed(pid, e) => log::error!("Could not resolve {}: {}", pid, e),
+            Response::RegistrationUnknown(pid, reason) => {
+                log::error!("Registration of {} may have failed, check manually: {}", pid, reason);
+                self.pid_manager.send(PidMsg::MarkRegistrationUnknown(pid));
+            }
+            Response::Queued(operation, reason) => {
+                self.outbox.send(outbox_manager::Incoming::Queue(operation, reason))
+            }
+            Response::Resolved(..) | Response::RecordRegistered(_) | Response::RecordRegistrationFailed(_) => {}
         }
         for sub in self.subscribers.iter() {
             self.link.respond(*sub, msg.clone());
         }
     }
 
-    fn handle_input(&mut self, msg: Self::Input, _id: HandlerId) {
+    fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
         match msg {
             Request::Register(mut info) => self.register_pidinfo(&mut info),
             Request::Update(mut info) => self.update_pidinfo(&mut info),
+            Request::Resolve(pid) => self.resolve(pid),
+            Request::RegisterRecord(record) => self.register_record(record, id),
         }
     }
 
@@ -88,112 +145,85 @@ impl Agent for PitService {
 
 impl PitService {
     pub fn update_pidinfo(&mut self, info: &mut PidInfo) {
-        let pid = Pid(info.pid().clone());
-        let record: PidRecord = info.as_record();
-        let record = json::to_value(record).unwrap();
-        self.update_json(
-            &pid,
-            record,
-            self.link
-                .clone()
-                .callback(move |response: fetch::Response<Result<String, Error>>| {
-                    if response.status().is_success() {
-                        json::from_str(
-                            response
-                                .body()
-                                .as_ref()
-                                .expect("Get reference from body.")
-                                .as_str(),
-                        )
-                        .and_then(|record: PidRecord| {
-                            Ok(Response::Updated(PidInfo::from_registered(record)))
-                        })
-                        .unwrap_or_else(|e| {
-                            Response::Error(format!("Error parsing record: {:?}", e))
-                        })
-                    } else {
-                        Response::Error(format!("HTTP error: {:?}", response))
-                    }
-                }),
-        )
+        let record = info.as_record().publishable();
+        let request = pit_api::update_request(Self::get_base_uri(), &record);
+        self.send(&self.transport, request, move |outcome| {
+            if let Some(reason) = transient_failure(&outcome) {
+                return Response::Queued(Operation::Update(record.clone()), reason);
+            }
+            outcome
+                .and_then(|response| pit_api::parse_record(&response))
+                .map(Response::Updated)
+                .unwrap_or_else(Response::Error)
+        });
     }
 
     pub fn register_pidinfo(&mut self, info: &mut PidInfo) {
         let old_pid = Pid(info.pid().clone());
-        let record: PidRecord = info.as_record();
-        let record = json::to_value(record).unwrap();
-        self.register_json(
-            record,
-            self.link
-                .clone()
-                .callback(move |response: fetch::Response<Result<String, Error>>| {
-                    if response.status().is_success() {
-                        json::from_str(
-                            response
-                                .body()
-                                .as_ref()
-                                .expect("Get reference from body.")
-                                .as_str(),
-                        )
-                        .and_then(|record: PidRecord| {
-                            Ok(Response::Registered(
-                                old_pid.clone(), // might be registered or not
-                                record,
-                            ))
-                        })
-                        .unwrap_or_else(|e| {
-                            Response::Error(format!("Error parsing record: {:?}", e))
-                        })
-                    } else {
-                        Response::Error(format!("HTTP error: {:?}", response))
-                    }
-                }),
-        )
+        let record = info.as_record().publishable();
+        let request = pit_api::register_request(Self::get_base_uri(), &record);
+        self.send(&self.transport, request, move |outcome| {
+            // registering twice would create two objects, so only those never sent are queued.
+            if let Some(reason) = unsent_failure(&outcome) {
+                return Response::Queued(Operation::Register(record.clone()), reason);
+            }
+            if let Some(reason) = transient_failure(&outcome) {
+                return Response::RegistrationUnknown(old_pid.clone(), reason);
+            }
+            outcome
+                .and_then(|response| pit_api::parse_record(&response))
+                // the old pid might be registered or not
+                .map(|record| Response::Registered(old_pid.clone(), record))
+                .unwrap_or_else(Response::Error)
+        });
     }
 
-    fn register_json(
-        &mut self,
-        record: serde_json::Value,
-        callback: Callback<fetch::Response<Result<String, Error>>>,
-    ) {
-        log::debug!("register() was called.");
-        let request = fetch::Request::post(PitService::get_create_uri())
-            .header("Content-Type", "application/json")
-            .body(Json(&record))
-            .expect("Failed to build this request.");
-        let task = FetchService::fetch(request, callback)
-            .map_err(|e| log::error!("Error creating task to register metadata: {}", e));
-        self.task = task.ok();
-        log::debug!("register() has finished.");
+    pub fn resolve(&mut self, pid: Pid) {
+        log::debug!("resolve() was called.");
+        let request = pit_api::resolve_request(Self::get_base_uri(), &pid);
+        self.send(&self.transport, request, move |outcome| {
+            if matches!(&outcome, Ok(response) if response.status() == 404) {
+                return Response::UnknownToPit(pid.clone());
+            }
+            outcome
+                .and_then(|response| pit_api::parse_record(&response))
+                .map(|record| Response::Resolved(pid.clone(), record))
+                .unwrap_or_else(|e| Response::ResolveFailed(pid.clone(), e))
+        });
     }
 
-    fn update_json(
-        &mut self,
-        pid: &Pid,
-        record: serde_json::Value,
-        callback: Callback<fetch::Response<Result<String, Error>>>,
-    ) {
-        log::debug!("update() was called.");
-        let request = fetch::Request::put(Self::get_update_uri(&pid))
-            .header("Content-Type", "application/json")
-            .body(Json(&record))
-            .expect("Failed to build this request.");
-        let task = FetchService::fetch(request, callback)
-            .map_err(|e| log::error!("Error creating task to update metadata: {}", e));
-        self.task = task.ok();
-        log::debug!("update() has finished.");
+    /// Resolves objects registered elsewhere, as plain Handles.
+    fn resolve_handle(&mut self, pid: Pid) {
+        let request = handle_api::resolve_request(crate::config::handle_resolver_uri(), &pid);
+        self.send(&self.handle_transport, request, move |outcome| {
+            outcome
+                .and_then(|response| handle_api::parse_record(&response))
+                .map(|record| Response::Resolved(pid.clone(), record))
+                .unwrap_or_else(|e| Response::ResolveFailed(pid.clone(), e))
+        });
     }
 
-    // TODO make this configurable at compile/run time
-    fn get_base_uri() -> &'static str {
-        "http://localhost:8090"
+    /// Registers the record for the component `requester`. Others are not notified.
+    pub fn register_record(&mut self, record: PidRecord, requester: HandlerId) {
+        let request = pit_api::register_request(Self::get_base_uri(), &record.publishable());
+        send_to_agent(&self.link, &self.transport, request, move |outcome| {
+            let response = outcome
+                .and_then(|response| pit_api::parse_record(&response))
+                .map(Response::RecordRegistered)
+                .unwrap_or_else(Response::RecordRegistrationFailed);
+            Msg::Reply(requester, response)
+        });
     }
 
-    fn get_create_uri() -> String {
-        format!("{}/{}/", Self::get_base_uri(), "api/v1/pit/pid")
+    /// Sends the request. The response is sent to all subscribers.
+    fn send<F>(&self, transport: &Pipeline, request: ApiRequest, response: F)
+    where
+        F: Fn(Outcome) -> Response + 'static,
+    {
+        send_to_agent(&self.link, transport, request, move |outcome| Msg::Broadcast(response(outcome)));
     }
 
-    fn get_update_uri(pid: &Pid) -> String {
-        format!("{}/{}/{}", Self::get_base_uri(), "api/v1/pit/pid", *pid)
+    fn get_base_uri() -> &'static str {
+        crate::config::pit_base_uri()
     }
 }
@@ -0,0 +1,125 @@
+//! The service communication of the model, plus a transport using yew's fetch service.
+
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use yew::{
+    services::{
+        fetch::{self, FetchTask},
+        timeout::TimeoutTask,
+        FetchService, Task, TimeoutService,
+    },
+    worker::{Agent, AgentLink},
+    Callback,
+};
+
+pub use fairris_core::service_communication::*;
+use transport::{Done, Logging, Outcome, Pipeline, Retry, Transport};
+
+/// The transport of all service agents.
+pub fn pipeline(service: &'static str) -> Pipeline {
+    Pipeline::new(FetchTransport::default())
+        .with(Logging::new(service))
+        .with(Retry::new(crate::config::request_attempts()).with_backoff(Duration::from_secs(1)))
+}
+
+/// Like `pipeline`, but requests carry the credentials of the current login.
+/// Only for our own services, third parties must not see the tokens.
+pub fn secured_pipeline(service: &'static str) -> Pipeline {
+    pipeline(service).with(crate::auth::authorization())
+}
+
+/// Sends the request and turns its outcome into a message of the agent.
+pub fn send_to_agent<A, F>(link: &AgentLink<A>, transport: &impl Transport, request: ApiRequest, message: F)
+where
+    A: Agent,
+    F: Fn(Outcome) -> A::Message + 'static,
+{
+    let callback = link.callback(message);
+    transport.send(request, Box::new(move |outcome| callback.emit(outcome)));
+}
+
+/// Sends requests with yew's fetch service. The tasks are kept until they finished.
+/// Requests fail if they take longer than the configured timeout.
+#[derive(Default)]
+pub struct FetchTransport {
+    tasks: RefCell<Vec<FetchTask>>,
+    timers: RefCell<Vec<TimeoutTask>>,
+}
+
+impl FetchTransport {
+    fn start_timer(&self, delay: Duration, callback: Callback<()>) {
+        let mut timers = self.timers.borrow_mut();
+        timers.retain(|timer| timer.is_active());
+        timers.push(TimeoutService::spawn(delay, callback));
+    }
+}
+
+impl Transport for FetchTransport {
+    fn send(&self, request: ApiRequest, done: Done) {
+        // the callbacks may be called only once, but have to be `Fn`.
+        // Whichever comes first, the response or the timeout, is delivered.
+        let done = Rc::new(RefCell::new(Some(done)));
+        let on_response = done.clone();
+        let callback = Callback::from(move |response: fetch::Response<Result<String, anyhow::Error>>| {
+            if let Some(done) = on_response.borrow_mut().take() {
+                done(from_fetch(response));
+            }
+        });
+        let timeout = crate::config::request_timeout();
+        let on_timeout = done.clone();
+        let description = transport::describe(&request);
+        self.start_timer(
+            timeout,
+            Callback::from(move |_| {
+                if let Some(done) = on_timeout.borrow_mut().take() {
+                    done(Err(format!("{} timed out after {} s.", description, timeout.as_secs())));
+                }
+            }),
+        );
+        let mut tasks = self.tasks.borrow_mut();
+        tasks.retain(|task| task.is_active());
+        match FetchService::fetch(to_fetch(request), callback) {
+            Ok(task) => tasks.push(task),
+            Err(e) => {
+                if let Some(done) = done.borrow_mut().take() {
+                    done(Err(transport::not_sent(format!("Could not send the request: {}", e))));
+                }
+            }
+        }
+    }
+
+    fn wait(&self, delay: Duration, then: Box<dyn FnOnce()>) {
+        let then = RefCell::new(Some(then));
+        self.start_timer(
+            delay,
+            Callback::from(move |_| {
+                if let Some(then) = then.borrow_mut().take() {
+                    then();
+                }
+            }),
+        );
+    }
+}
+
+/// Makes the request sendable with yew's fetch service.
+fn to_fetch(request: ApiRequest) -> http::Request<Result<String, anyhow::Error>> {
+    // yew sends no body for errors.
+    request.map(|body| body.ok_or_else(|| anyhow::anyhow!("Request without body.")))
+}
+
+/// Reads a response of yew's fetch service. Unreadable bodies become empty.
+/// yew reports unreachable services as status 408 without headers and body. The browser does
+/// not tell whether the connection failed before or after the request was sent, so only
+/// requests while the browser is offline are known to be not sent.
+pub fn from_fetch(response: fetch::Response<Result<String, anyhow::Error>>) -> Outcome {
+    let unreachable = response.status() == 408 && response.headers().is_empty();
+    match response.into_parts() {
+        (_, Err(e)) if unreachable && is_offline() => Err(transport::not_sent(format!("The browser is offline: {}", e))),
+        (_, Err(e)) if unreachable => Err(format!("The service is unreachable: {}", e)),
+        (parts, body) => Ok(http::Response::from_parts(parts, body.unwrap_or_default())),
+    }
+}
+
+fn is_offline() -> bool {
+    web_sys::window().is_some_and(|window| !window.navigator().on_line())
+}
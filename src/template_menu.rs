@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use yew::prelude::*;
+use yew_router::{agent::RouteRequest, prelude::*};
+
+use crate::{
+    app_state::{
+        data::{Data, DataID},
+        data_manager::{self, DataManager},
+        pid_manager::{self, PidManager},
+        template_manager::{self, TemplateManager},
+        templates::{random_uuid, Template},
+    },
+    AppRoute,
+};
+
+const CHOOSE: &str = "Add from template…";
+
+/// Creates new drafts from templates and opens them.
+pub struct TemplateMenu {
+    link: ComponentLink<Self>,
+
+    _template_manager: Box<dyn Bridge<TemplateManager>>,
+    pid_manager: Box<dyn Bridge<PidManager>>,
+    data_manager: Box<dyn Bridge<DataManager>>,
+    router: RouteAgentDispatcher,
+
+    templates: Vec<Template>,
+    known_data: HashMap<DataID, Data>,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    SetTemplates(Vec<Template>),
+    SetData(HashMap<DataID, Data>),
+    TemplateSelected(String),
+    Created(pid_manager::Outgoing),
+    Error(String),
+    Noop,
+}
+
+impl Component for TemplateMenu {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut template_manager = TemplateManager::bridge(link.callback(|msg| match msg {
+            template_manager::Outgoing::AllTemplates(templates) => Msg::SetTemplates(templates),
+        }));
+        template_manager.send(template_manager::Incoming::GetAllTemplates);
+        let pid_manager = PidManager::bridge(link.callback(Msg::Created));
+        let mut data_manager = DataManager::bridge(link.callback(|msg| match msg {
+            data_manager::Outgoing::AllData(data) => Msg::SetData(data),
+            data_manager::Outgoing::SelectedData(_) => Msg::Noop,
+        }));
+        data_manager.send(data_manager::Incoming::GetAllData);
+        Self {
+            link,
+            _template_manager: template_manager,
+            pid_manager,
+            data_manager,
+            router: RouteAgentDispatcher::new(),
+            templates: Vec::new(),
+            known_data: Default::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetTemplates(templates) => self.templates = templates,
+            Msg::SetData(data) => {
+                self.known_data = data;
+                return false;
+            }
+            Msg::TemplateSelected(name) => {
+                match self.templates.iter().find(|t| t.name == name) {
+                    Some(template) => self.create_draft(template.clone()),
+                    None if name == CHOOSE => {}
+                    None => log::error!("Template {} does not exist.", name),
+                }
+            }
+            Msg::Created(pid_manager::Outgoing::Created(pid)) => {
+                let route = Route::from(AppRoute::Details { path: pid.0 });
+                self.router.send(RouteRequest::ChangeRoute(route));
+                return false;
+            }
+            Msg::Created(_) => return false,
+            Msg::Error(e) => {
+                log::error!("Template menu error: {}", e);
+                return false;
+            }
+            Msg::Noop => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        if self.templates.is_empty() {
+            return html! {
+                <RouterButton<AppRoute> route=AppRoute::Templates>{ "Templates" }</RouterButton<AppRoute>>
+            };
+        }
+        let on_select = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => {
+                let name = element.value();
+                // the menu acts like a button, so it shows the prompt again.
+                element.set_value(CHOOSE);
+                Msg::TemplateSelected(name)
+            }
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        html! {
+            <div class="template-menu">
+                <select onchange=on_select>
+                    <option value=CHOOSE selected=true>{ CHOOSE }</option>
+                    { for self.templates.iter().map(|t| html! { <option value=t.name.clone()>{ &t.name }</option> }) }
+                </select>
+                <RouterButton<AppRoute> route=AppRoute::Templates>{ "Templates" }</RouterButton<AppRoute>>
+            </div>
+        }
+    }
+}
+
+impl TemplateMenu {
+    fn create_draft(&mut self, template: Template) {
+        let now = chrono::Utc::now().to_rfc3339();
+        let (mut info, data) = template.instantiate(&now, &random_uuid());
+        if let Some(data) = data {
+            let id = loop {
+                let id = DataID(rand::thread_rng().gen::<u16>());
+                if !self.known_data.contains_key(&id) {
+                    break id;
+                }
+            };
+            self.known_data.insert(id, data.clone());
+            self.data_manager.send(data_manager::Incoming::UpdateData(id, data));
+            info.data = Some(id);
+        }
+        self.pid_manager
+            .send(pid_manager::Incoming::AddUnregisteredPidInfo(info));
+    }
+}
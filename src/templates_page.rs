@@ -0,0 +1,153 @@
+use yew::{
+    prelude::*,
+    services::reader::{File, FileData, ReaderService, ReaderTask},
+};
+
+use crate::{
+    app_state::{
+        template_manager::{self, TemplateManager},
+        templates::{Template, TemplateFile, PLACEHOLDERS},
+    },
+    details_page::helpers::Dom,
+};
+
+/// Lists, removes, exports and imports templates for new drafts.
+/// Templates are created on the details page of an object.
+pub struct TemplatesPage {
+    link: ComponentLink<Self>,
+
+    template_manager: Box<dyn Bridge<TemplateManager>>,
+    reader: ReaderService,
+    reader_task: Option<ReaderTask>,
+
+    templates: Vec<Template>,
+    status: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    SetTemplates(Vec<Template>),
+    Remove(String),
+    Export,
+    FileSelected(File),
+    FileLoaded(FileData),
+    Error(String),
+    Noop,
+}
+
+impl Component for TemplatesPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut template_manager = TemplateManager::bridge(link.callback(|msg| match msg {
+            template_manager::Outgoing::AllTemplates(templates) => Msg::SetTemplates(templates),
+        }));
+        template_manager.send(template_manager::Incoming::GetAllTemplates);
+        Self {
+            link,
+            template_manager,
+            reader: ReaderService::new(),
+            reader_task: None,
+            templates: Vec::new(),
+            status: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetTemplates(templates) => self.templates = templates,
+            Msg::Remove(name) => {
+                self.template_manager
+                    .send(template_manager::Incoming::Remove(name));
+                return false;
+            }
+            Msg::Export => {
+                let file = TemplateFile::new(self.templates.clone());
+                if let Err(e) = Dom::download("templates.fairris.json", "application/json", file.to_json().as_bytes()) {
+                    log::error!("Could not download templates: {:?}", e);
+                }
+                return false;
+            }
+            Msg::FileSelected(file) => {
+                let callback = self.link.callback(Msg::FileLoaded);
+                self.reader_task = self.reader.read_file(file, callback)
+                    .map_err(|e| log::error!("Could not read file: {}", e))
+                    .ok();
+                return false;
+            }
+            Msg::FileLoaded(file) => {
+                self.reader_task = None;
+                let content = String::from_utf8_lossy(&file.content);
+                match TemplateFile::from_json(&content) {
+                    Ok(imported) => {
+                        self.status = Some(format!("Imported {} template(s) from {}.", imported.templates.len(), file.name));
+                        self.template_manager
+                            .send(template_manager::Incoming::Import(imported.templates));
+                    }
+                    Err(e) => self.link.send_message(Msg::Error(e)),
+                }
+            }
+            Msg::Error(e) => {
+                log::error!("Template import/export error: {}", e);
+                self.status = Some(e);
+            }
+            Msg::Noop => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        let on_file = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Files(files) => files.get(0).map(Msg::FileSelected).unwrap_or(Msg::Noop),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        html! {
+            <div id="content" class="maincolumns scroll-vertical">
+                <h2>{ "Templates" }</h2>
+                <p class="hint-text">
+                    { "Save an object as template on its details page. New drafts can be created from templates in the sidebar. These placeholders are replaced in all values:" }
+                </p>
+                <ul>
+                    { for PLACEHOLDERS.iter().map(|(placeholder, meaning)| html! { <li><code>{ placeholder }</code>{ " – " }{ meaning }</li> }) }
+                </ul>
+                <div class="column-form">
+                    <button onclick=self.link.callback(|_| Msg::Export) disabled=self.templates.is_empty()>{ "Export templates" }</button>
+                    <label class="file-button">
+                        { "Import templates" }
+                        <input type="file" accept=".json,application/json" onchange=on_file />
+                    </label>
+                </div>
+                { for self.status.iter().map(|status| html! { <p class="status-text">{ status }</p> }) }
+                <div class="template-list">
+                    { for self.templates.iter().map(|t| self.view_template(t)) }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl TemplatesPage {
+    fn view_template(&self, template: &Template) -> Html {
+        let name = template.name.clone();
+        let data = template
+            .data
+            .as_ref()
+            .map_or_else(|| "No data".to_string(), |d| d.type_name());
+        let placeholders = template.placeholders().join(", ");
+        html! {
+            <div class="template-item">
+                <p><strong>{ &template.name }</strong></p>
+                <p>{ template.record.describe() }</p>
+                <p>{ data }</p>
+                <p><code>{ placeholders }</code></p>
+                <button class="fdo-remove-button" title="Remove template"
+                    onclick=self.link.callback(move |_| Msg::Remove(name.clone()))>{ "✗" }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use strum::IntoEnumIterator;
+use yew::{
+    prelude::*,
+    services::reader::{File, FileData, ReaderService, ReaderTask},
+};
+
+use crate::{
+    app_state::{
+        data::{Data, DataID},
+        data_manager::{self, DataManager},
+        pid_manager::{self, PidManager},
+        workspace::{Conflict, WorkspaceFile},
+    },
+    data_type_registry::Pid,
+    details_page::helpers::Dom,
+    pidinfo::PidInfo,
+};
+
+/// Exports (and optionally imports) workspaces as JSON files.
+pub struct WorkspaceIo {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    pid_manager: Box<dyn Bridge<PidManager>>,
+    data_manager: Box<dyn Bridge<DataManager>>,
+    reader: ReaderService,
+    reader_task: Option<ReaderTask>,
+
+    known_pids: HashMap<Pid, PidInfo>,
+    known_data: HashMap<DataID, Data>,
+    conflict: Conflict,
+    status: Option<String>,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    /// The objects to export, i.e. the selection. If empty, the whole workspace will be exported.
+    #[prop_or_default]
+    pub pids: Vec<Pid>,
+    #[prop_or(true)]
+    pub allow_import: bool,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    SetPidInfos(HashMap<Pid, PidInfo>),
+    SetData(HashMap<DataID, Data>),
+
+    Export,
+    ConflictSelected(String),
+    FileSelected(File),
+    FileLoaded(FileData),
+    Error(String),
+    Noop,
+}
+
+impl Component for WorkspaceIo {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut pid_manager = PidManager::bridge(link.callback(|msg| match msg {
+            pid_manager::Outgoing::AllPidInformation(infos) => Msg::SetPidInfos(infos),
+            pid_manager::Outgoing::Created(_) => Msg::Noop,
+        }));
+        pid_manager.send(pid_manager::Incoming::GetAllPidInformation);
+        let mut data_manager = DataManager::bridge(link.callback(|msg| match msg {
+            data_manager::Outgoing::AllData(data) => Msg::SetData(data),
+            data_manager::Outgoing::SelectedData(_) => Msg::Noop,
+        }));
+        data_manager.send(data_manager::Incoming::GetAllData);
+        Self {
+            link,
+            props,
+            pid_manager,
+            data_manager,
+            reader: ReaderService::new(),
+            reader_task: None,
+            known_pids: Default::default(),
+            known_data: Default::default(),
+            conflict: Conflict::default(),
+            status: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetPidInfos(infos) => self.known_pids = infos,
+            Msg::SetData(data) => self.known_data = data,
+            Msg::Export => self.export(),
+            Msg::ConflictSelected(name) => {
+                self.conflict = Conflict::iter()
+                    .find(|c| c.to_string() == name)
+                    .unwrap_or_default();
+            }
+            Msg::FileSelected(file) => {
+                let callback = self.link.callback(Msg::FileLoaded);
+                self.reader_task = self.reader.read_file(file, callback)
+                    .map_err(|e| log::error!("Could not read file: {}", e))
+                    .ok();
+            }
+            Msg::FileLoaded(file) => {
+                self.reader_task = None;
+                self.import(file);
+            }
+            Msg::Error(e) => {
+                log::error!("Workspace import/export error: {}", e);
+                self.status = Some(e);
+            }
+            Msg::Noop => return false,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let export_label = match self.props.pids.len() {
+            0 => "Export workspace",
+            1 => "Export object",
+            _ => "Export selection",
+        };
+        let import = if self.props.allow_import {
+            let on_conflict = self.link.callback(|e: ChangeData| match e {
+                ChangeData::Select(element) => Msg::ConflictSelected(element.value()),
+                other => Msg::Error(format!("Got unexpected: {:?}", other)),
+            });
+            let on_file = self.link.callback(|e: ChangeData| match e {
+                ChangeData::Files(files) => files
+                    .get(0)
+                    .map(Msg::FileSelected)
+                    .unwrap_or(Msg::Noop),
+                other => Msg::Error(format!("Got unexpected: {:?}", other)),
+            });
+            html! {
+                <>
+                    <select class="form-input" onchange=on_conflict title="If an imported PID already exists:">
+                        {
+                            for Conflict::iter().map(|c: Conflict| {
+                                let selected = c == self.conflict;
+                                html! { <option selected=selected>{ c }</option> }
+                            })
+                        }
+                    </select>
+                    <label class="file-button">
+                        { "Import workspace" }
+                        <input type="file" accept=".json,application/json" onchange=on_file />
+                    </label>
+                </>
+            }
+        } else {
+            html! {}
+        };
+        html! {
+            <div class="workspace-io">
+                <button onclick=self.link.callback(|_| Msg::Export)>{ export_label }</button>
+                { import }
+                {
+                    self.status.as_ref().map_or_else(
+                        || html! {},
+                        |status| html! { <p class="status-text">{ status }</p> },
+                    )
+                }
+            </div>
+        }
+    }
+}
+
+impl WorkspaceIo {
+    fn export(&mut self) {
+        let file = WorkspaceFile::export_selection(&self.props.pids, &self.known_pids, &self.known_data);
+        let filename = WorkspaceFile::file_name(&self.props.pids);
+        if let Err(e) = Dom::download(filename, "application/json", file.to_json().as_bytes()) {
+            log::error!("Could not download workspace: {:?}", e);
+        }
+    }
+
+    fn import(&mut self, file: FileData) {
+        let content = String::from_utf8_lossy(&file.content);
+        match WorkspaceFile::from_json(&content) {
+            Ok(workspace) => {
+                let import = workspace.import(&self.known_pids, &self.known_data, self.conflict);
+                for (id, data) in import.data {
+                    self.data_manager
+                        .send(data_manager::Incoming::UpdateData(id, data));
+                }
+                let imported = import.pid_infos.len();
+                for info in import.pid_infos {
+                    self.pid_manager.send(pid_manager::Incoming::AddPidInfo(info));
+                }
+                self.status = Some(format!(
+                    "Imported {} object(s) from {}, skipped {}.",
+                    imported,
+                    file.name,
+                    import.skipped.len()
+                ));
+            }
+            Err(e) => self.link.send_message(Msg::Error(e.to_string())),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::{HashMap, HashSet};
+
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+use crate::{
+    app_state::{
+        list_preferences::{Grouping, ListPreferences, SortKey},
+        outbox::Outbox,
+        outbox_manager::{self, OutboxManager},
+    },
+    data_type_registry::Pid,
+    pidinfo::PidInfo,
+    pidinfo_viewer::PidInfoView,
+    Model,
+};
+
+/// Lists the objects of the workspace in the sidebar.
+/// Sorting, grouping, filter and pinned objects are kept in the local storage.
+/// Objects with changes waiting in the outbox are marked.
+pub struct WorkspaceList {
+    link: ComponentLink<Self>,
+    props: Props,
+    preferences: ListPreferences,
+    outbox_manager: Box<dyn Bridge<OutboxManager>>,
+    outbox: Outbox,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub model_link: ComponentLink<Model>,
+    pub known_pids: HashMap<Pid, PidInfo>,
+    /// Objects selected for bulk editing.
+    pub selection: HashSet<Pid>,
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    FilterChanged(String),
+    SortSelected(String),
+    ToggleDirection,
+    GroupingSelected(String),
+    TogglePin(Pid),
+    Outbox(Outbox),
+    SyncNow,
+    Error(String),
+}
+
+impl Component for WorkspaceList {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let mut outbox_manager = OutboxManager::bridge(link.callback(|response| match response {
+            outbox_manager::Outgoing::Outbox(outbox) => Msg::Outbox(outbox),
+        }));
+        outbox_manager.send(outbox_manager::Incoming::GetOutbox);
+        Self {
+            link,
+            props,
+            preferences: ListPreferences::load(),
+            outbox_manager,
+            outbox: Outbox::default(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::FilterChanged(filter) => self.preferences.filter = filter,
+            Msg::SortSelected(name) => {
+                self.preferences.sort = SortKey::parse(&name).unwrap_or(SortKey::Pid);
+            }
+            Msg::ToggleDirection => self.preferences.descending = !self.preferences.descending,
+            Msg::GroupingSelected(name) => {
+                self.preferences.grouping = Grouping::parse(&name).unwrap_or(Grouping::None);
+            }
+            Msg::TogglePin(pid) => self.preferences.toggle_pin(&pid),
+            Msg::Outbox(outbox) => {
+                self.outbox = outbox;
+                return true;
+            }
+            Msg::SyncNow => {
+                self.outbox_manager.send(outbox_manager::Incoming::Replay);
+                return false;
+            }
+            Msg::Error(e) => {
+                log::error!("Workspace list error: {}", e);
+                return false;
+            }
+        }
+        self.preferences.store();
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let on_sort = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::SortSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let on_grouping = self.link.callback(|e: ChangeData| match e {
+            ChangeData::Select(element) => Msg::GroupingSelected(element.value()),
+            other => Msg::Error(format!("Got unexpected: {:?}", other)),
+        });
+        let direction = if self.preferences.descending { "↓" } else { "↑" };
+        let groups = self.preferences.arrange(&self.props.known_pids);
+        html! {
+            <>
+                <div class="workspace-controls">
+                    <input type="search" placeholder="Filter" value=self.preferences.filter.clone()
+                        oninput=self.link.callback(|e: InputData| Msg::FilterChanged(e.value)) />
+                    <select onchange=on_sort title="Sort by">
+                        {
+                            for SortKey::iter().map(|key| {
+                                let selected = key == self.preferences.sort;
+                                html! { <option selected=selected>{ key }</option> }
+                            })
+                        }
+                    </select>
+                    <button onclick=self.link.callback(|_| Msg::ToggleDirection) title="Sort direction">{ direction }</button>
+                    <select onchange=on_grouping title="Grouping">
+                        {
+                            for Grouping::iter().map(|grouping| {
+                                let selected = grouping == self.preferences.grouping;
+                                html! { <option selected=selected>{ grouping }</option> }
+                            })
+                        }
+                    </select>
+                </div>
+                { self.view_outbox() }
+                <div id="workspace" class="scroll-vertical">
+                    {
+                        for groups.into_iter().map(|group| html! {
+                            <>
+                                { if group.name.is_empty() { html! {} } else { html! { <p class="group-name">{ group.name }</p> } } }
+                                { for group.items.into_iter().map(|info| self.view_item(info)) }
+                            </>
+                        })
+                    }
+                </div>
+            </>
+        }
+    }
+}
+
+impl WorkspaceList {
+    fn view_item(&self, info: &PidInfo) -> Html {
+        let pid = Pid(info.pid().clone());
+        let pinned = self.preferences.is_pinned(&pid);
+        let selected = self.props.selection.contains(&pid);
+        // changes of the record or of its data.
+        let pending = self
+            .outbox
+            .iter()
+            .find(|p| p.operation.pid().as_ref() == Some(&pid) || (info.data.is_some() && p.operation.data() == info.data))
+            .map(|p| p.reason.clone());
+        html! {
+            <PidInfoView
+                model_link=self.props.model_link.clone()
+                record=info.clone()
+                selected=selected
+                pinned=pinned
+                pending=pending
+                on_pin=self.link.callback(move |_| Msg::TogglePin(pid.clone()))
+            />
+        }
+    }
+
+    fn view_outbox(&self) -> Html {
+        if self.outbox.is_empty() {
+            return html! {};
+        }
+        html! {
+            <div class="outbox-bar">
+                <span>{ format!("{} change(s) waiting to be published", self.outbox.len()) }</span>
+                <button onclick=self.link.callback(|_| Msg::SyncNow)>{ "Sync now" }</button>
+            </div>
+        }
+    }
+}
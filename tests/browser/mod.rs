@@ -0,0 +1,184 @@
+//! Helpers for the browser tests: an in-process fake of the services and DOM access.
+
+use std::cell::RefCell;
+
+use fairris::Model;
+use fairris_mock_services::{http, store::Store};
+use js_sys::{Array, Promise};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::{Element, Event, HtmlElement, HtmlInputElement, HtmlSelectElement, ResponseInit, Url};
+use yew::App;
+use yew_router::{agent::RouteRequest, prelude::*};
+
+thread_local! {
+    static STORE: RefCell<Store> = RefCell::new(Store::default());
+    /// Method and path of every request the fake answered.
+    static REQUESTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static INSTALLED: RefCell<bool> = const { RefCell::new(false) };
+}
+
+/// Replaces `window.fetch`, so all requests of the agents are answered by the mock services,
+/// running in this process. Requests to other endpoints (i.e. the DTR) get a 404.
+pub fn install_fake_services() {
+    if INSTALLED.with(|installed| installed.replace(true)) {
+        return;
+    }
+    let fetch = Closure::wrap(Box::new(|input: JsValue, init: JsValue| -> Promise {
+        future_to_promise(answer(input, init))
+    }) as Box<dyn FnMut(JsValue, JsValue) -> Promise>);
+    let window = web_sys::window().unwrap();
+    js_sys::Reflect::set(&window, &"fetch".into(), fetch.as_ref()).unwrap();
+    fetch.forget();
+}
+
+async fn answer(input: JsValue, init: JsValue) -> Result<JsValue, JsValue> {
+    let request = match input.dyn_into::<web_sys::Request>() {
+        Ok(request) => request,
+        Err(url) => {
+            let url = url.as_string().unwrap_or_default();
+            web_sys::Request::new_with_str_and_init(&url, init.unchecked_ref())?
+        }
+    };
+    let body = JsFuture::from(request.text()?).await?.as_string().unwrap_or_default();
+    let mut headers = std::collections::HashMap::new();
+    for entry in js_sys::try_iter(&request.headers())?.unwrap() {
+        let entry: Array = entry?.unchecked_into();
+        let name = entry.get(0).as_string().unwrap_or_default().to_lowercase();
+        headers.insert(name, entry.get(1).as_string().unwrap_or_default());
+    }
+    let url = Url::new(&request.url())?;
+    let path = String::from(js_sys::decode_uri(&url.pathname())?);
+    let query = url.search().trim_start_matches('?').to_owned();
+    let mock_request = http::Request {
+        method: request.method(),
+        path,
+        query,
+        headers,
+        body,
+    };
+    REQUESTS.with(|r| r.borrow_mut().push(format!("{} {}", mock_request.method, mock_request.path)));
+    let response = STORE.with(|store| fairris_mock_services::handle(&mut store.borrow_mut(), &mock_request));
+
+    let response_headers = web_sys::Headers::new()?;
+    for (name, value) in response.headers.iter() {
+        response_headers.set(name, value)?;
+    }
+    let mut response_init = ResponseInit::new();
+    response_init.status(response.status).headers(&response_headers);
+    // responses like 204 must not have a body.
+    let body = Some(response.body.as_str()).filter(|b| !b.is_empty());
+    web_sys::Response::new_with_opt_str_and_init(body, &response_init).map(JsValue::from)
+}
+
+/// The requests answered so far, like `POST /api/v1/pit/pid/`.
+pub fn requests() -> Vec<String> {
+    REQUESTS.with(|r| r.borrow().clone())
+}
+
+/// A mounted application. `finish` has to be called at the end of each test.
+pub struct Fixture {
+    pub root: Element,
+}
+
+impl Fixture {
+    pub async fn mount() -> Self {
+        install_fake_services();
+        navigate("/");
+        let document = web_sys::window().unwrap().document().unwrap();
+        let root = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&root).unwrap();
+        App::<Model>::new().mount(root.clone());
+        let app = Self { root };
+        wait_until("the workspace list is shown", || app.button("Add").is_some()).await;
+        app
+    }
+
+    /// All applications of earlier tests are still alive (agents are shared), so they
+    /// are routed away from any page with DOM access before the root is removed.
+    pub async fn finish(self) {
+        navigate("/");
+        wait_until("the start page is shown", || current_path() == "/").await;
+        self.root.remove();
+    }
+
+    pub fn query(&self, selector: &str) -> Option<Element> {
+        self.root.query_selector(selector).unwrap()
+    }
+
+    pub fn query_all(&self, selector: &str) -> Vec<Element> {
+        let list = self.root.query_selector_all(selector).unwrap();
+        (0..list.length())
+            .filter_map(|i| list.item(i))
+            .filter_map(|node| node.dyn_into::<Element>().ok())
+            .collect()
+    }
+
+    /// The first button which shows exactly this text.
+    pub fn button(&self, text: &str) -> Option<HtmlElement> {
+        self.query_all("button")
+            .into_iter()
+            .find(|b| b.text_content().unwrap_or_default().trim() == text)
+            .map(|b| b.unchecked_into())
+    }
+
+    pub fn text(&self) -> String {
+        self.root.text_content().unwrap_or_default()
+    }
+
+    /// PIDs of the objects in the workspace list.
+    pub fn listed_pids(&self) -> Vec<String> {
+        self.query_all(".piditem .fdo-button p:first-child")
+            .into_iter()
+            .filter_map(|p| p.text_content())
+            .collect()
+    }
+}
+
+pub fn navigate(path: &str) {
+    RouteAgentDispatcher::<()>::new().send(RouteRequest::ChangeRoute(Route::new_no_state(path)));
+}
+
+pub fn current_path() -> String {
+    web_sys::window().unwrap().location().pathname().unwrap()
+}
+
+/// Sets the value like a user would, including the change event yew listens to.
+pub fn select(element: &Element, value: &str) {
+    element.unchecked_ref::<HtmlSelectElement>().set_value(value);
+    change(element);
+}
+
+pub fn type_into(element: &Element, value: &str) {
+    element.unchecked_ref::<HtmlInputElement>().set_value(value);
+    element.dispatch_event(&Event::new("input").unwrap()).unwrap();
+    change(element);
+}
+
+fn change(element: &Element) {
+    element.dispatch_event(&Event::new("change").unwrap()).unwrap();
+}
+
+/// How long agents, fetches and rendering may take before a test fails.
+const TIMEOUT_MS: f64 = 5000.0;
+
+/// Waits until the condition holds, i.e. an element is rendered, and fails the test after `TIMEOUT_MS`.
+pub async fn wait_until(what: &str, condition: impl Fn() -> bool) {
+    let start = js_sys::Date::now();
+    while !condition() {
+        if js_sys::Date::now() - start > TIMEOUT_MS {
+            panic!("Timed out waiting until {}.", what);
+        }
+        sleep(10).await;
+    }
+}
+
+async fn sleep(ms: i32) {
+    let promise = Promise::new(&mut |resolve, _| {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    JsFuture::from(promise).await.unwrap();
+}
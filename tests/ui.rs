@@ -0,0 +1,142 @@
+//! Browser tests of the user interface. Run with `wasm-pack test --headless --firefox` (or `--chrome`).
+//! The service agents talk to an in-process fake of the services (see `browser/mod.rs`).
+#![cfg(target_arch = "wasm32")]
+
+mod browser;
+
+use std::{cell::RefCell, rc::Rc};
+
+use browser::{current_path, requests, select, type_into, wait_until, Fixture};
+use fairris::pit_service::{self, PitService};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+use web_sys::{HtmlElement, HtmlInputElement, HtmlSelectElement};
+use yew::{agent::Bridged, Callback};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The PID which was added to the list.
+fn added_pid(before: &[String], after: &[String]) -> String {
+    let added: Vec<&String> = after.iter().filter(|pid| !before.contains(pid)).collect();
+    assert_eq!(added.len(), 1, "expected exactly one new object in {:?}", after);
+    added[0].clone()
+}
+
+async fn add_object(app: &Fixture) -> String {
+    let before = app.listed_pids();
+    app.button("Add").expect("Add button").click();
+    wait_until("the new object is listed", || app.listed_pids().len() > before.len()).await;
+    added_pid(&before, &app.listed_pids())
+}
+
+/// Clicks the object in the workspace list.
+async fn open(app: &Fixture, pid: &str) {
+    let item = app
+        .query_all(".piditem .fdo-button")
+        .into_iter()
+        .find(|button| button.text_content().unwrap_or_default().starts_with(pid))
+        .unwrap_or_else(|| panic!("{} is not listed", pid));
+    item.unchecked_into::<HtmlElement>().click();
+    let path = format!("/fdo/{}", pid);
+    wait_until("the details are shown", || current_path() == path && app.query("#version").is_some()).await;
+}
+
+fn version_input(app: &Fixture) -> HtmlInputElement {
+    app.query("#version").expect("version input").unchecked_into()
+}
+
+#[wasm_bindgen_test]
+async fn new_objects_are_listed_and_open_their_details() {
+    let app = Fixture::mount().await;
+    let pid = add_object(&app).await;
+    assert!(pid.starts_with("unregistered-"), "{}", pid);
+
+    open(&app, &pid).await;
+    assert_eq!(current_path(), format!("/fdo/{}", pid));
+    assert!(app.button("Edit object").is_some());
+    app.finish().await;
+}
+
+#[wasm_bindgen_test]
+async fn edit_mode_enables_inputs_and_hides_publishing() {
+    let app = Fixture::mount().await;
+    let pid = add_object(&app).await;
+    open(&app, &pid).await;
+    assert!(version_input(&app).disabled());
+
+    app.button("Edit object").unwrap().click();
+    wait_until("the inputs are enabled", || !version_input(&app).disabled()).await;
+    assert!(app.button("Publish object").is_none());
+
+    app.button("Save changes in object").unwrap().click();
+    wait_until("the inputs are disabled", || version_input(&app).disabled()).await;
+    assert!(app.button("Publish object").is_some());
+    app.finish().await;
+}
+
+#[wasm_bindgen_test]
+async fn publish_button_follows_the_state() {
+    let responses = Rc::new(RefCell::new(Vec::new()));
+    let recorder = responses.clone();
+    let _pit_service = PitService::bridge(Callback::from(move |r| recorder.borrow_mut().push(r)));
+
+    let app = Fixture::mount().await;
+    let unregistered = add_object(&app).await;
+    open(&app, &unregistered).await;
+    let publish = app.button("Publish object").expect("unregistered objects can be published");
+    assert!(!publish.unchecked_ref::<web_sys::HtmlButtonElement>().disabled());
+
+    let before = app.listed_pids();
+    publish.click();
+    wait_until("the registered object is listed", || {
+        app.listed_pids().iter().any(|pid| pid.starts_with("sandboxed/") && !before.contains(pid))
+    })
+    .await;
+    assert!(requests().contains(&"POST /api/v1/pit/pid/".to_string()));
+    assert!(responses
+        .borrow()
+        .iter()
+        .any(|r| matches!(r, pit_service::Response::Registered(pid, _) if pid.0 == unregistered)));
+    // the object is listed with the registered PID now.
+    let registered = added_pid(&before, &app.listed_pids());
+    assert!(registered.starts_with("sandboxed/"), "{}", registered);
+
+    open(&app, &registered).await;
+    let clean = app.button("Object state is published").expect("registered objects are clean");
+    assert!(clean.unchecked_ref::<web_sys::HtmlButtonElement>().disabled());
+
+    app.button("Edit object").unwrap().click();
+    wait_until("the inputs are enabled", || !version_input(&app).disabled()).await;
+    type_into(version_input(&app).as_ref(), "2.0.0");
+    app.button("Save changes in object").unwrap().click();
+    wait_until("the changes can be published", || app.button("Publish object changes").is_some()).await;
+    app.button("Publish object changes").unwrap().click();
+    wait_until("the changes are published", || app.button("Object state is published").is_some()).await;
+    assert!(requests().contains(&format!("PUT /api/v1/pit/pid/{}", registered)));
+    app.finish().await;
+}
+
+#[wasm_bindgen_test]
+async fn data_can_be_created_and_deselected() {
+    let app = Fixture::mount().await;
+    let pid = add_object(&app).await;
+    open(&app, &pid).await;
+    let chooser = || -> HtmlSelectElement { app.query("#data_chooser").expect("data chooser").unchecked_into() };
+    assert_eq!(chooser().value(), "new");
+
+    let create = app
+        .query_all("button.ok-button")
+        .into_iter()
+        .find(|b| b.text_content().unwrap_or_default().starts_with("Create "))
+        .expect("create data button");
+    create.unchecked_into::<HtmlElement>().click();
+    wait_until("the new data is selected", || chooser().value() != "new").await;
+    let selected = chooser().value();
+    assert_ne!(selected, "new");
+    assert!(selected.parse::<u16>().is_ok(), "{}", selected);
+
+    select(chooser().as_ref(), "new");
+    wait_until("the data form is shown", || app.text().contains("Type of your data:")).await;
+    assert_eq!(chooser().value(), "new");
+    app.finish().await;
+}